@@ -6,8 +6,26 @@ use num_traits::Float;
 
 use crate::property_map::PropertyType;
 use crate::property_map::PropertyMap;
+use crate::property_map::PropertyInterpolation;
+use crate::property_map::Blend;
+use crate::property_map::blend_values;
+use crate::property_map::vote_values;
+use crate::property_map::get_dominant_source;
 use crate::vector::{FloatVector, Vec3};
 
+/// Weighted sum of arrays of floats, where the weights sum up to one
+fn blend_arrays<U : Float, const N : usize>(values : impl Iterator<Item = ([U; N], f64)>) -> [U; N] {
+
+    let mut result = [U::zero(); N];
+    for (value, weight) in values {
+        let weight = U::from(weight).unwrap();
+        for (target, component) in result.iter_mut().zip(value) {
+            *target = *target + component * weight;
+        }
+    }
+    return result;
+}
+
 pub struct NormalMap<T : Vec3<U> + FloatVector<U>, U : Num + Float + PartialOrd<U>> {
 
     data : Vec<T>,
@@ -65,8 +83,386 @@ impl<T : Vec3<U> + FloatVector<U> + 'static, U : Num + Float + PartialOrd<U> + '
         return self.data.len();
     }
 
-    fn property_type() -> PropertyType {
-        return PropertyType::NORMAL;
+    fn property_type() -> Option<PropertyType> {
+        return Some(PropertyType::NORMAL);
+    }
+
+    /// Blends the normals and normalizes the result. Opposite normals, which cancel each other out, are copied instead.
+    fn interpolate(&self, sources : &[(usize, f64)]) -> T {
+
+        let mut sum = T::zero();
+        for (idx, weight) in sources {
+            sum += self.data[*idx] * U::from(*weight).unwrap();
+        }
+        return sum.normalize().unwrap_or(self.data[get_dominant_source(sources)]);
+    }
+
+}
+
+/// Tangents with the handedness of the tangent frame in the fourth component, which is either 1 or -1.
+/// The bitangent is the cross product of the normal and the tangent, multiplied by the handedness.
+pub struct TangentMap<U : Num + Float + PartialOrd<U>> {
+
+    data : Vec<[U; 4]>,
+}
+
+impl<U : Num + Float + PartialOrd<U>> TangentMap<U> {
+
+    pub fn with_size(size : usize, default_value : [U; 4]) -> Self {
+        Self {
+            data : vec![default_value; size],
+        }
+    }
+}
+
+impl<U : Num + Float + PartialOrd<U> + 'static> PropertyMap<[U; 4]> for TangentMap<U> {
+
+    fn new() -> Self {
+        Self {
+            data : Vec::<[U; 4]>::new(),
+        }
+    }
+
+    fn with_capacity(capacity : usize) -> Self {
+        Self {
+            data : Vec::<[U; 4]>::with_capacity(capacity),
+        }
+    }
+
+    fn push(&mut self, value : [U; 4]) {
+        self.data.push(value);
+    }
+
+    fn set(&mut self, idx : usize, value : [U; 4]) {
+        self.data[idx] = value;
+    }
+
+    fn get(&self, idx : usize) -> [U; 4] {
+        return self.data[idx];
+    }
+
+    fn len(&self) -> usize {
+        return self.data.len();
+    }
+
+    fn property_type() -> Option<PropertyType> {
+        return Some(PropertyType::TANGENT);
+    }
+
+    /// Blends and normalizes the tangents, while the handedness is copied from the source with the highest weight
+    fn interpolate(&self, sources : &[(usize, f64)]) -> [U; 4] {
+
+        let dominant = self.data[get_dominant_source(sources)];
+        let mut tangent = blend_arrays(sources.iter().map(|(idx, weight)| (self.data[*idx], *weight)));
+
+        let length = (tangent[0] * tangent[0] + tangent[1] * tangent[1] + tangent[2] * tangent[2]).sqrt();
+        if length == U::zero() {
+            return dominant;
+        }
+        for component in tangent.iter_mut().take(3) {
+            *component = *component / length;
+        }
+        tangent[3] = dominant[3];
+        return tangent;
+    }
+
+}
+
+/// Texture coordinates with one or more channels (also known as UV sets), where each element has one coordinate per channel.
+/// The value of an element is the list of its coordinates, ordered by channel.
+pub struct UvMap<U : Num + Float + PartialOrd<U>> {
+
+    channels : Vec<Vec<[U; 2]>>,
+    len : usize,
+    /// Elements reserved for each channel, once the first element of an empty map determines the number of channels
+    capacity : usize,
+}
+
+impl<U : Num + Float + PartialOrd<U>> UvMap<U> {
+
+    /// Creates a map with a single channel
+    ///
+    /// # Examples
+    /// ```
+    /// let uvs = UvMap::from(Vec::from([[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]));
+    /// mesh.add_vertex_property(uvs);
+    /// ```
+    pub fn from(coordinates : Vec<[U; 2]>) -> Self {
+        Self {
+            len : coordinates.len(),
+            channels : Vec::from([coordinates]),
+            capacity : 0,
+        }
+    }
+
+    /// Adds another channel, e.g. for lightmap coordinates. Returns false, if the channel has a different amount of elements.
+    pub fn add_channel(&mut self, coordinates : Vec<[U; 2]>) -> bool {
+
+        if !self.channels.is_empty() && coordinates.len() != self.len {
+            return false;
+        }
+
+        self.len = coordinates.len();
+        self.channels.push(coordinates);
+        return true;
+    }
+
+    pub fn get_channel_count(&self) -> usize {
+        return self.channels.len();
+    }
+
+    pub fn get_channel(&self, channel : usize) -> &[[U; 2]] {
+        return &self.channels[channel];
+    }
+
+    pub fn get_uv(&self, idx : usize, channel : usize) -> [U; 2] {
+        return self.channels[channel][idx];
+    }
+
+    pub fn set_uv(&mut self, idx : usize, channel : usize, uv : [U; 2]) {
+        self.channels[channel][idx] = uv;
+    }
+}
+
+impl<U : Num + Float + PartialOrd<U> + 'static> PropertyMap<Vec<[U; 2]>> for UvMap<U> {
+
+    fn new() -> Self {
+        return Self::with_capacity(0);
+    }
+
+    fn with_capacity(capacity : usize) -> Self {
+        Self {
+            channels : Vec::<Vec<[U; 2]>>::new(),
+            len : 0,
+            capacity,
+        }
+    }
+
+    /// Adds an element with one coordinate per channel. The first element determines the number of channels of an empty map.
+    fn push(&mut self, value : Vec<[U; 2]>) {
+
+        if self.len == 0 && self.channels.is_empty() {
+            self.channels = (0..value.len()).map(|_| Vec::<[U; 2]>::with_capacity(self.capacity)).collect();
+        }
+
+        for (channel, uv) in self.channels.iter_mut().zip(value) {
+            channel.push(uv);
+        }
+        self.len += 1;
+    }
+
+    fn set(&mut self, idx : usize, value : Vec<[U; 2]>) {
+        for (channel, uv) in self.channels.iter_mut().zip(value) {
+            channel[idx] = uv;
+        }
+    }
+
+    fn get(&self, idx : usize) -> Vec<[U; 2]> {
+        return self.channels.iter().map(|channel| channel[idx]).collect();
+    }
+
+    fn len(&self) -> usize {
+        return self.len;
+    }
+
+    fn property_type() -> Option<PropertyType> {
+        return Some(PropertyType::UV);
+    }
+
+    fn interpolate(&self, sources : &[(usize, f64)]) -> Vec<[U; 2]> {
+        return self.channels.iter().map(|channel| blend_arrays(sources.iter().map(|(idx, weight)| (channel[*idx], *weight)))).collect();
+    }
+
+}
+
+
+/// Colors with three (RGB) or four (RGBA) components, usually in the range from 0 to 1
+pub struct ColorMap<U : Num + Float + PartialOrd<U>, const CHANNELS : usize> {
+
+    data : Vec<[U; CHANNELS]>,
+}
+
+pub type RgbMap<U> = ColorMap<U, 3>;
+pub type RgbaMap<U> = ColorMap<U, 4>;
+
+impl<U : Num + Float + PartialOrd<U>, const CHANNELS : usize> ColorMap<U, CHANNELS> {
+
+    pub fn with_size(size : usize, default_value : [U; CHANNELS]) -> Self {
+        Self {
+            data : vec![default_value; size],
+        }
+    }
+
+    pub fn from(colors : Vec<[U; CHANNELS]>) -> Self {
+        Self {
+            data : colors,
+        }
+    }
+
+    /// Returns the color of an element with four components, where RGB colors are opaque
+    pub fn get_rgba(&self, idx : usize) -> [U; 4] {
+
+        let mut rgba = [U::one(); 4];
+        for (target, source) in rgba.iter_mut().zip(self.data[idx]) {
+            *target = source;
+        }
+        return rgba;
+    }
+}
+
+impl<U : Num + Float + PartialOrd<U> + 'static, const CHANNELS : usize> PropertyMap<[U; CHANNELS]> for ColorMap<U, CHANNELS> {
+
+    fn new() -> Self {
+        Self {
+            data : Vec::<[U; CHANNELS]>::new(),
+        }
+    }
+
+    fn with_capacity(capacity : usize) -> Self {
+        Self {
+            data : Vec::<[U; CHANNELS]>::with_capacity(capacity),
+        }
+    }
+
+    fn push(&mut self, value : [U; CHANNELS]) {
+        self.data.push(value);
+    }
+
+    fn set(&mut self, idx : usize, value : [U; CHANNELS]) {
+        self.data[idx] = value;
+    }
+
+    fn get(&self, idx : usize) -> [U; CHANNELS] {
+        return self.data[idx];
+    }
+
+    fn len(&self) -> usize {
+        return self.data.len();
+    }
+
+    fn property_type() -> Option<PropertyType> {
+        return Some(PropertyType::COLOR);
+    }
+
+    fn interpolate(&self, sources : &[(usize, f64)]) -> [U; CHANNELS] {
+        return blend_arrays(sources.iter().map(|(idx, weight)| (self.data[*idx], *weight)));
+    }
+
+}
+
+/// Values of an arbitrary type, e.g. curvatures, segmentation labels or weights. Maps of this type have no built-in
+/// property, so they are usually added with a [PropertyHandle](crate::property_map::PropertyHandle), which gives them a name.
+/// When elements are created from several old ones, the values are copied, unless the map is set to blend or vote on them.
+///
+/// # Examples
+/// ```
+/// let labels = PropertyHandle::<AttributeMap<u32>>::named("segment");
+/// mesh.add_named_face_property(&labels, AttributeMap::with_size(mesh.get_face_count(), 0).with_voting());
+/// ```
+pub struct AttributeMap<T : Clone> {
+
+    data : Vec<T>,
+    default_value : Option<T>,
+    interpolation : PropertyInterpolation,
+    interpolate_values : fn(&Self, &[(usize, f64)]) -> T,
+}
+
+impl<T : Clone> AttributeMap<T> {
+
+    /// Creates a map, where all elements have the default value. The default value is also used for new elements,
+    /// which are not made of old ones.
+    pub fn with_size(size : usize, default_value : T) -> Self {
+        return Self::from(vec![default_value.clone(); size]).with_default(default_value);
+    }
+
+    pub fn from(values : Vec<T>) -> Self {
+        Self {
+            data : values,
+            default_value : None,
+            interpolation : PropertyInterpolation::COPY,
+            interpolate_values : copy_values::<T>,
+        }
+    }
+
+    /// Sets the value of new elements, which are not made of old ones, e.g. edges inside a subdivided face
+    pub fn with_default(mut self, default_value : T) -> Self {
+        self.default_value = Some(default_value);
+        return self;
+    }
+
+    /// Blends the values of new elements, e.g. for weights or scalar fields
+    pub fn with_blending(mut self) -> Self where T : Blend {
+        self.interpolation = PropertyInterpolation::BLEND;
+        self.interpolate_values = blend_values::<Self, T>;
+        return self;
+    }
+
+    /// Takes the value with the highest weight of all old elements for new elements, e.g. for labels
+    pub fn with_voting(mut self) -> Self where T : PartialEq {
+        self.interpolation = PropertyInterpolation::VOTE;
+        self.interpolate_values = vote_values::<Self, T>;
+        return self;
+    }
+
+    pub fn get_interpolation(&self) -> PropertyInterpolation {
+        return self.interpolation;
+    }
+
+    pub fn get_ref(&self, idx : usize) -> &T {
+        return &self.data[idx];
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        return &self.data;
+    }
+}
+
+fn copy_values<T : Clone>(map : &AttributeMap<T>, sources : &[(usize, f64)]) -> T {
+    return map.data[get_dominant_source(sources)].clone();
+}
+
+impl<T : Clone> PropertyMap<T> for AttributeMap<T> {
+
+    fn new() -> Self {
+        return Self::from(Vec::<T>::new());
+    }
+
+    fn with_capacity(capacity : usize) -> Self {
+        return Self::from(Vec::<T>::with_capacity(capacity));
+    }
+
+    /// Creates an empty map, which combines values in the same way as this one
+    fn new_like(&self, capacity : usize) -> Self {
+        Self {
+            data : Vec::<T>::with_capacity(capacity),
+            default_value : self.default_value.clone(),
+            interpolation : self.interpolation,
+            interpolate_values : self.interpolate_values,
+        }
+    }
+
+    fn push(&mut self, value : T) {
+        self.data.push(value);
+    }
+
+    fn set(&mut self, idx : usize, value : T) {
+        self.data[idx] = value;
+    }
+
+    fn get(&self, idx : usize) -> T {
+        return self.data[idx].clone();
+    }
+
+    fn len(&self) -> usize {
+        return self.data.len();
+    }
+
+    fn interpolate(&self, sources : &[(usize, f64)]) -> T {
+        return (self.interpolate_values)(self, sources);
     }
 
-}
\ No newline at end of file
+    fn get_default(&self) -> Option<T> {
+        return self.default_value.clone();
+    }
+
+}
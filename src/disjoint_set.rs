@@ -0,0 +1,99 @@
+//! # Disjoint Sets
+//!
+//! Also known as Union-Find. Keeps track of a partition of the elements 0..n into disjoint sets,
+//! where two sets can be merged and the set of an element can be found in nearly constant time.
+
+pub struct DisjointSet {
+
+    parents : Vec<usize>,
+    ranks : Vec<u8>,
+}
+
+impl DisjointSet {
+
+    /// Creates a partition of the given amount of elements, where each element is in a set of its own
+    pub fn new(size : usize) -> Self {
+        Self {
+            parents: (0..size).collect(),
+            ranks: vec![0; size],
+        }
+    }
+
+    /// Returns the representative of the set containing the element. Two elements are in the same set
+    /// if and only if they have the same representative.
+    pub fn find(&mut self, element : usize) -> usize {
+
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        //Path compression
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        return root;
+    }
+
+    /// Merges the sets of both elements. Returns false, if they already were in the same set.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut sets = DisjointSet::new(3);
+    /// sets.union(0, 2);
+    ///
+    /// let same = sets.find(0) == sets.find(2); //Returns true
+    /// ```
+    pub fn union(&mut self, a : usize, b : usize) -> bool {
+
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+
+        if root_a == root_b {
+            return false;
+        }
+
+        if self.ranks[root_a] < self.ranks[root_b] {
+            self.parents[root_a] = root_b;
+        } else if self.ranks[root_a] > self.ranks[root_b] {
+            self.parents[root_b] = root_a;
+        } else {
+            self.parents[root_b] = root_a;
+            self.ranks[root_a] += 1;
+        }
+        return true;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.parents.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.parents.is_empty();
+    }
+}
+
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::disjoint_set::DisjointSet;
+
+    #[test]
+    fn test_union_find() {
+
+        let mut sets = DisjointSet::new(5);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+
+        assert_eq!(sets.find(0), sets.find(3));
+        assert_ne!(sets.find(0), sets.find(2));
+    }
+}
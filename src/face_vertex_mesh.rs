@@ -0,0 +1,467 @@
+//! #Face-Vertex Meshes
+//!
+//! The Face-Vertex Mesh is the general form of the Triangle-Vertex Mesh. Each face is a list of indices
+//! referencing the vertices of the mesh, but faces can have an arbitrary amount of corners (at least three).
+//! The indices of all faces are stored in one consecutive list, together with the offsets where each face starts.
+
+use std::marker::PhantomData;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::property_map::PropertyStore;
+use crate::property_map::ElementSources;
+use crate::property_map::VertexProperties;
+use crate::property_map::FaceProperties;
+use crate::property_map::EdgeProperties;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+use crate::mesh::Mesh;
+use crate::mesh_components::MeshComponent;
+use crate::mesh_edges::MeshEdges;
+use crate::mesh_edges::get_edge_sources;
+use crate::mesh_triangulation::face_triangulation::triangulate_face;
+use crate::triangle_vertex_mesh::TriangleVertexMesh;
+
+use num_traits::PrimInt;
+use num_traits::Num;
+use num_traits::NumCast;
+use num_traits::Unsigned;
+use num_traits::Float;
+
+#[repr(C)]
+pub struct FaceVertexMesh<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> {
+
+    vertices : Vec<T>,
+    indices : Vec<IndexType>,
+    face_offsets : Vec<usize>,
+    vertex_properties : PropertyStore,
+    edge_properties : PropertyStore,
+    face_properties : PropertyStore,
+    number_type : PhantomData<U>
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U: Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> FaceVertexMesh<T, U, IndexType> {
+
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::<T>::new(),
+            indices: Vec::<IndexType>::new(),
+            face_offsets: Vec::from([0]),
+            vertex_properties: PropertyStore::new(),
+            edge_properties: PropertyStore::new(),
+            face_properties: PropertyStore::new(),
+            number_type: PhantomData
+        }
+    }
+
+    /// Construct a Face-Vertex Mesh by providing a list of vertices and a list of faces.
+    /// Note that each face must consist of at least three indices.
+    pub fn from(vertices : Vec<T>, faces : Vec<Vec<IndexType>>) -> Option<Self> {
+
+        let mut mesh = Self::new();
+        mesh.vertices = vertices;
+
+        for face in faces {
+            if !mesh.push_face(&face) {
+                return None;
+            }
+        }
+
+        return Some(mesh);
+    }
+
+    /// Adds a face to the end of the list of faces. Returns false and leaves the mesh unchanged,
+    /// if the face has less than three indices.
+    pub fn push_face(&mut self, face : &[IndexType]) -> bool {
+
+        if face.len() < 3 {
+            return false;
+        }
+
+        self.indices.extend_from_slice(face);
+        self.face_offsets.push(self.indices.len());
+        return true;
+    }
+
+    /// Returns the indices of all faces, stored consecutively. Use [FaceVertexMesh::get_face_offsets]
+    /// to find where each face starts.
+    pub fn get_indices(&self) -> &Vec<IndexType> {
+        return &self.indices;
+    }
+
+    /// Returns the start offset of each face within the list of indices. The list contains one additional
+    /// entry at the end, which is the total amount of indices.
+    pub fn get_face_offsets(&self) -> &Vec<usize> {
+        return &self.face_offsets;
+    }
+
+    /// Splits all faces into triangles with [triangulate_face] and returns the result as a Triangle-Vertex Mesh.
+    /// The vertices and their properties stay the same, while each triangle receives the properties of its face.
+    /// Edges of the faces keep their properties, while the new edges inside the faces receive the default values, or the values
+    /// of neighbouring edges for maps without a default value.
+    ///
+    /// # Examples
+    /// ```
+    /// let quads = FaceVertexMesh::<Vec3f, f32, u32>::from(vertices, faces).unwrap();
+    /// let triangles = quads.triangulate();
+    /// ```
+    pub fn triangulate(&self) -> TriangleVertexMesh<T, U, IndexType> {
+
+        let mut indices = Vec::<IndexType>::with_capacity(self.indices.len() * 3);
+        let mut face_sources = Vec::<usize>::with_capacity(self.indices.len());
+
+        for f in 0..self.get_face_count() {
+            let triangles = triangulate_face(&self.vertices, self.get_face_slice(f));
+            face_sources.extend(std::iter::repeat_n(f, triangles.len() / 3));
+            indices.extend(triangles);
+        }
+
+        let vertex_sources = (0..self.vertices.len()).collect::<Vec<usize>>();
+
+        let mut edge_properties = PropertyStore::new();
+        if !self.edge_properties.is_empty() {
+            let old_edges = self.make_edges();
+            let new_edges = MeshEdges::from_faces(indices.chunks(3).map(|t| t.iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>()));
+            let single_sources = vertex_sources.iter().map(|v| Vec::from([(*v, 1.0)])).collect::<Vec<ElementSources>>();
+            edge_properties = self.edge_properties.interpolate(old_edges.get_edge_count(), &get_edge_sources(&old_edges, &new_edges, &single_sources));
+        }
+
+        return TriangleVertexMesh::from_parts(
+            self.vertices.clone(),
+            indices,
+            self.vertex_properties.select(self.vertices.len(), &vertex_sources),
+            edge_properties,
+            self.face_properties.select(self.get_face_count(), &face_sources));
+    }
+
+    /// Replaces all vertices and faces, carrying the property maps over to the new elements, like
+    /// [TriangleVertexMesh::replace]. Edges are carried over by matching their vertices with [get_edge_sources].
+    pub(in crate) fn replace(&mut self, vertices : Vec<T>, vertex_sources : &[ElementSources], faces : &[Vec<IndexType>], face_sources : &[ElementSources]) {
+
+        let face_offsets = std::iter::once(0).chain(faces.iter().scan(0, |offset, face| {
+            *offset += face.len();
+            return Some(*offset);
+        })).collect::<Vec<usize>>();
+
+        if !self.edge_properties.is_empty() {
+            let old_edges = self.make_edges();
+            let new_edges = MeshEdges::from_faces(faces.iter().map(|face| face.iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>()));
+            self.edge_properties = self.edge_properties.interpolate(old_edges.get_edge_count(), &get_edge_sources(&old_edges, &new_edges, vertex_sources));
+        }
+
+        self.vertex_properties = self.vertex_properties.interpolate(self.vertices.len(), vertex_sources);
+        self.face_properties = self.face_properties.interpolate(self.get_face_count(), face_sources);
+        self.vertices = vertices;
+        self.indices = faces.concat();
+        self.face_offsets = face_offsets;
+    }
+
+    // Private
+
+    fn make_edges(&self) -> MeshEdges {
+        return MeshEdges::from_faces((0..self.get_face_count()).map(|f| {
+            return self.get_face_slice(f).iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>();
+        }));
+    }
+
+    fn get_face_slice(&self, face_idx : usize) -> &[IndexType] {
+        return &self.indices[self.face_offsets[face_idx]..self.face_offsets[face_idx + 1]];
+    }
+
+    fn insert_incidence(map : &mut HashMap<IndexType, HashSet<IndexType>>, origin : IndexType, incident : IndexType) {
+        map.entry(origin).or_default().insert(incident);
+    }
+
+    fn make_vertex_vertex_incidence_map(&self, map : &mut HashMap<IndexType, HashSet<IndexType>>) {
+
+        for f in 0..self.get_face_count() {
+
+            let face = self.get_face_slice(f);
+            let size = face.len();
+
+            for i in 0..size {
+
+                let v_idx_a = face[i];
+                let v_idx_b = face[(i + 1) % size];
+
+                Self::insert_incidence(map, v_idx_a, v_idx_b);
+                Self::insert_incidence(map, v_idx_b, v_idx_a);
+            }
+        }
+    }
+
+    fn make_vertex_face_incidence_map(&self, map : &mut HashMap<IndexType, HashSet<IndexType>>) {
+
+        for f in 0..self.get_face_count() {
+
+            let f_idx = NumCast::from(f).unwrap();
+            for v_idx in self.get_face_slice(f) {
+                Self::insert_incidence(map, *v_idx, f_idx);
+            }
+        }
+    }
+
+    fn make_face_vertex_incidence_map(&self, map : &mut HashMap<IndexType, HashSet<IndexType>>) {
+
+        for f in 0..self.get_face_count() {
+
+            let f_idx = NumCast::from(f).unwrap();
+            map.insert(f_idx, HashSet::<IndexType>::new());
+            for v_idx in self.get_face_slice(f) {
+                Self::insert_incidence(map, f_idx, *v_idx);
+            }
+        }
+    }
+
+    fn make_face_face_incidence_map(&self, map : &mut HashMap<IndexType, HashSet<IndexType>>) {
+
+        //Faces are neighbours, if they share an edge
+        let mut edge_faces = HashMap::<(IndexType, IndexType), Vec<IndexType>>::new();
+
+        for f in 0..self.get_face_count() {
+
+            let f_idx = NumCast::from(f).unwrap();
+            let face = self.get_face_slice(f);
+            let size = face.len();
+
+            for i in 0..size {
+
+                let a = face[i];
+                let b = face[(i + 1) % size];
+                let key = if a < b { (a, b) } else { (b, a) };
+
+                edge_faces.entry(key).or_default().push(f_idx);
+            }
+        }
+
+        for faces in edge_faces.values() {
+            for f_idx_a in faces {
+                for f_idx_b in faces {
+                    if f_idx_a != f_idx_b {
+                        Self::insert_incidence(map, *f_idx_a, *f_idx_b);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> Default for FaceVertexMesh<T, U, IndexType> {
+
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> VertexProperties for FaceVertexMesh<T, U, IndexType> {
+
+    fn get_vertex_properties(&self) -> &PropertyStore {
+        return &self.vertex_properties;
+    }
+
+    fn get_vertex_properties_mut(&mut self) -> &mut PropertyStore {
+        return &mut self.vertex_properties;
+    }
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> EdgeProperties for FaceVertexMesh<T, U, IndexType> {
+
+    fn get_edge_properties(&self) -> &PropertyStore {
+        return &self.edge_properties;
+    }
+
+    fn get_edge_properties_mut(&mut self) -> &mut PropertyStore {
+        return &mut self.edge_properties;
+    }
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> FaceProperties for FaceVertexMesh<T, U, IndexType> {
+
+    fn get_face_properties(&self) -> &PropertyStore {
+        return &self.face_properties;
+    }
+
+    fn get_face_properties_mut(&mut self) -> &mut PropertyStore {
+        return &mut self.face_properties;
+    }
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> Mesh<T, U, IndexType> for FaceVertexMesh<T, U, IndexType> {
+
+    fn make_incidence_map(&self, origin_type : MeshComponent, incident_type : MeshComponent) -> HashMap<IndexType, HashSet<IndexType>> {
+
+        let mut map = HashMap::<IndexType, HashSet<IndexType>>::new();
+
+        match origin_type {
+
+            MeshComponent::VERTEX =>
+                match incident_type {
+                    MeshComponent::VERTEX => self.make_vertex_vertex_incidence_map(&mut map),
+                    MeshComponent::EDGE => self.make_edges().make_incidence_map(origin_type, incident_type, &mut map),
+                    MeshComponent::FACE => self.make_vertex_face_incidence_map(&mut map)
+                }
+            MeshComponent::EDGE => self.make_edges().make_incidence_map(origin_type, incident_type, &mut map),
+            MeshComponent::FACE =>
+                match incident_type {
+                    MeshComponent::VERTEX => self.make_face_vertex_incidence_map(&mut map),
+                    MeshComponent::EDGE => self.make_edges().make_incidence_map(origin_type, incident_type, &mut map),
+                    MeshComponent::FACE => self.make_face_face_incidence_map(&mut map)
+                }
+        }
+
+        return map;
+    }
+
+    fn get_face(&self, idx: IndexType) -> Vec<IndexType> {
+        return self.get_face_slice(idx.to_usize().unwrap()).to_vec();
+    }
+
+    fn get_face_count(&self) -> usize {
+        return self.face_offsets.len() - 1;
+    }
+
+    fn get_vertices(&self) -> &Vec<T> {
+        return &self.vertices;
+    }
+}
+
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::face_vertex_mesh::FaceVertexMesh;
+    use crate::mesh::Mesh;
+    use crate::mesh_components::MeshComponent;
+    use crate::vector::Vec3f;
+    use crate::vector::Vec3;
+    use crate::common_properties::AttributeMap;
+    use crate::common_properties::RgbMap;
+    use crate::property_map::FaceProperties;
+    use crate::property_map::EdgeProperties;
+    use crate::mesh_edges::MeshEdges;
+    use crate::property_map::PropertyHandle;
+    use crate::property_map::PropertyName;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
+
+    fn create_unit_cube() -> FaceVertexMesh<Vec3f, f32, u32> {
+
+        //Unit Cube
+        let vertices = Vec::from(
+            [Vec3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            Vec3f::new(1.0, 1.0, 0.0),
+            Vec3f::new(0.0, 0.0, 1.0),
+            Vec3f::new(1.0, 0.0, 1.0),
+            Vec3f::new(0.0, 1.0, 1.0),
+            Vec3f::new(1.0, 1.0, 1.0),
+            ]);
+
+        let faces = Vec::from(
+            [
+                Vec::from([0, 1, 3, 2]),
+                Vec::from([5, 4, 6, 7]),
+                Vec::from([4, 0, 2, 6]),
+                Vec::from([1, 5, 7, 3]),
+                Vec::from([2, 3, 7, 6]),
+                Vec::from([4, 5, 1, 0])
+            ]);
+
+        return FaceVertexMesh::from(vertices, faces).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_face() {
+
+        let vertices = Vec::from([Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0)]);
+        let mesh = FaceVertexMesh::<Vec3f, f32, u32>::from(vertices, Vec::from([Vec::from([0, 1])]));
+
+        assert!(mesh.is_none());
+    }
+
+    #[test]
+    fn test_get_face() {
+
+        let ucube = create_unit_cube();
+
+        assert_eq!(ucube.get_face_count(), 6);
+        assert_eq!(ucube.get_face(1), Vec::from([5, 4, 6, 7]));
+    }
+
+    #[test]
+    fn test_vertex_vertex_map() {
+
+        let ucube = create_unit_cube();
+
+        let vv_map = ucube.make_incidence_map(MeshComponent::VERTEX, MeshComponent::VERTEX);
+        assert_eq!(vv_map.len(), 8);
+        assert_eq!(vv_map.get(&0).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_face_face_map() {
+
+        let ucube = create_unit_cube();
+
+        let face_face_map = ucube.make_incidence_map(MeshComponent::FACE, MeshComponent::FACE);
+        assert_eq!(face_face_map.len(), 6);
+        assert_eq!(face_face_map.get(&0).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_triangulate() {
+
+        let mut ucube = create_unit_cube();
+        let labels = PropertyHandle::<AttributeMap<u32>>::named("labels");
+
+        ucube.add_vertex_property(RgbMap::from((0..8).map(|v| [v as f32, 0.0, 0.0]).collect::<Vec<[f32; 3]>>()));
+        ucube.add_named_face_property(&labels, AttributeMap::from(Vec::from([10, 11, 12, 13, 14, 15])));
+
+        let triangles = ucube.triangulate();
+        assert_eq!(triangles.get_face_count(), 12);
+        assert_eq!(triangles.get_vertices().len(), 8);
+
+        let colors = triangles.get_vertex_property_ref::<RgbMap<f32>, [f32; 3]>(PropertyType::COLOR).unwrap();
+        assert_eq!(colors.get(7), [7.0, 0.0, 0.0]);
+
+        let triangle_labels = triangles.get_named_face_property_ref(&labels).unwrap();
+        assert_eq!(triangle_labels.len(), 12);
+        for t in 0..12 {
+            assert_eq!(triangle_labels.get(t), 10 + t as u32 / 2);
+        }
+    }
+
+    #[test]
+    fn test_triangulate_edge_properties() {
+
+        let mut ucube = create_unit_cube();
+        let seams = PropertyHandle::<AttributeMap<u8>>::named("seams");
+
+        assert_eq!(ucube.make_incidence_map(MeshComponent::FACE, MeshComponent::EDGE)[&0].len(), 4);
+        ucube.add_named_edge_property(&seams, AttributeMap::from(vec![1; 12]).with_default(0));
+
+        //The diagonals inside the quads are new edges, which receive the default value. Without one, they take the value
+        //of a neighbouring edge.
+        let triangles = ucube.triangulate();
+        let edges = MeshEdges::from_mesh(&triangles);
+        let triangle_seams = triangles.get_named_edge_property_ref(&seams).unwrap();
+
+        assert_eq!(edges.get_edge_count(), 18);
+        assert_eq!(triangle_seams.as_slice().iter().filter(|seam| **seam == 1).count(), 12);
+
+        ucube.remove_edge_property(seams.get_name());
+        ucube.add_named_edge_property(&seams, AttributeMap::from(vec![1; 12]));
+        let mut triangles = ucube.triangulate();
+        assert_eq!(triangles.get_named_edge_property_ref(&seams).unwrap().as_slice(), [1; 18]);
+        assert!(triangles.get_edge_properties_mut().take_dropped_names().is_empty());
+
+        //Out of date maps are dropped and reported
+        ucube.add_named_vertex_property(&PropertyHandle::<AttributeMap<u8>>::named("stale"), AttributeMap::from(vec![1; 5]));
+        let mut triangles = ucube.triangulate();
+        assert_eq!(triangles.get_vertex_properties_mut().take_dropped_names(), Vec::from([PropertyName::NAMED("stale".into())]));
+        assert!(triangles.get_vertex_properties_mut().take_dropped_names().is_empty());
+    }
+}
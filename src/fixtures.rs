@@ -0,0 +1,101 @@
+//! # Fixtures
+//!
+//! Meshes shared by the unit tests of the mesh algorithms
+
+use std::collections::HashMap;
+
+use num_traits::Num;
+use num_traits::Float;
+
+use crate::face_vertex_mesh::FaceVertexMesh;
+use crate::mesh::Mesh;
+use crate::triangle_vertex_mesh::TriangleVertexMesh;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+/// Octahedron with its vertices on the axes at distance one from the origin and outward facing triangles
+pub(in crate) fn create_octahedron<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>() -> TriangleVertexMesh<T, U, u32> {
+
+    let (zero, one) = (U::zero(), U::one());
+    let vertices = Vec::from([
+        T::new(one, zero, zero), T::new(-one, zero, zero),
+        T::new(zero, one, zero), T::new(zero, -one, zero),
+        T::new(zero, zero, one), T::new(zero, zero, -one)]);
+
+    let indices = Vec::from([
+        0, 2, 4, 2, 1, 4, 1, 3, 4, 3, 0, 4,
+        2, 0, 5, 1, 2, 5, 3, 1, 5, 0, 3, 5]);
+
+    return TriangleVertexMesh::from(vertices, indices).unwrap();
+}
+
+/// Sphere around the origin, made of an octahedron whose triangles are split into four at their edge midpoints in each
+/// iteration, with all vertices moved onto the sphere. It is built by hand, so it does not depend on the subdivision code.
+pub(in crate) fn create_sphere<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(iterations : usize, radius : U) -> TriangleVertexMesh<T, U, u32> {
+
+    let octahedron = create_octahedron::<T, U>();
+    let mut vertices = octahedron.get_vertices().clone();
+    let mut indices = octahedron.get_indices().clone();
+
+    for _ in 0..iterations {
+
+        let mut midpoints = HashMap::<(u32, u32), u32>::new();
+        let mut midpoint = |a : u32, b : u32, vertices : &mut Vec<T>| -> u32 {
+            return *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let m = (vertices[a as usize] + vertices[b as usize]) * U::from(0.5).unwrap();
+                vertices.push(m * (U::one() / m.length()));
+                return vertices.len() as u32 - 1;
+            });
+        };
+
+        let mut refined = Vec::<u32>::with_capacity(indices.len() * 4);
+        for t in indices.chunks(3) {
+            let ab = midpoint(t[0], t[1], &mut vertices);
+            let bc = midpoint(t[1], t[2], &mut vertices);
+            let ca = midpoint(t[2], t[0], &mut vertices);
+            refined.extend([t[0], ab, ca, ab, t[1], bc, ca, bc, t[2], ab, bc, ca]);
+        }
+        indices = refined;
+    }
+
+    return TriangleVertexMesh::from(vertices.iter().map(|v| *v * radius).collect(), indices).unwrap();
+}
+
+/// Planar grid of n x n quads in the unit square, split into two triangles each
+pub(in crate) fn create_grid<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(n : u32) -> TriangleVertexMesh<T, U, u32> {
+    return create_noisy_grid(n, U::zero());
+}
+
+/// Grid of n x n quads in the unit square, split into two triangles each, with pseudo random noise along z
+pub(in crate) fn create_noisy_grid<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(n : u32, noise : U) -> TriangleVertexMesh<T, U, u32> {
+
+    let coordinate = |i : u32| -> U { U::from(i).unwrap() / U::from(n).unwrap() };
+    let vertices = (0..(n + 1) * (n + 1)).map(|v| {
+        let offset = U::from(((v * 7919) % 13) as f64 / 12.0 - 0.5).unwrap();
+        return T::new(coordinate(v % (n + 1)), coordinate(v / (n + 1)), offset * noise);
+    }).collect();
+
+    let mut indices = Vec::new();
+    for y in 0..n {
+        for x in 0..n {
+            let v = y * (n + 1) + x;
+            indices.extend([v, v + 1, v + n + 2, v, v + n + 2, v + n + 1]);
+        }
+    }
+    return TriangleVertexMesh::from(vertices, indices).unwrap();
+}
+
+/// Axis aligned box between two corners with outward facing quads
+pub(in crate) fn create_box<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(min : T, max : T) -> FaceVertexMesh<T, U, u32> {
+
+    let vertices = (0..8).map(|v| T::new(
+        if v & 1 == 0 { min.x() } else { max.x() },
+        if v & 2 == 0 { min.y() } else { max.y() },
+        if v & 4 == 0 { min.z() } else { max.z() })).collect::<Vec<T>>();
+
+    let faces = Vec::from([
+        Vec::from([2, 3, 1, 0]), Vec::from([7, 6, 4, 5]), Vec::from([6, 2, 0, 4]),
+        Vec::from([3, 7, 5, 1]), Vec::from([6, 7, 3, 2]), Vec::from([0, 1, 5, 4])]);
+
+    return FaceVertexMesh::from(vertices, faces).unwrap();
+}
@@ -11,6 +11,10 @@ pub mod math;
 pub mod cardinal_direction;
 pub mod vector;
 pub mod util;
+pub mod spatial_hash;
+pub mod disjoint_set;
+pub mod sparse_matrix;
+pub mod triangle_topology;
 
 pub mod common_properties;
 
@@ -18,8 +22,30 @@ pub mod polygon;
 
 pub mod mesh_components;
 pub mod mesh;
+pub mod mesh_edges;
 pub mod mesh_normal_calculation;
+pub mod mesh_tangent_calculation;
+pub mod mesh_triangulation;
+pub mod mesh_validation;
+pub mod mesh_orientation_repair;
+pub mod mesh_cleaning;
+pub mod mesh_hole_filling;
+pub mod mesh_connected_components;
+pub mod mesh_subdivision;
+pub mod mesh_decimation;
+pub mod mesh_smoothing;
+pub mod mesh_curvature;
+pub mod mesh_measures;
+pub mod mesh_remeshing;
+pub mod mesh_geodesics;
 pub mod triangle_vertex_mesh;
+pub mod face_vertex_mesh;
+pub mod point_cloud;
+
+pub mod mesh_io;
+
+#[cfg(test)]
+pub(in crate) mod fixtures;
 
 #[cfg(feature = "c_export")]
 pub mod c_export;
\ No newline at end of file
@@ -1,4 +1,42 @@
-
 //! # Mathematics
-//! 
+//!
+//! Helpers for points with f64 coordinates, in which the mesh algorithms compute independent of the vector type of the mesh.
+
+use num_traits::Num;
+use num_traits::Float;
+
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+pub(in crate) type Point = [f64; 3];
+
+pub(in crate) fn to_point<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(vertex : &T) -> Point {
+    return [vertex.x(), vertex.y(), vertex.z()].map(|x| x.to_f64().unwrap());
+}
+
+pub(in crate) fn from_point<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(point : Point) -> T {
+    return T::new(U::from(point[0]).unwrap(), U::from(point[1]).unwrap(), U::from(point[2]).unwrap());
+}
+
+pub(in crate) fn sub(a : &Point, b : &Point) -> Point {
+    return [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+}
+
+pub(in crate) fn dot(a : &Point, b : &Point) -> f64 {
+    return a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+}
+
+pub(in crate) fn cross(a : &Point, b : &Point) -> Point {
+    return [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]];
+}
+
+pub(in crate) fn get_distance(a : &Point, b : &Point) -> f64 {
+    let d = sub(a, b);
+    return dot(&d, &d).sqrt();
+}
 
+/// Returns None for vectors too short to have a direction
+pub(in crate) fn normalize(a : Point) -> Option<Point> {
+    let length = dot(&a, &a).sqrt();
+    return (length > f64::EPSILON).then(|| a.map(|x| x / length));
+}
@@ -18,8 +18,9 @@ use crate::vector::FloatVector;
 use crate::vector::Vec3;
 use crate::property_map::VertexProperties;
 use crate::property_map::FaceProperties;
+use crate::property_map::EdgeProperties;
 
-pub trait Mesh<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> : VertexProperties + FaceProperties {
+pub trait Mesh<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> : VertexProperties + EdgeProperties + FaceProperties {
     
     /// Creates an incidence map between different basic components. E.g. by setting the origin type to vertex
     /// and the incident type to faces, a map is created where for each vertex, all incident faces are provided
@@ -29,6 +30,9 @@ pub trait Mesh<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, Ind
     ///Returns the indices making up a face of the mesh with the given face index
     fn get_face(&self, idx: IndexType) -> Vec<IndexType>;
 
+    ///Returns the number of faces in the mesh
+    fn get_face_count(&self) -> usize;
+
     //Returns a list of all vertices in the mesh
     fn get_vertices(&self) -> &Vec<T>;
 }
@@ -0,0 +1,299 @@
+pub mod mesh_cleanup {
+
+    use std::collections::HashSet;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::mesh::Mesh;
+    use crate::mesh_edges::MeshEdges;
+    use crate::mesh_edges::get_merged_edge_sources;
+    use crate::property_map::EdgeProperties;
+    use crate::property_map::ElementSources;
+    use crate::mesh_validation::mesh_diagnostics::is_degenerate_face;
+    use crate::spatial_hash::SpatialHash;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Number of elements removed by [clean]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct CleanupResult {
+        pub merged_vertices : usize,
+        pub degenerate_faces : usize,
+        pub duplicate_faces : usize,
+        pub unreferenced_vertices : usize,
+    }
+
+    fn get_triangles<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &TriangleVertexMesh<T, U, IndexType>)
+        -> Vec<[usize; 3]>
+    {
+        return mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect();
+    }
+
+    /// Keeps only the faces for which the predicate returns true, together with their face properties
+    fn retain_faces<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned, F : FnMut(&[usize; 3]) -> bool>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, mut predicate : F) -> usize
+    {
+        let triangles = get_triangles(mesh);
+        let face_sources = (0..triangles.len()).filter(|f| predicate(&triangles[*f])).collect::<Vec<usize>>();
+        let removed = triangles.len() - face_sources.len();
+
+        if removed > 0 {
+            let vertex_count = mesh.get_vertices().len();
+            let identity = (0..vertex_count).collect::<Vec<usize>>();
+            mesh.rebuild(&identity, &face_sources, &identity);
+        }
+        return removed;
+    }
+
+    /// Merges all vertices closer to each other than the tolerance into one vertex, which keeps the position of the vertex
+    /// with the lowest index. The vertex properties of the merged vertices are combined with equal weights, as declared
+    /// by each property map (e.g. normals are averaged), just like those of merged edges. Returns the number of removed vertices.
+    /// Triangles collapsing to a line or point are kept, use [remove_degenerate_faces] to remove them.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut mesh = ...;
+    ///
+    /// let merged = weld_vertices(&mut mesh, 1e-6);
+    /// ```
+    pub fn weld_vertices<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, tolerance : U) -> usize
+    {
+        let vertices = mesh.get_vertices();
+        let vertex_count = vertices.len();
+
+        let mut hash = SpatialHash::<T, U>::new(tolerance);
+        let mut new_vertices = Vec::<T>::with_capacity(vertex_count);
+        let mut vertex_sources = Vec::<ElementSources>::with_capacity(vertex_count);
+        let mut vertex_map = Vec::<usize>::with_capacity(vertex_count);
+
+        for (v, vertex) in vertices.iter().enumerate() {
+            match hash.find_nearest(*vertex, tolerance) {
+                Some(idx) => {
+                    vertex_map.push(idx);
+                    vertex_sources[idx].push((v, 1.0));
+                },
+                None => {
+                    vertex_map.push(hash.insert(*vertex));
+                    new_vertices.push(*vertex);
+                    vertex_sources.push(Vec::from([(v, 1.0)]));
+                }
+            }
+        }
+
+        let merged = vertex_count - new_vertices.len();
+        if merged > 0 {
+
+            for sources in vertex_sources.iter_mut() {
+                let weight = 1.0 / sources.len() as f64;
+                sources.iter_mut().for_each(|source| source.1 = weight);
+            }
+
+            let indices = mesh.get_indices().iter().map(|idx| NumCast::from(vertex_map[idx.to_usize().unwrap()]).unwrap()).collect::<Vec<IndexType>>();
+            let face_sources = (0..mesh.get_face_count()).map(|f| Vec::from([(f, 1.0)])).collect::<Vec<ElementSources>>();
+            let edge_sources = match mesh.get_edge_properties().is_empty() {
+                true => None,
+                false => Some(get_merged_edge_sources(&MeshEdges::from_mesh(mesh), &MeshEdges::from_faces(indices.chunks(3).map(|t| {
+                    return t.iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>();
+                })), &vertex_map)),
+            };
+            mesh.replace(new_vertices, &vertex_sources, indices, &face_sources, edge_sources.as_deref());
+        }
+        return merged;
+    }
+
+    /// Removes all triangles with repeated vertices or an area close to zero. Returns the number of removed triangles.
+    pub fn remove_degenerate_faces<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>) -> usize
+    {
+        let vertices = mesh.get_vertices().clone();
+        return retain_faces(mesh, |triangle| !is_degenerate_face(&vertices, triangle));
+    }
+
+    /// Removes triangles using the same three vertices as an earlier triangle, regardless of their orientation.
+    /// Returns the number of removed triangles.
+    pub fn remove_duplicate_faces<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>) -> usize
+    {
+        let mut seen = HashSet::<[usize; 3]>::new();
+        return retain_faces(mesh, |triangle| {
+            let mut key = *triangle;
+            key.sort();
+            return seen.insert(key);
+        });
+    }
+
+    /// Removes all vertices, which are not part of any triangle, and compacts the indices of the remaining ones
+    /// while keeping their order. Returns the number of removed vertices.
+    pub fn remove_unreferenced_vertices<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>) -> usize
+    {
+        let vertex_count = mesh.get_vertices().len();
+
+        let mut referenced = vec![false; vertex_count];
+        for idx in mesh.get_indices() {
+            referenced[idx.to_usize().unwrap()] = true;
+        }
+
+        let vertex_sources = (0..vertex_count).filter(|v| referenced[*v]).collect::<Vec<usize>>();
+        let removed = vertex_count - vertex_sources.len();
+
+        if removed > 0 {
+            let mut vertex_map = vec![0; vertex_count];
+            for (new_idx, old_idx) in vertex_sources.iter().enumerate() {
+                vertex_map[*old_idx] = new_idx;
+            }
+            let face_sources = (0..mesh.get_face_count()).collect::<Vec<usize>>();
+            mesh.rebuild(&vertex_sources, &face_sources, &vertex_map);
+        }
+        return removed;
+    }
+
+    /// Runs all cleanup operations: vertices are welded, degenerate and duplicate triangles removed and finally
+    /// the indices compacted. Vertex and face property maps are remapped along with their elements.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut mesh = obj::read_triangle_mesh::<_, Vec3f, f32, u32>(reader)?.mesh;
+    ///
+    /// let result = clean(&mut mesh, 1e-6);
+    /// ```
+    pub fn clean<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, tolerance : U) -> CleanupResult
+    {
+        return CleanupResult {
+            merged_vertices: weld_vertices(mesh, tolerance),
+            degenerate_faces: remove_degenerate_faces(mesh),
+            duplicate_faces: remove_duplicate_faces(mesh),
+            unreferenced_vertices: remove_unreferenced_vertices(mesh),
+        };
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::common_properties::NormalMap;
+        use crate::mesh::Mesh;
+        use crate::mesh_cleaning::mesh_cleanup::clean;
+        use crate::mesh_cleaning::mesh_cleanup::CleanupResult;
+        use crate::mesh_cleaning::mesh_cleanup::remove_unreferenced_vertices;
+        use crate::mesh_cleaning::mesh_cleanup::weld_vertices;
+        use crate::common_properties::AttributeMap;
+        use crate::common_properties::RgbMap;
+        use crate::property_map::PropertyHandle;
+        use crate::property_map::PropertyInterpolation;
+        use crate::property_map::EdgeProperties;
+        use crate::mesh_edges::MeshEdges;
+        use crate::property_map::PropertyMap;
+        use crate::property_map::PropertyType;
+        use crate::property_map::VertexProperties;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+
+        #[test]
+        fn test_clean() {
+
+            //Two triangles forming a square, stored with separate vertices (like in STL files),
+            //a copy of the first triangle, a sliver and an unused vertex
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(1.0, 1.0, 0.0),
+                Vec3f::new(0.0, 0.0, 0.0000001),
+                Vec3f::new(1.0, 1.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(5.0, 5.0, 5.0),
+                Vec3f::new(0.5, 0.0, 0.0)]);
+
+            let indices = Vec::from([
+                0, 1, 2,
+                3, 4, 5,
+                1, 2, 0,
+                0, 7, 1]);
+
+            let mut mesh = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, indices).unwrap();
+            let mut normals = NormalMap::<Vec3f, f32>::with_size(8, Vec3f::new(0.0, 0.0, 1.0));
+            normals.set(5, Vec3f::new(0.0, 1.0, 0.0));
+            mesh.add_vertex_property(normals);
+
+            let result = clean(&mut mesh, 0.001);
+
+            assert_eq!(result, CleanupResult { merged_vertices: 2, degenerate_faces: 1, duplicate_faces: 1, unreferenced_vertices: 2 });
+            assert_eq!(mesh.get_vertices().len(), 4);
+            assert_eq!(mesh.get_indices(), &Vec::from([0, 1, 2, 0, 2, 3]));
+
+            let normals = mesh.get_vertex_property::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+            assert_eq!(normals.len(), 4);
+            assert_eq!(normals.get(3).y(), 1.0);
+        }
+
+        #[test]
+        fn test_weld_interpolates_properties() {
+
+            //Three triangles sharing the corner at the origin, stored with separate vertices
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(0.0, 1.0, 0.0), Vec3f::new(-1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(-1.0, 0.0, 0.0), Vec3f::new(0.0, -1.0, 0.0)]);
+
+            let mut mesh = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, (0..9).collect()).unwrap();
+
+            let weights = PropertyHandle::<AttributeMap<f32>>::named("weights");
+            let labels = PropertyHandle::<AttributeMap<u32>>::named("labels");
+            let flags = PropertyHandle::<AttributeMap<bool>>::named("flags");
+
+            mesh.add_vertex_property(RgbMap::<f32>::with_size(9, [0.0, 0.0, 0.0]));
+            mesh.get_vertex_property::<RgbMap<f32>, [f32; 3]>(PropertyType::COLOR).unwrap().set(3, [0.9, 0.3, 0.0]);
+            mesh.add_named_vertex_property(&weights, AttributeMap::from(Vec::from([1.0, 0.0, 0.0, 2.0, 0.0, 0.0, 6.0, 0.0, 0.0])).with_blending());
+            mesh.add_named_vertex_property(&labels, AttributeMap::from(Vec::from([1, 0, 0, 2, 0, 0, 2, 0, 0])).with_voting());
+            mesh.add_named_vertex_property(&flags, AttributeMap::from(Vec::from([true, false, false, false, false, false, false, false, false])));
+
+            //Edges to the origin are shared by two triangles after welding
+            let edge_weights = PropertyHandle::<AttributeMap<f64>>::named("edge weights");
+            mesh.add_named_edge_property(&edge_weights, AttributeMap::from((0..9).map(|e| e as f64).collect()).with_blending());
+
+            assert_eq!(weld_vertices(&mut mesh, 0.001), 4);
+
+            let edges = MeshEdges::from_mesh(&mesh);
+            let welded_weights = mesh.get_named_edge_property_ref(&edge_weights).unwrap();
+            assert_eq!(edges.get_edge_count(), 7);
+            assert_eq!(welded_weights.get(edges.find_edge(0, 2).unwrap()), 2.5);
+            assert_eq!(welded_weights.get(edges.find_edge(0, 1).unwrap()), 0.0);
+            assert_eq!(mesh.get_vertices().len(), 5);
+
+            let color = mesh.get_vertex_property_ref::<RgbMap<f32>, [f32; 3]>(PropertyType::COLOR).unwrap().get(0);
+            assert!((color[0] - 0.3).abs() < 1e-6 && (color[1] - 0.1).abs() < 1e-6);
+
+            let weights = mesh.get_named_vertex_property_ref(&weights).unwrap();
+            assert_eq!(weights.get_interpolation(), PropertyInterpolation::BLEND);
+            assert!((weights.get(0) - 3.0).abs() < 1e-6);
+            assert_eq!(mesh.get_named_vertex_property_ref(&labels).unwrap().get(0), 2);
+            assert!(mesh.get_named_vertex_property_ref(&flags).unwrap().get(0));
+        }
+
+        #[test]
+        fn test_remove_unreferenced_vertices() {
+
+            let vertices = Vec::from([
+                Vec3f::new(9.0, 9.0, 9.0),
+                Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0)]);
+
+            let mut mesh = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, Vec::from([1, 2, 3])).unwrap();
+
+            assert_eq!(remove_unreferenced_vertices(&mut mesh), 1);
+            assert_eq!(mesh.get_face(0), Vec::from([0, 1, 2]));
+            assert_eq!(mesh.get_vertices()[0].x(), 0.0);
+        }
+    }
+}
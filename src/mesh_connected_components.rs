@@ -0,0 +1,235 @@
+pub mod mesh_connectivity {
+
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::disjoint_set::DisjointSet;
+    use crate::mesh::Mesh;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Assigns each face the index of its connected component, where faces are connected if they share an edge.
+    /// Components are numbered in the order of their first face. Returns the labels together with the number of components.
+    ///
+    /// # Examples
+    /// ```
+    /// let (labels, component_count) = label_connected_components(&mesh);
+    /// ```
+    pub fn label_connected_components<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &MeshType)
+        -> (Vec<usize>, usize)
+    {
+        let face_count = mesh.get_face_count();
+        let mut sets = DisjointSet::new(face_count);
+        let mut edge_faces = HashMap::<(usize, usize), usize>::new();
+
+        for f in 0..face_count {
+
+            let face = mesh.get_face(NumCast::from(f).unwrap());
+            let size = face.len();
+            for i in 0..size {
+                let a = face[i].to_usize().unwrap();
+                let b = face[(i + 1) % size].to_usize().unwrap();
+
+                match edge_faces.get(&(a.min(b), a.max(b))) {
+                    Some(other) => { sets.union(f, *other); }
+                    None => { edge_faces.insert((a.min(b), a.max(b)), f); }
+                }
+            }
+        }
+
+        let mut root_labels = HashMap::<usize, usize>::new();
+        let mut labels = Vec::<usize>::with_capacity(face_count);
+        for f in 0..face_count {
+            let root = sets.find(f);
+            let next_label = root_labels.len();
+            labels.push(*root_labels.entry(root).or_insert(next_label));
+        }
+
+        return (labels, root_labels.len());
+    }
+
+    /// Creates a mesh from a subset of the faces, containing only the vertices used by them. Vertices and faces keep
+    /// their relative order, and vertex and face properties are carried along.
+    ///
+    /// # Examples
+    /// ```
+    /// let upper_half = extract_faces(&mesh, &[0, 1, 2, 3]);
+    /// ```
+    pub fn extract_faces<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>, faces : &[usize]) -> TriangleVertexMesh<T, U, IndexType>
+    {
+        let vertex_count = mesh.get_vertices().len();
+        let indices = mesh.get_indices();
+
+        let mut used = vec![false; vertex_count];
+        for f in faces {
+            for i in 0..3 {
+                used[indices[f * 3 + i].to_usize().unwrap()] = true;
+            }
+        }
+
+        let vertex_sources = (0..vertex_count).filter(|v| used[*v]).collect::<Vec<usize>>();
+        let mut vertex_map = vec![0; vertex_count];
+        for (new_idx, old_idx) in vertex_sources.iter().enumerate() {
+            vertex_map[*old_idx] = new_idx;
+        }
+
+        return mesh.extract(&vertex_sources, faces, &vertex_map);
+    }
+
+    /// Splits the mesh into one mesh per connected component, in the order given by [label_connected_components]
+    pub fn split_connected_components<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> Vec<TriangleVertexMesh<T, U, IndexType>>
+    {
+        let (labels, component_count) = label_connected_components(mesh);
+
+        let mut component_faces = vec![Vec::<usize>::new(); component_count];
+        for (f, label) in labels.iter().enumerate() {
+            component_faces[*label].push(f);
+        }
+
+        return component_faces.iter().map(|faces| extract_faces(mesh, faces)).collect();
+    }
+
+    /// Returns the connected component with the most faces, e.g. to remove floating noise from a scan.
+    /// For a mesh without faces, an empty mesh is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// let scan = ply::read_triangle_mesh::<_, Vec3f, f32, u32>(reader)?.geometry;
+    /// let cleaned = extract_largest_component(&scan);
+    /// ```
+    pub fn extract_largest_component<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> TriangleVertexMesh<T, U, IndexType>
+    {
+        let (labels, component_count) = label_connected_components(mesh);
+
+        let mut sizes = vec![0; component_count];
+        for label in &labels {
+            sizes[*label] += 1;
+        }
+
+        let largest = (0..component_count).max_by_key(|c| (sizes[*c], std::cmp::Reverse(*c)));
+        let faces = (0..labels.len()).filter(|f| Some(labels[*f]) == largest).collect::<Vec<usize>>();
+        return extract_faces(mesh, &faces);
+    }
+
+    /// Combines several meshes into one. The vertices and faces of each mesh follow those of the previous meshes.
+    /// Meshes without a property map receive its default values, maps without one are only kept if all meshes have them.
+    /// Dropped maps are reported by [PropertyStore::take_dropped_names](crate::property_map::PropertyStore::take_dropped_names).
+    /// Returns None, if the total number of vertices cannot be addressed with the index type.
+    ///
+    /// # Examples
+    /// ```
+    /// let assembly = merge_meshes(&[&body, &wheel_left, &wheel_right]).unwrap();
+    /// ```
+    pub fn merge_meshes<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        meshes : &[&TriangleVertexMesh<T, U, IndexType>]) -> Option<TriangleVertexMesh<T, U, IndexType>>
+    {
+        return TriangleVertexMesh::concatenate(meshes);
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::common_properties::AttributeMap;
+        use crate::common_properties::NormalMap;
+        use crate::mesh::Mesh;
+        use crate::mesh_connected_components::mesh_connectivity::extract_largest_component;
+        use crate::mesh_connected_components::mesh_connectivity::label_connected_components;
+        use crate::mesh_connected_components::mesh_connectivity::merge_meshes;
+        use crate::mesh_connected_components::mesh_connectivity::split_connected_components;
+        use crate::property_map::PropertyHandle;
+        use crate::property_map::PropertyMap;
+        use crate::property_map::PropertyType;
+        use crate::property_map::VertexProperties;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+
+        /// A square of two triangles and a separate triangle, sharing no edge with it
+        fn create_two_parts() -> TriangleVertexMesh<Vec3f, f32, u32> {
+
+            let vertices = Vec::from([
+                Vec3f::new(5.0, 0.0, 0.0),
+                Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(1.0, 1.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(6.0, 0.0, 0.0),
+                Vec3f::new(5.0, 1.0, 0.0)]);
+
+            let indices = Vec::from([
+                1, 2, 3,
+                0, 5, 6,
+                1, 3, 4]);
+
+            let mut mesh = TriangleVertexMesh::from(vertices, indices).unwrap();
+            let mut normals = NormalMap::<Vec3f, f32>::with_size(7, Vec3f::new(0.0, 0.0, 1.0));
+            normals.set(6, Vec3f::new(1.0, 0.0, 0.0));
+            mesh.add_vertex_property(normals);
+            return mesh;
+        }
+
+        #[test]
+        fn test_label_components() {
+
+            let mesh = create_two_parts();
+            let (labels, count) = label_connected_components(&mesh);
+
+            assert_eq!(count, 2);
+            assert_eq!(labels, Vec::from([0, 1, 0]));
+        }
+
+        #[test]
+        fn test_split_and_merge() {
+
+            let mesh = create_two_parts();
+            let mut parts = split_connected_components(&mesh);
+
+            assert_eq!(parts.len(), 2);
+            assert_eq!(parts[0].get_vertices().len(), 4);
+            assert_eq!(parts[0].get_indices(), &Vec::from([0, 1, 2, 0, 2, 3]));
+            assert_eq!(parts[1].get_face(0), Vec::from([0, 1, 2]));
+
+            let normals = parts[1].get_vertex_property::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+            assert_eq!(normals.get(2).x(), 1.0);
+
+            let largest = extract_largest_component(&mesh);
+            assert_eq!(largest.get_face_count(), 2);
+
+            let mut merged = merge_meshes(&[&parts[0], &parts[1]]).unwrap();
+            assert_eq!(merged.get_vertices().len(), 7);
+            assert_eq!(merged.get_face(2), Vec::from([4, 5, 6]));
+
+            let normals = merged.get_vertex_property::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+            assert_eq!(normals.len(), 7);
+            assert_eq!(normals.get(6).x(), 1.0);
+        }
+
+        #[test]
+        fn test_merge_missing_properties() {
+
+            let mesh = create_two_parts();
+            let mut parts = split_connected_components(&mesh);
+
+            //The first part has a map with a default value and one without, which the second part lacks
+            let weights = PropertyHandle::<AttributeMap<f32>>::named("weights");
+            let labels = PropertyHandle::<AttributeMap<u32>>::named("labels");
+            parts[0].add_named_vertex_property(&weights, AttributeMap::with_size(4, 2.0).with_default(1.0));
+            parts[0].add_named_vertex_property(&labels, AttributeMap::from(vec![3; 4]));
+
+            let mut merged = merge_meshes(&[&parts[0], &parts[1]]).unwrap();
+            assert_eq!(merged.get_named_vertex_property_ref(&weights).unwrap().as_slice(), [2.0, 2.0, 2.0, 2.0, 1.0, 1.0, 1.0]);
+            assert!(merged.get_named_vertex_property_ref(&labels).is_none());
+            assert_eq!(merged.get_vertex_properties_mut().take_dropped_names(), Vec::from([labels.get_name().clone()]));
+        }
+    }
+}
@@ -0,0 +1,389 @@
+pub mod curvature {
+
+    use std::f64::consts::PI;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::common_properties::AttributeMap;
+    use crate::math::Point;
+    use crate::math::cross;
+    use crate::math::dot;
+    use crate::math::from_point;
+    use crate::math::sub;
+    use crate::math::to_point;
+    use crate::mesh::Mesh;
+    use crate::mesh_edges::MeshEdges;
+    use crate::mesh_normal_calculation::mesh_normals::VertexNormalWeighting;
+    use crate::mesh_normal_calculation::mesh_normals::create_vertex_normals;
+    use crate::mesh_smoothing::smoothing::create_cotangent_laplacian;
+    use crate::property_map::PropertyHandle;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::VertexProperties;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Principal curvatures of a vertex, i.e. the largest and smallest normal curvature, with their tangent directions.
+    /// Curvatures are positive where the surface bends away from the normal, e.g. 1 / r on a sphere with outward normals.
+    #[derive(Clone, Copy)]
+    pub struct PrincipalCurvatures<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> {
+        pub max_curvature : U,
+        pub min_curvature : U,
+        pub max_direction : T,
+        pub min_direction : T,
+    }
+
+    /// Handles of the curvature maps stored in the vertex properties by [add_curvatures]
+    pub struct CurvatureHandles<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> {
+        pub gaussian : PropertyHandle<AttributeMap<U>>,
+        pub mean : PropertyHandle<AttributeMap<U>>,
+        pub principal : PropertyHandle<AttributeMap<PrincipalCurvatures<T, U>>>,
+    }
+
+    fn get_points_and_triangles<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> (Vec<Point>, Vec<[usize; 3]>)
+    {
+        let points = mesh.get_vertices().iter().map(to_point).collect();
+        let triangles = mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect();
+        return (points, triangles);
+    }
+
+    /// Returns the angle of the corner of a triangle at vertex i
+    fn get_corner_angle(corners : &[Point; 3], i : usize) -> f64 {
+        let u = sub(&corners[(i + 1) % 3], &corners[i]);
+        let v = sub(&corners[(i + 2) % 3], &corners[i]);
+        let normal = cross(&u, &v);
+        return dot(&normal, &normal).sqrt().atan2(dot(&u, &v));
+    }
+
+    /// Calculates the mixed Voronoi area of each vertex as proposed by Meyer et al., i.e. the part of the adjacent triangles
+    /// closer to the vertex than to the others. Obtuse triangles are split at the midpoints of their edges instead.
+    fn get_mixed_areas(points : &[Point], triangles : &[[usize; 3]]) -> Vec<f64> {
+
+        let mut areas = vec![0.0; points.len()];
+        for triangle in triangles {
+
+            let corners = triangle.map(|v| points[v]);
+            let normal = cross(&sub(&corners[1], &corners[0]), &sub(&corners[2], &corners[0]));
+            let area = 0.5 * dot(&normal, &normal).sqrt();
+            if area <= f64::EPSILON {
+                continue;
+            }
+
+            let angles = [0, 1, 2].map(|i| get_corner_angle(&corners, i));
+            let obtuse = (0..3).find(|i| angles[*i] > 0.5 * PI);
+            for i in 0..3 {
+                areas[triangle[i]] += match obtuse {
+                    Some(corner) if corner == i => 0.5 * area,
+                    Some(_) => 0.25 * area,
+                    None => {
+                        let next = sub(&corners[(i + 1) % 3], &corners[i]);
+                        let previous = sub(&corners[(i + 2) % 3], &corners[i]);
+                        let next_cot = 1.0 / angles[(i + 2) % 3].tan();
+                        let previous_cot = 1.0 / angles[(i + 1) % 3].tan();
+                        (dot(&next, &next) * next_cot + dot(&previous, &previous) * previous_cot) / 8.0
+                    },
+                };
+            }
+        }
+        return areas;
+    }
+
+    /// Marks vertices on boundary or non-manifold edges
+    fn get_boundary_vertices(vertex_count : usize, triangles : &[[usize; 3]]) -> Vec<bool> {
+
+        let edges = MeshEdges::from_faces(triangles.iter());
+        let mut face_counts = vec![0; edges.get_edge_count()];
+        for f in 0..triangles.len() {
+            edges.get_face_edges(f).iter().for_each(|e| face_counts[*e] += 1);
+        }
+
+        let mut boundary = vec![false; vertex_count];
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            if face_counts[e] != 2 {
+                boundary[*a] = true;
+                boundary[*b] = true;
+            }
+        }
+        return boundary;
+    }
+
+    /// Calculates the Gaussian curvature of each vertex from its angle defect, i.e. the difference of the angles of its
+    /// corners to a full circle, divided by the mixed Voronoi area. The defect of boundary vertices is measured against a
+    /// half circle. Vertices without area get a curvature of zero.
+    ///
+    /// # Examples
+    /// ```
+    /// let gaussian = create_gaussian_curvature(&mesh);
+    /// let is_saddle = gaussian.get(v) < 0.0;
+    /// ```
+    pub fn create_gaussian_curvature<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> AttributeMap<U>
+    {
+        let (points, triangles) = get_points_and_triangles(mesh);
+        let areas = get_mixed_areas(&points, &triangles);
+        let boundary = get_boundary_vertices(points.len(), &triangles);
+
+        let mut angle_sums = vec![0.0; points.len()];
+        for triangle in &triangles {
+            let corners = triangle.map(|v| points[v]);
+            for i in 0..3 {
+                angle_sums[triangle[i]] += get_corner_angle(&corners, i);
+            }
+        }
+
+        let mut curvature = AttributeMap::with_size(points.len(), U::zero());
+        for v in (0..points.len()).filter(|v| areas[*v] > 0.0) {
+            let full_angle = if boundary[v] { PI } else { 2.0 * PI };
+            curvature.set(v, U::from((full_angle - angle_sums[v]) / areas[v]).unwrap());
+        }
+        return curvature;
+    }
+
+    /// Calculates the mean curvature of each vertex from the cotangent Laplacian, which gives the mean curvature normal
+    /// divided by the mixed Voronoi area. The sign is taken from the angle weighted vertex normal, so that the curvature is
+    /// positive on convex regions with outward normals. The values at boundary vertices are less accurate.
+    ///
+    /// # Examples
+    /// ```
+    /// let mean = create_mean_curvature(&mesh);
+    /// ```
+    pub fn create_mean_curvature<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> AttributeMap<U>
+    {
+        let (points, triangles) = get_points_and_triangles(mesh);
+        let areas = get_mixed_areas(&points, &triangles);
+        let normals = create_vertex_normals(mesh, VertexNormalWeighting::ANGLE);
+
+        let laplacian = create_cotangent_laplacian(mesh);
+        let coordinates = [0, 1, 2].map(|c| laplacian.multiply(&points.iter().map(|p| p[c]).collect::<Vec<f64>>()));
+
+        let mut curvature = AttributeMap::with_size(points.len(), U::zero());
+        for v in (0..points.len()).filter(|v| areas[*v] > 0.0) {
+            let normal = to_point(&normals.get(v));
+            let curvature_normal = [coordinates[0][v], coordinates[1][v], coordinates[2][v]];
+            curvature.set(v, U::from(0.5 * dot(&curvature_normal, &normal) / areas[v]).unwrap());
+        }
+        return curvature;
+    }
+
+    /// Estimates the principal curvatures and directions of each vertex by fitting a shape operator to the normal curvatures
+    /// along its edges, as proposed by Taubin, where the normal curvature along an edge is that of the circle through both
+    /// vertices touching the tangent plane. Where the edges do not determine the shape operator, e.g. at vertices with less
+    /// than three neighbours, both curvatures are the average normal curvature along arbitrary tangent directions.
+    ///
+    /// # Examples
+    /// ```
+    /// let principal = create_principal_curvatures(&mesh);
+    /// let ridge_direction = principal.get(v).min_direction;
+    /// ```
+    pub fn create_principal_curvatures<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> AttributeMap<PrincipalCurvatures<T, U>>
+    {
+        let (points, triangles) = get_points_and_triangles(mesh);
+        let normals = create_vertex_normals(mesh, VertexNormalWeighting::ANGLE);
+
+        let mut neighbours = vec![Vec::<usize>::new(); points.len()];
+        for triangle in &triangles {
+            for i in 0..3 {
+                for j in [(i + 1) % 3, (i + 2) % 3] {
+                    if !neighbours[triangle[i]].contains(&triangle[j]) {
+                        neighbours[triangle[i]].push(triangle[j]);
+                    }
+                }
+            }
+        }
+
+        let mut curvatures = AttributeMap::<PrincipalCurvatures<T, U>>::with_capacity(points.len());
+        for v in 0..points.len() {
+
+            //Tangent frame around the normal
+            let normal = to_point(&normals.get(v));
+            let helper = if normal[0].abs() < 0.9 { [1.0, 0.0, 0.0] } else { [0.0, 1.0, 0.0] };
+            let t1 = cross(&normal, &helper);
+            let length = dot(&t1, &t1).sqrt();
+            let t1 = if length > 0.0 { t1.map(|x| x / length) } else { [1.0, 0.0, 0.0] };
+            let t2 = cross(&normal, &t1);
+
+            //Least squares fit of the shape operator [a b; b c] with normal curvature a u² + 2 b u v + c v² along (u, v)
+            let mut normal_matrix = [[0.0; 3]; 3];
+            let mut right_hand_side = [0.0; 3];
+            let (mut curvature_sum, mut curvature_count) = (0.0, 0);
+            for n in &neighbours[v] {
+                let edge = sub(&points[*n], &points[v]);
+                let (u, w) = (dot(&edge, &t1), dot(&edge, &t2));
+                let tangent_length = (u * u + w * w).sqrt();
+                if tangent_length <= f64::EPSILON {
+                    continue;
+                }
+                let normal_curvature = -2.0 * dot(&edge, &normal) / dot(&edge, &edge);
+                curvature_sum += normal_curvature;
+                curvature_count += 1;
+                let (u, w) = (u / tangent_length, w / tangent_length);
+                let row = [u * u, 2.0 * u * w, w * w];
+                for i in 0..3 {
+                    for j in 0..3 {
+                        normal_matrix[i][j] += row[i] * row[j];
+                    }
+                    right_hand_side[i] += row[i] * normal_curvature;
+                }
+            }
+
+            //Edges in only two directions, e.g. at vertices with four neighbours, cannot distinguish the curvatures
+            let average = if curvature_count > 0 { curvature_sum / curvature_count as f64 } else { 0.0 };
+            let [a, b, c] = solve_3x3(normal_matrix, right_hand_side).unwrap_or([average, 0.0, average]);
+            let mean = 0.5 * (a + c);
+            let radius = (0.25 * (a - c) * (a - c) + b * b).sqrt();
+            let angle = 0.5 * (2.0 * b).atan2(a - c);
+            let max_direction = [0, 1, 2].map(|i| angle.cos() * t1[i] + angle.sin() * t2[i]);
+
+            curvatures.push(PrincipalCurvatures {
+                max_curvature: U::from(mean + radius).unwrap(),
+                min_curvature: U::from(mean - radius).unwrap(),
+                max_direction: from_point(max_direction),
+                min_direction: from_point(cross(&normal, &max_direction)),
+            });
+        }
+        return curvatures;
+    }
+
+    /// Solves a linear system of three equations with Cramer's rule, if it is not singular
+    fn solve_3x3(m : [[f64; 3]; 3], b : [f64; 3]) -> Option<[f64; 3]> {
+
+        let determinant = |m : &[[f64; 3]; 3]| -> f64 {
+            return m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        };
+
+        let full = determinant(&m);
+        let scale = m.iter().flatten().fold(0.0f64, |max, x| max.max(x.abs()));
+        if full.abs() <= 1e-12 * scale * scale * scale {
+            return None;
+        }
+
+        let mut solution = [0.0; 3];
+        for (column, x) in solution.iter_mut().enumerate() {
+            let mut replaced = m;
+            (0..3).for_each(|row| replaced[row][column] = b[row]);
+            *x = determinant(&replaced) / full;
+        }
+        return Some(solution);
+    }
+
+    /// Calculates all curvatures and stores them in the vertex properties of the mesh under the names "gaussian_curvature",
+    /// "mean_curvature" and "principal_curvatures", replacing existing ones. Returns the handles of the maps.
+    ///
+    /// # Examples
+    /// ```
+    /// let handles = add_curvatures(&mut mesh);
+    /// let mean = mesh.get_named_vertex_property_ref(&handles.mean).unwrap();
+    /// ```
+    pub fn add_curvatures<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>) -> CurvatureHandles<T, U>
+    {
+        let handles = CurvatureHandles {
+            gaussian: PropertyHandle::named("gaussian_curvature"),
+            mean: PropertyHandle::named("mean_curvature"),
+            principal: PropertyHandle::named("principal_curvatures"),
+        };
+
+        let gaussian = create_gaussian_curvature(mesh);
+        let mean = create_mean_curvature(mesh);
+        let principal = create_principal_curvatures(mesh);
+
+        mesh.remove_vertex_property(handles.gaussian.get_name());
+        mesh.remove_vertex_property(handles.mean.get_name());
+        mesh.remove_vertex_property(handles.principal.get_name());
+        mesh.add_named_vertex_property(&handles.gaussian, gaussian);
+        mesh.add_named_vertex_property(&handles.mean, mean);
+        mesh.add_named_vertex_property(&handles.principal, principal);
+        return handles;
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::fixtures::create_sphere;
+        use crate::mesh::Mesh;
+        use crate::mesh_curvature::curvature::add_curvatures;
+        use crate::mesh_curvature::curvature::create_gaussian_curvature;
+        use crate::mesh_curvature::curvature::create_mean_curvature;
+        use crate::mesh_curvature::curvature::create_principal_curvatures;
+        use crate::mesh_smoothing::smoothing::get_vertex_areas;
+        use crate::property_map::PropertyMap;
+        use crate::property_map::VertexProperties;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::Vec3d;
+        use crate::vector::Vec3;
+
+        /// Open cylinder of radius one around the z axis, with outward facing triangles
+        fn create_cylinder(segments : u32, rows : u32) -> TriangleVertexMesh<Vec3d, f64, u32> {
+
+            let vertices = (0..segments * (rows + 1)).map(|v| {
+                let angle = 2.0 * std::f64::consts::PI * (v % segments) as f64 / segments as f64;
+                return Vec3d::new(angle.cos(), angle.sin(), 0.2 * (v / segments) as f64);
+            }).collect();
+
+            let mut indices = Vec::new();
+            for row in 0..rows {
+                for i in 0..segments {
+                    let a = row * segments + i;
+                    let b = row * segments + (i + 1) % segments;
+                    indices.extend([a, b, b + segments, a, b + segments, a + segments]);
+                }
+            }
+            return TriangleVertexMesh::from(vertices, indices).unwrap();
+        }
+
+        #[test]
+        fn test_sphere_curvature() {
+
+            let sphere = create_sphere::<Vec3d, f64>(3, 2.0);
+            let gaussian = create_gaussian_curvature(&sphere);
+            let mean = create_mean_curvature(&sphere);
+            let principal = create_principal_curvatures(&sphere);
+
+            for v in 0..sphere.get_vertices().len() {
+                assert!((gaussian.get(v) - 0.25).abs() < 0.05);
+                assert!((mean.get(v) - 0.5).abs() < 0.05);
+                assert!((principal.get(v).max_curvature - 0.5).abs() < 0.05);
+                assert!((principal.get(v).min_curvature - 0.5).abs() < 0.05);
+            }
+
+            //The total Gaussian curvature of a closed surface is 2 pi times its Euler characteristic
+            let areas = get_vertex_areas(&sphere);
+            let total = (0..areas.len()).map(|v| gaussian.get(v) * areas[v]).sum::<f64>();
+            assert!((total - 4.0 * std::f64::consts::PI).abs() < 0.1);
+        }
+
+        #[test]
+        fn test_cylinder_curvature() {
+
+            let mut cylinder = create_cylinder(32, 6);
+            let handles = add_curvatures(&mut cylinder);
+            let interior = (96..128).collect::<Vec<usize>>();
+
+            let gaussian = cylinder.get_named_vertex_property_ref(&handles.gaussian).unwrap();
+            let mean = cylinder.get_named_vertex_property_ref(&handles.mean).unwrap();
+            let principal = cylinder.get_named_vertex_property_ref(&handles.principal).unwrap();
+
+            for v in interior {
+                assert!(gaussian.get(v).abs() < 1e-6);
+                assert!((mean.get(v) - 0.5).abs() < 0.01);
+
+                let curvatures = principal.get(v);
+                assert!((curvatures.max_curvature - 1.0).abs() < 0.01);
+                assert!(curvatures.min_curvature.abs() < 0.01);
+                assert!(curvatures.min_direction.z().abs() > 0.99);
+                assert!(curvatures.max_direction.z().abs() < 0.01);
+            }
+        }
+    }
+}
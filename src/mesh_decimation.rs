@@ -0,0 +1,629 @@
+pub mod decimation {
+
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+    use std::collections::HashSet;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::common_properties::NormalMap;
+    use crate::common_properties::UvMap;
+    use crate::math::Point;
+    use crate::math::cross;
+    use crate::math::normalize;
+    use crate::math::sub;
+    use crate::mesh::Mesh;
+    use crate::mesh_edges::MeshEdges;
+    use crate::mesh_edges::get_merged_edge_sources;
+    use crate::property_map::EdgeProperties;
+    use crate::property_map::ElementSources;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Weight of the planes perpendicular to boundary edges, which keep the boundary in shape if it is not preserved
+    const BOUNDARY_WEIGHT : f64 = 100.0;
+
+    /// When [decimate] stops collapsing edges
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[allow(non_camel_case_types)]
+    pub enum DecimationTarget {
+        /// Collapses edges until the mesh has at most the given number of triangles
+        FACE_COUNT(usize),
+        /// Collapses edges as long as their quadric error, i.e. the sum of squared distances to the planes of the merged
+        /// triangles, stays below the given value
+        ERROR(f64),
+    }
+
+    /// Options of [decimate]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct DecimationOptions {
+        pub target : DecimationTarget,
+        /// Keeps boundary vertices in place and never collapses boundary edges
+        pub preserve_boundaries : bool,
+        /// Rejects collapses which flip the normal of an adjacent triangle
+        pub prevent_flips : bool,
+        /// Weight of the vertex normals in the error, if the mesh has a normal map
+        pub normal_weight : f64,
+        /// Weight of the texture coordinates of all channels in the error, if the mesh has a uv map
+        pub uv_weight : f64,
+    }
+
+    impl DecimationOptions {
+
+        /// Creates options which prevent normal flips, but neither preserve the boundaries nor weigh attributes
+        pub fn new(target : DecimationTarget) -> Self {
+            return Self {
+                target,
+                preserve_boundaries: false,
+                prevent_flips: true,
+                normal_weight: 0.0,
+                uv_weight: 0.0,
+            };
+        }
+    }
+
+    /// Statistics of [decimate]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct DecimationResult {
+        pub collapsed_edges : usize,
+        pub removed_faces : usize,
+        /// Largest quadric error of all collapses
+        pub max_error : f64,
+    }
+
+    /// Quadric error of a point with the position in the first three and the weighted attributes in the other coordinates,
+    /// given by p^T A p + 2 b^T p + c, as proposed by Garland and Heckbert
+    #[derive(Clone)]
+    struct Quadric {
+        size : usize,
+        a : Vec<f64>,
+        b : Vec<f64>,
+        c : f64,
+    }
+
+    impl Quadric {
+
+        fn zero(size : usize) -> Self {
+            return Self { size, a: vec![0.0; size * size], b: vec![0.0; size], c: 0.0 };
+        }
+
+        /// Squared distance to the plane spanned by a triangle, in the space of positions and attributes
+        fn from_triangle(p : &[f64], q : &[f64], r : &[f64]) -> Self {
+
+            let size = p.len();
+            let mut quadric = Self::zero(size);
+
+            let dot = |x : &[f64], y : &[f64]| -> f64 { x.iter().zip(y).map(|(a, b)| a * b).sum() };
+            let mut e1 = q.iter().zip(p).map(|(q, p)| q - p).collect::<Vec<f64>>();
+            let length = dot(&e1, &e1).sqrt();
+            if length < f64::EPSILON {
+                return quadric;
+            }
+            e1.iter_mut().for_each(|x| *x /= length);
+
+            let mut e2 = r.iter().zip(p).map(|(r, p)| r - p).collect::<Vec<f64>>();
+            let projection = dot(&e2, &e1);
+            e2.iter_mut().zip(&e1).for_each(|(x, e)| *x -= projection * e);
+            let length = dot(&e2, &e2).sqrt();
+            if length < f64::EPSILON {
+                return quadric;
+            }
+            e2.iter_mut().for_each(|x| *x /= length);
+
+            let (p1, p2) = (dot(p, &e1), dot(p, &e2));
+            for i in 0..size {
+                for j in 0..size {
+                    let identity = if i == j { 1.0 } else { 0.0 };
+                    quadric.a[i * size + j] = identity - e1[i] * e1[j] - e2[i] * e2[j];
+                }
+                quadric.b[i] = p1 * e1[i] + p2 * e2[i] - p[i];
+            }
+            quadric.c = dot(p, p) - p1 * p1 - p2 * p2;
+            return quadric;
+        }
+
+        /// Weighted squared distance to a plane through a point, which only depends on the position
+        fn from_plane(size : usize, normal : [f64; 3], point : &[f64], weight : f64) -> Self {
+
+            let mut quadric = Self::zero(size);
+            let d = -(normal[0] * point[0] + normal[1] * point[1] + normal[2] * point[2]);
+            for i in 0..3 {
+                for j in 0..3 {
+                    quadric.a[i * size + j] = weight * normal[i] * normal[j];
+                }
+                quadric.b[i] = weight * d * normal[i];
+            }
+            quadric.c = weight * d * d;
+            return quadric;
+        }
+
+        fn add(&self, other : &Self) -> Self {
+            return Self {
+                size: self.size,
+                a: self.a.iter().zip(&other.a).map(|(x, y)| x + y).collect(),
+                b: self.b.iter().zip(&other.b).map(|(x, y)| x + y).collect(),
+                c: self.c + other.c,
+            };
+        }
+
+        fn evaluate(&self, point : &[f64]) -> f64 {
+
+            let mut error = self.c;
+            for i in 0..self.size {
+                error += 2.0 * self.b[i] * point[i];
+                for j in 0..self.size {
+                    error += point[i] * self.a[i * self.size + j] * point[j];
+                }
+            }
+            return error.max(0.0);
+        }
+
+        /// Finds the point with minimal error by solving A p = -b with Gaussian elimination, if A is not singular
+        fn minimize(&self) -> Option<Vec<f64>> {
+
+            let n = self.size;
+            let mut a = self.a.clone();
+            let mut x = self.b.iter().map(|b| -b).collect::<Vec<f64>>();
+            let scale = a.iter().fold(0.0f64, |max, v| max.max(v.abs()));
+
+            for column in 0..n {
+                let pivot = (column..n).max_by(|i, j| a[i * n + column].abs().total_cmp(&a[j * n + column].abs()))?;
+                if a[pivot * n + column].abs() <= 1e-10 * scale {
+                    return None;
+                }
+                for k in 0..n {
+                    a.swap(column * n + k, pivot * n + k);
+                }
+                x.swap(column, pivot);
+
+                for row in (column + 1)..n {
+                    let factor = a[row * n + column] / a[column * n + column];
+                    for k in column..n {
+                        a[row * n + k] -= factor * a[column * n + k];
+                    }
+                    x[row] -= factor * x[column];
+                }
+            }
+
+            for row in (0..n).rev() {
+                let sum = ((row + 1)..n).map(|k| a[row * n + k] * x[k]).sum::<f64>();
+                x[row] = (x[row] - sum) / a[row * n + row];
+            }
+            return x.iter().all(|v| v.is_finite()).then_some(x);
+        }
+    }
+
+    /// Candidate for collapsing edge (a, b) into a vertex at the point, ordered by increasing cost in a [BinaryHeap]
+    struct Collapse {
+        cost : f64,
+        a : usize,
+        b : usize,
+        versions : (usize, usize),
+        point : Vec<f64>,
+    }
+
+    impl PartialEq for Collapse {
+        fn eq(&self, other : &Self) -> bool {
+            return self.cost == other.cost;
+        }
+    }
+
+    impl Eq for Collapse {}
+
+    impl PartialOrd for Collapse {
+        fn partial_cmp(&self, other : &Self) -> Option<Ordering> {
+            return Some(self.cmp(other));
+        }
+    }
+
+    impl Ord for Collapse {
+        fn cmp(&self, other : &Self) -> Ordering {
+            return other.cost.total_cmp(&self.cost);
+        }
+    }
+
+    /// State of the decimation, where collapsed vertices and triangles are marked as removed
+    struct Decimation<'a> {
+        options : &'a DecimationOptions,
+        points : Vec<Vec<f64>>,
+        quadrics : Vec<Quadric>,
+        sources : Vec<ElementSources>,
+        versions : Vec<usize>,
+        vertex_alive : Vec<bool>,
+        boundary : Vec<bool>,
+        merged_into : Vec<usize>,
+        triangles : Vec<[usize; 3]>,
+        face_alive : Vec<bool>,
+        vertex_faces : Vec<Vec<usize>>,
+    }
+
+    /// Position of a point, whose coordinates are followed by its attributes
+    fn get_position(point : &[f64]) -> &Point {
+        return point.first_chunk::<3>().unwrap();
+    }
+
+    impl<'a> Decimation<'a> {
+
+        fn get_neighbours(&self, v : usize) -> HashSet<usize> {
+            return self.vertex_faces[v].iter().flat_map(|f| self.triangles[*f]).filter(|w| *w != v).collect();
+        }
+
+        fn get_shared_faces(&self, a : usize, b : usize) -> Vec<usize> {
+            return self.vertex_faces[a].iter().filter(|f| self.triangles[**f].contains(&b)).copied().collect();
+        }
+
+        /// Finds the point and cost of collapsing an edge, if the boundary options allow it
+        fn evaluate(&self, a : usize, b : usize) -> Option<Collapse> {
+
+            let is_boundary_edge = self.get_shared_faces(a, b).len() == 1;
+            if self.boundary[a] && self.boundary[b] && !is_boundary_edge {
+                return None;
+            }
+
+            let quadric = self.quadrics[a].add(&self.quadrics[b]);
+            let point = match (self.options.preserve_boundaries, self.boundary[a], self.boundary[b]) {
+                (true, true, true) => return None,
+                (true, true, false) => self.points[a].clone(),
+                (true, false, true) => self.points[b].clone(),
+                _ => quadric.minimize().unwrap_or_else(|| {
+                    let midpoint = self.points[a].iter().zip(&self.points[b]).map(|(x, y)| 0.5 * (x + y)).collect::<Vec<f64>>();
+                    return [self.points[a].clone(), self.points[b].clone(), midpoint].into_iter()
+                        .min_by(|p, q| quadric.evaluate(p).total_cmp(&quadric.evaluate(q))).unwrap();
+                }),
+            };
+
+            return Some(Collapse {
+                cost: quadric.evaluate(&point),
+                a,
+                b,
+                versions: (self.versions[a], self.versions[b]),
+                point,
+            });
+        }
+
+        /// Checks that the collapse keeps the mesh manifold and, if requested, does not flip any triangle
+        fn is_valid(&self, collapse : &Collapse) -> bool {
+
+            let (a, b) = (collapse.a, collapse.b);
+            let shared = self.get_shared_faces(a, b);
+            if shared.is_empty() || shared.len() > 2 {
+                return false;
+            }
+
+            //Link condition: the only common neighbours are the opposite vertices of the shared triangles
+            let opposite = shared.iter().flat_map(|f| self.triangles[*f]).filter(|v| *v != a && *v != b).collect::<HashSet<usize>>();
+            if self.get_neighbours(a).intersection(&self.get_neighbours(b)).any(|v| !opposite.contains(v)) {
+                return false;
+            }
+
+            if self.options.prevent_flips {
+                for f in self.vertex_faces[a].iter().chain(&self.vertex_faces[b]).filter(|f| !shared.contains(f)) {
+                    let triangle = self.triangles[*f];
+                    let corners = triangle.map(|v| get_position(&self.points[v]));
+                    let moved = triangle.map(|v| get_position(if v == a || v == b { &collapse.point } else { &self.points[v] }));
+
+                    let before = normalize(cross(&sub(corners[1], corners[0]), &sub(corners[2], corners[0])));
+                    let after = normalize(cross(&sub(moved[1], moved[0]), &sub(moved[2], moved[0])));
+                    match (before, after) {
+                        (Some(before), Some(after)) if before[0] * after[0] + before[1] * after[1] + before[2] * after[2] > 0.0 => {},
+                        (None, _) => {},
+                        _ => return false,
+                    }
+                }
+            }
+            return true;
+        }
+
+        /// Merges b into a, returns the number of removed triangles
+        fn collapse(&mut self, collapse : Collapse) -> usize {
+
+            let (a, b) = (collapse.a, collapse.b);
+            let shared = self.get_shared_faces(a, b);
+            for f in &shared {
+                self.face_alive[*f] = false;
+                for v in self.triangles[*f] {
+                    self.vertex_faces[v].retain(|g| g != f);
+                }
+            }
+
+            for f in std::mem::take(&mut self.vertex_faces[b]) {
+                self.triangles[f].iter_mut().filter(|v| **v == b).for_each(|v| *v = a);
+                self.vertex_faces[a].push(f);
+            }
+
+            //The properties are interpolated at the projection of the new point onto the edge
+            let edge = self.points[b].iter().zip(&self.points[a]).map(|(x, y)| x - y).collect::<Vec<f64>>();
+            let length = edge.iter().map(|x| x * x).sum::<f64>();
+            let t = match length > 0.0 {
+                true => (collapse.point.iter().zip(&self.points[a]).zip(&edge).map(|((p, x), e)| (p - x) * e).sum::<f64>() / length).clamp(0.0, 1.0),
+                false => 0.5,
+            };
+            let mut sources = self.sources[a].iter().map(|(v, weight)| (*v, weight * (1.0 - t))).collect::<ElementSources>();
+            for (v, weight) in std::mem::take(&mut self.sources[b]) {
+                match sources.iter_mut().find(|(w, _)| *w == v) {
+                    Some(source) => source.1 += weight * t,
+                    None => sources.push((v, weight * t)),
+                }
+            }
+
+            self.sources[a] = sources;
+            self.quadrics[a] = self.quadrics[a].add(&self.quadrics[b]);
+            self.points[a] = collapse.point;
+            self.boundary[a] |= self.boundary[b];
+            self.versions[a] += 1;
+            self.vertex_alive[b] = false;
+            self.merged_into[b] = a;
+            return shared.len();
+        }
+    }
+
+    /// Simplifies a triangle mesh by collapsing edges in the order of their quadric error, as proposed by Garland and Heckbert,
+    /// until the target of the options is reached. The merged vertex is placed where the summed squared distances to the planes
+    /// of the original triangles are minimal. Collapses which would make the mesh non-manifold are skipped.
+    ///
+    /// With attribute weights, the quadrics measure distances in the space of positions, normals and texture coordinates as
+    /// proposed in the extension by Garland and Heckbert, such that collapses distorting these attributes are avoided, e.g. at
+    /// sharp features or uv seams. Boundary edges are either kept entirely, or held in shape by additional planes perpendicular
+    /// to the boundary.
+    ///
+    /// Vertex properties are interpolated between the vertices of each collapsed edge, while edge properties of merged edges
+    /// are combined and the remaining triangles keep their face properties.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut options = DecimationOptions::new(DecimationTarget::FACE_COUNT(mesh.get_face_count() / 2));
+    /// options.uv_weight = 0.1;
+    ///
+    /// let lod = mesh.clone();
+    /// decimate(&mut lod, &options);
+    /// ```
+    pub fn decimate<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, options : &DecimationOptions) -> DecimationResult
+    {
+        let vertex_count = mesh.get_vertices().len();
+        let to_f64 = |x : U| -> f64 { x.to_f64().unwrap() };
+
+        //Points are made of the positions and the weighted attributes
+        let mut points = mesh.get_vertices().iter().map(|v| Vec::from([to_f64(v.x()), to_f64(v.y()), to_f64(v.z())])).collect::<Vec<Vec<f64>>>();
+        let normals = mesh.get_vertex_property_ref::<NormalMap<T, U>, T>(PropertyType::NORMAL)
+            .filter(|map| options.normal_weight > 0.0 && map.len() == vertex_count);
+        if let Some(normals) = normals {
+            for (v, point) in points.iter_mut().enumerate() {
+                let normal = normals.get(v);
+                point.extend([normal.x(), normal.y(), normal.z()].map(|x| to_f64(x) * options.normal_weight));
+            }
+        }
+        let uvs = mesh.get_vertex_property_ref::<UvMap<U>, Vec<[U; 2]>>(PropertyType::UV)
+            .filter(|map| options.uv_weight > 0.0 && map.len() == vertex_count);
+        if let Some(uvs) = uvs {
+            for (v, point) in points.iter_mut().enumerate() {
+                point.extend(uvs.get(v).iter().flat_map(|uv| uv.map(|x| to_f64(x) * options.uv_weight)));
+            }
+        }
+        let size = points.first().map(|p| p.len()).unwrap_or(3);
+
+        let triangles = mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect::<Vec<[usize; 3]>>();
+        let face_count = triangles.len();
+
+        let mut vertex_faces = vec![Vec::<usize>::new(); vertex_count];
+        let mut quadrics = vec![Quadric::zero(size); vertex_count];
+        for (f, [a, b, c]) in triangles.iter().enumerate() {
+            let quadric = Quadric::from_triangle(&points[*a], &points[*b], &points[*c]);
+            for v in [a, b, c] {
+                vertex_faces[*v].push(f);
+                quadrics[*v] = quadrics[*v].add(&quadric);
+            }
+        }
+
+        let edges = MeshEdges::from_faces(triangles.iter());
+        let mut edge_faces = vec![Vec::<usize>::new(); edges.get_edge_count()];
+        for f in 0..face_count {
+            edges.get_face_edges(f).iter().for_each(|e| edge_faces[*e].push(f));
+        }
+
+        let mut boundary = vec![false; vertex_count];
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            if edge_faces[e].len() == 1 {
+                boundary[*a] = true;
+                boundary[*b] = true;
+
+                let [p, q, r] = triangles[edge_faces[e][0]].map(|v| get_position(&points[v]));
+                let Some(normal) = normalize(cross(&sub(q, p), &sub(r, p))) else { continue; };
+                let Some(perpendicular) = normalize(cross(&sub(get_position(&points[*b]), get_position(&points[*a])), &normal)) else { continue; };
+                let quadric = Quadric::from_plane(size, perpendicular, &points[*a], BOUNDARY_WEIGHT);
+                quadrics[*a] = quadrics[*a].add(&quadric);
+                quadrics[*b] = quadrics[*b].add(&quadric);
+            }
+        }
+
+        let mut decimation = Decimation {
+            options,
+            points,
+            quadrics,
+            sources: (0..vertex_count).map(|v| Vec::from([(v, 1.0)])).collect(),
+            versions: vec![0; vertex_count],
+            vertex_alive: vec![true; vertex_count],
+            boundary,
+            merged_into: (0..vertex_count).collect(),
+            triangles,
+            face_alive: vec![true; face_count],
+            vertex_faces,
+        };
+
+        let mut heap = edges.get_edges().iter().filter_map(|[a, b]| decimation.evaluate(*a, *b)).collect::<BinaryHeap<Collapse>>();
+        let mut result = DecimationResult::default();
+        let mut remaining_faces = face_count;
+
+        while let Some(collapse) = heap.pop() {
+
+            let done = match options.target {
+                DecimationTarget::FACE_COUNT(count) => remaining_faces <= count,
+                DecimationTarget::ERROR(error) => collapse.cost > error,
+            };
+            if done {
+                break;
+            }
+
+            let (a, b) = (collapse.a, collapse.b);
+            let is_current = decimation.vertex_alive[a] && decimation.vertex_alive[b] &&
+                collapse.versions == (decimation.versions[a], decimation.versions[b]);
+            if !is_current || !decimation.is_valid(&collapse) {
+                continue;
+            }
+
+            result.max_error = result.max_error.max(collapse.cost);
+            remaining_faces -= decimation.collapse(collapse);
+            result.collapsed_edges += 1;
+
+            //Edges of the merged vertex have a new cost, while their old entries in the heap are outdated
+            for neighbour in decimation.get_neighbours(a) {
+                if let Some(collapse) = decimation.evaluate(a, neighbour) {
+                    heap.push(collapse);
+                }
+            }
+        }
+
+        result.removed_faces = face_count - remaining_faces;
+        if result.collapsed_edges == 0 {
+            return result;
+        }
+
+        //Remaining vertices are compacted, and each original vertex is mapped to the vertex it was merged into
+        let mut new_index = vec![usize::MAX; vertex_count];
+        let mut new_vertices = Vec::<T>::new();
+        let mut vertex_sources = Vec::<ElementSources>::new();
+        for v in (0..vertex_count).filter(|v| decimation.vertex_alive[*v]) {
+            let point = &decimation.points[v];
+            new_index[v] = new_vertices.len();
+            new_vertices.push(T::new(U::from(point[0]).unwrap(), U::from(point[1]).unwrap(), U::from(point[2]).unwrap()));
+            vertex_sources.push(std::mem::take(&mut decimation.sources[v]));
+        }
+        let vertex_map = (0..vertex_count).map(|v| {
+            let mut target = v;
+            while !decimation.vertex_alive[target] {
+                target = decimation.merged_into[target];
+            }
+            return new_index[target];
+        }).collect::<Vec<usize>>();
+
+        let faces = (0..face_count).filter(|f| decimation.face_alive[*f]).collect::<Vec<usize>>();
+        let indices = faces.iter().flat_map(|f| decimation.triangles[*f].map(|v| NumCast::from(new_index[v]).unwrap())).collect::<Vec<IndexType>>();
+        let face_sources = faces.iter().map(|f| Vec::from([(*f, 1.0)])).collect::<Vec<ElementSources>>();
+        let edge_sources = match mesh.get_edge_properties().is_empty() {
+            true => None,
+            false => Some(get_merged_edge_sources(&edges, &MeshEdges::from_faces(indices.chunks(3).map(|t| {
+                return t.iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>();
+            })), &vertex_map)),
+        };
+        mesh.replace(new_vertices, &vertex_sources, indices, &face_sources, edge_sources.as_deref());
+        return result;
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::common_properties::AttributeMap;
+        use crate::common_properties::UvMap;
+        use crate::fixtures::create_grid;
+        use crate::fixtures::create_sphere;
+        use crate::mesh::Mesh;
+        use crate::mesh_decimation::decimation::DecimationOptions;
+        use crate::mesh_decimation::decimation::DecimationTarget;
+        use crate::mesh_decimation::decimation::decimate;
+        use crate::mesh_edges::MeshEdges;
+        use crate::property_map::PropertyHandle;
+        use crate::property_map::PropertyMap;
+        use crate::property_map::VertexProperties;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::FloatVector;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+        use crate::vector::Vector;
+
+        fn get_area(mesh : &TriangleVertexMesh<Vec3f, f32, u32>) -> f32 {
+
+            return mesh.get_indices().chunks(3).map(|t| {
+                let [a, b, c] = [t[0], t[1], t[2]].map(|v| mesh.get_vertices()[v as usize]);
+                return Vec3f::cross(b - a, c - a).z() * 0.5;
+            }).sum();
+        }
+
+        #[test]
+        fn test_decimate_plane() {
+
+            let mut grid = create_grid::<Vec3f, f32>(8);
+            let handle = PropertyHandle::<AttributeMap<f32>>::named("x");
+            let x = grid.get_vertices().iter().map(|v| v.x()).collect::<Vec<f32>>();
+            grid.add_named_vertex_property(&handle, AttributeMap::from(x).with_blending());
+
+            let mut options = DecimationOptions::new(DecimationTarget::ERROR(1e-9));
+            options.preserve_boundaries = true;
+            let result = decimate(&mut grid, &options);
+
+            //Interior vertices of the plane are removed without error, while the boundary stays unchanged
+            assert!(result.max_error < 1e-9);
+            assert_eq!(result.removed_faces, 128 - grid.get_face_count());
+            assert!(grid.get_face_count() < 40);
+            assert!((get_area(&grid) - 1.0).abs() < 1e-5);
+            assert!(grid.get_vertices().iter().all(|v| v.z() == 0.0));
+            assert_eq!(grid.get_vertices().iter().filter(|v| v.x() == 0.0 || v.x() == 1.0 || v.y() == 0.0 || v.y() == 1.0).count(), 32);
+
+            //Vertex properties are interpolated along the collapsed edges
+            let x = grid.get_named_vertex_property_ref(&handle).unwrap();
+            for (v, vertex) in grid.get_vertices().iter().enumerate() {
+                assert!((x.get(v) - vertex.x()).abs() < 1e-5);
+            }
+        }
+
+        #[test]
+        fn test_decimate_with_uv_weight() {
+
+            //Texture coordinates which are not linear on the plane
+            let decimated_face_count = |uv_weight : f64| -> usize {
+                let mut grid = create_grid::<Vec3f, f32>(8);
+                let uvs = grid.get_vertices().iter().map(|v| [v.x() * v.x() + v.y() * v.y(), v.y()]).collect::<Vec<[f32; 2]>>();
+                grid.add_vertex_property(UvMap::from(uvs));
+
+                let mut options = DecimationOptions::new(DecimationTarget::ERROR(1e-6));
+                options.preserve_boundaries = true;
+                options.uv_weight = uv_weight;
+                decimate(&mut grid, &options);
+                return grid.get_face_count();
+            };
+
+            assert!(decimated_face_count(1.0) > decimated_face_count(0.0) + 20);
+        }
+
+        #[test]
+        fn test_decimate_to_face_count() {
+
+            let mut sphere = create_sphere::<Vec3f, f32>(3, 1.0);
+            assert_eq!(sphere.get_face_count(), 512);
+
+            let result = decimate(&mut sphere, &DecimationOptions::new(DecimationTarget::FACE_COUNT(100)));
+            assert!(sphere.get_face_count() <= 100 && sphere.get_face_count() >= 98);
+            assert_eq!(result.removed_faces, 512 - sphere.get_face_count());
+            assert_eq!(sphere.get_vertices().len(), sphere.get_face_count() / 2 + 2);
+
+            //The sphere stays closed and all triangles still face outwards
+            let edges = MeshEdges::from_mesh(&sphere);
+            assert_eq!(edges.get_edge_count() * 2, sphere.get_face_count() * 3);
+            for t in sphere.get_indices().chunks(3) {
+                let [a, b, c] = [t[0], t[1], t[2]].map(|v| sphere.get_vertices()[v as usize]);
+                assert!(Vec3f::dot(&Vec3f::cross(b - a, c - a), &(a + b + c)) > 0.0);
+            }
+            assert!(sphere.get_vertices().iter().all(|v| (v.length() - 1.0).abs() < 0.1));
+        }
+    }
+}
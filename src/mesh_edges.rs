@@ -0,0 +1,244 @@
+//! # Mesh Edges
+//!
+//! Meshes only store their faces, while edges are given implicitly by two consecutive corners of a face.
+//! This module enumerates the unique edges of a mesh, so that they can be addressed by an id, e.g. to
+//! attach properties like crease flags or seam markers to them.
+//!
+//! The id of an edge is its position in the order of first appearance, when traversing the corners of all
+//! faces in order. Ids are therefore stable, as long as the faces of the mesh are unchanged. Operations changing
+//! the faces carry edge properties over to the new ids, by matching the vertices of the edges.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use num_traits::Float;
+use num_traits::Num;
+use num_traits::NumCast;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+
+use crate::mesh::Mesh;
+use crate::mesh_components::MeshComponent;
+use crate::property_map::ElementSources;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+pub struct MeshEdges {
+
+    edges : Vec<[usize; 2]>,
+    edge_ids : HashMap<(usize, usize), usize>,
+    face_edges : Vec<usize>,
+    face_offsets : Vec<usize>,
+}
+
+impl MeshEdges {
+
+    /// Enumerates the edges of a mesh
+    ///
+    /// # Examples
+    /// ```
+    /// let edges = MeshEdges::from_mesh(&mesh);
+    /// let crease = edges.find_edge(3, 7).map(|e| creases.get(e));
+    /// ```
+    pub fn from_mesh<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &MeshType) -> Self {
+        return Self::from_faces((0..mesh.get_face_count()).map(|f| {
+            return mesh.get_face(NumCast::from(f).unwrap()).iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>();
+        }));
+    }
+
+    /// Enumerates the edges of a list of faces, given by their vertex indices
+    pub fn from_faces<F : AsRef<[usize]>>(faces : impl Iterator<Item = F>) -> Self {
+
+        let mut mesh_edges = Self {
+            edges: Vec::new(),
+            edge_ids: HashMap::new(),
+            face_edges: Vec::new(),
+            face_offsets: Vec::from([0]),
+        };
+
+        for face in faces {
+            let face = face.as_ref();
+            for i in 0..face.len() {
+                let a = face[i];
+                let b = face[(i + 1) % face.len()];
+                let key = (a.min(b), a.max(b));
+
+                let next_id = mesh_edges.edges.len();
+                let id = *mesh_edges.edge_ids.entry(key).or_insert(next_id);
+                if id == next_id {
+                    mesh_edges.edges.push([key.0, key.1]);
+                }
+                mesh_edges.face_edges.push(id);
+            }
+            mesh_edges.face_offsets.push(mesh_edges.face_edges.len());
+        }
+        return mesh_edges;
+    }
+
+    pub fn get_edge_count(&self) -> usize {
+        return self.edges.len();
+    }
+
+    /// Returns the vertices of the edge, where the smaller vertex index comes first
+    pub fn get_edge(&self, id : usize) -> [usize; 2] {
+        return self.edges[id];
+    }
+
+    pub fn get_edges(&self) -> &[[usize; 2]] {
+        return &self.edges;
+    }
+
+    /// Returns the id of the edge between two vertices in any order, if there is one
+    pub fn find_edge(&self, a : usize, b : usize) -> Option<usize> {
+        return self.edge_ids.get(&(a.min(b), a.max(b))).copied();
+    }
+
+    /// Returns the edges of a face, where edge i connects corner i with corner i + 1
+    pub fn get_face_edges(&self, face : usize) -> &[usize] {
+        return &self.face_edges[self.face_offsets[face]..self.face_offsets[face + 1]];
+    }
+
+    pub fn get_face_count(&self) -> usize {
+        return self.face_offsets.len() - 1;
+    }
+
+    /// Fills an incidence map between edges and other components, like [Mesh::make_incidence_map].
+    /// Edges are incident to their two vertices, the faces they border and all edges sharing a vertex with them.
+    pub(in crate) fn make_incidence_map<IndexType : PrimInt + Hash + Unsigned>(&self, origin_type : MeshComponent, incident_type : MeshComponent,
+        map : &mut HashMap<IndexType, HashSet<IndexType>>)
+    {
+        let index = |idx : usize| -> IndexType { NumCast::from(idx).unwrap() };
+        let mut insert = |origin : usize, incident : usize| {
+            map.entry(index(origin)).or_default().insert(index(incident));
+        };
+
+        match (&origin_type, &incident_type) {
+            (MeshComponent::VERTEX, MeshComponent::EDGE) | (MeshComponent::EDGE, MeshComponent::VERTEX) => {
+                for (e, edge) in self.edges.iter().enumerate() {
+                    for v in edge {
+                        if origin_type == MeshComponent::EDGE { insert(e, *v); } else { insert(*v, e); }
+                    }
+                }
+            }
+            (MeshComponent::FACE, MeshComponent::EDGE) | (MeshComponent::EDGE, MeshComponent::FACE) => {
+                for f in 0..self.get_face_count() {
+                    for e in self.get_face_edges(f) {
+                        if origin_type == MeshComponent::EDGE { insert(*e, f); } else { insert(f, *e); }
+                    }
+                }
+            }
+            (MeshComponent::EDGE, MeshComponent::EDGE) => {
+                let mut vertex_edges = HashMap::<usize, Vec<usize>>::new();
+                for (e, edge) in self.edges.iter().enumerate() {
+                    vertex_edges.entry(edge[0]).or_default().push(e);
+                    vertex_edges.entry(edge[1]).or_default().push(e);
+                }
+                for (e, edge) in self.edges.iter().enumerate() {
+                    for neighbour in edge.iter().flat_map(|v| vertex_edges[v].iter()).filter(|n| **n != e) {
+                        insert(e, *neighbour);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Finds the old edge each new edge lies on, after the vertices were replaced by new ones made of the weighted old
+/// vertices vertex_sources. A new edge lies on an old edge, if the sources of both of its vertices are vertices of the old
+/// edge, e.g. both halves of a split edge have the split edge as source. Other new edges, e.g. those inside an old face,
+/// have no sources.
+pub(in crate) fn get_edge_sources(old_edges : &MeshEdges, new_edges : &MeshEdges, vertex_sources : &[ElementSources]) -> Vec<ElementSources> {
+
+    let mut edge_sources = Vec::<ElementSources>::with_capacity(new_edges.get_edge_count());
+    for [a, b] in new_edges.get_edges() {
+
+        let mut vertices = vertex_sources[*a].iter().chain(vertex_sources[*b].iter()).map(|(v, _)| *v).collect::<Vec<usize>>();
+        vertices.sort();
+        vertices.dedup();
+
+        let source = match vertices.as_slice() {
+            [x, y] => old_edges.find_edge(*x, *y),
+            _ => None,
+        };
+        edge_sources.push(source.map(|e| Vec::from([(e, 1.0)])).unwrap_or_default());
+    }
+    return edge_sources;
+}
+
+/// Finds the old edges each new edge is made of, after old vertices were merged into the new vertices vertex_map.
+/// Old edges merged into the same new edge have equal weights.
+pub(in crate) fn get_merged_edge_sources(old_edges : &MeshEdges, new_edges : &MeshEdges, vertex_map : &[usize]) -> Vec<ElementSources> {
+
+    let mut edge_sources = vec![ElementSources::new(); new_edges.get_edge_count()];
+    for (e, [x, y]) in old_edges.get_edges().iter().enumerate() {
+        if let Some(new_edge) = new_edges.find_edge(vertex_map[*x], vertex_map[*y]) {
+            edge_sources[new_edge].push((e, 1.0));
+        }
+    }
+
+    for sources in edge_sources.iter_mut() {
+        let weight = 1.0 / sources.len() as f64;
+        sources.iter_mut().for_each(|source| source.1 = weight);
+    }
+    return edge_sources;
+}
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::mesh_components::MeshComponent;
+    use crate::mesh_edges::MeshEdges;
+    use crate::mesh_edges::get_edge_sources;
+    use crate::mesh_edges::get_merged_edge_sources;
+
+    #[test]
+    fn test_edge_ids() {
+
+        //Two triangles sharing the diagonal of a square
+        let edges = MeshEdges::from_faces([[0, 1, 2], [0, 2, 3]].iter());
+
+        assert_eq!(edges.get_edge_count(), 5);
+        assert_eq!(edges.get_edges(), &[[0, 1], [1, 2], [0, 2], [2, 3], [0, 3]]);
+        assert_eq!(edges.find_edge(2, 0), Some(2));
+        assert_eq!(edges.find_edge(1, 3), None);
+        assert_eq!(edges.get_face_edges(1), &[2, 3, 4]);
+
+        let mut map = std::collections::HashMap::new();
+        edges.make_incidence_map::<u32>(MeshComponent::EDGE, MeshComponent::FACE, &mut map);
+        assert_eq!(map[&2].len(), 2);
+        assert_eq!(map[&0].len(), 1);
+    }
+
+    #[test]
+    fn test_edge_sources() {
+
+        //The square is split into four triangles by a new center vertex
+        let old_edges = MeshEdges::from_faces([[0, 1, 2], [0, 2, 3]].iter());
+        let new_edges = MeshEdges::from_faces([[0, 1, 4], [1, 2, 4], [2, 3, 4], [3, 0, 4]].iter());
+
+        let mut vertex_sources = (0..4).map(|v| Vec::from([(v, 1.0)])).collect::<Vec<Vec<(usize, f64)>>>();
+        vertex_sources.push(Vec::from([(0, 0.5), (2, 0.5)]));
+
+        let edge_sources = get_edge_sources(&old_edges, &new_edges, &vertex_sources);
+
+        assert_eq!(edge_sources[new_edges.find_edge(0, 1).unwrap()], Vec::from([(0, 1.0)]));
+        assert_eq!(edge_sources[new_edges.find_edge(2, 3).unwrap()], Vec::from([(3, 1.0)]));
+        assert_eq!(edge_sources[new_edges.find_edge(0, 4).unwrap()], Vec::from([(2, 1.0)]));
+        assert!(edge_sources[new_edges.find_edge(1, 4).unwrap()].is_empty());
+    }
+
+    #[test]
+    fn test_merged_edge_sources() {
+
+        //Two triangles with separate vertices, which are welded along the diagonal
+        let old_edges = MeshEdges::from_faces([[0, 1, 2], [3, 4, 5]].iter());
+        let new_edges = MeshEdges::from_faces([[0, 1, 2], [0, 2, 3]].iter());
+
+        let edge_sources = get_merged_edge_sources(&old_edges, &new_edges, &[0, 1, 2, 0, 2, 3]);
+
+        assert_eq!(edge_sources[new_edges.find_edge(0, 2).unwrap()], Vec::from([(2, 0.5), (3, 0.5)]));
+        assert_eq!(edge_sources[new_edges.find_edge(2, 3).unwrap()], Vec::from([(4, 1.0)]));
+    }
+}
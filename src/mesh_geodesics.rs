@@ -0,0 +1,701 @@
+pub mod geodesics {
+
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+    use std::collections::HashMap;
+    use std::collections::VecDeque;
+    use std::f64::consts::PI;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::common_properties::AttributeMap;
+    use crate::math::Point;
+    use crate::math::cross;
+    use crate::math::dot;
+    use crate::math::from_point;
+    use crate::math::get_distance;
+    use crate::math::sub;
+    use crate::math::to_point;
+    use crate::mesh::Mesh;
+    use crate::mesh_components::MeshComponent;
+    use crate::mesh_edges::MeshEdges;
+    use crate::mesh_smoothing::smoothing::create_cotangent_laplacian;
+    use crate::mesh_smoothing::smoothing::get_vertex_areas;
+    use crate::property_map::PropertyMap;
+    use crate::sparse_matrix::SparseMatrix;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Relative residual, at which the linear systems of the heat method are solved
+    const SOLVER_TOLERANCE : f64 = 1e-10;
+
+    /// Relative tolerance, below which windows of the exact method are too narrow to be propagated
+    const WINDOW_TOLERANCE : f64 = 1e-10;
+
+    fn get_distance_2d(a : &[f64; 2], b : &[f64; 2]) -> f64 {
+        return ((a[0] - b[0]) * (a[0] - b[0]) + (a[1] - b[1]) * (a[1] - b[1])).sqrt();
+    }
+
+    fn get_points_and_triangles<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> (Vec<Point>, Vec<[usize; 3]>)
+    {
+        let points = mesh.get_vertices().iter().map(to_point).collect();
+        let triangles = mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect();
+        return (points, triangles);
+    }
+
+    fn get_vertex_faces(vertex_count : usize, triangles : &[[usize; 3]]) -> Vec<Vec<usize>> {
+        let mut vertex_faces = vec![Vec::<usize>::new(); vertex_count];
+        for (f, triangle) in triangles.iter().enumerate() {
+            triangle.iter().for_each(|v| vertex_faces[*v].push(f));
+        }
+        return vertex_faces;
+    }
+
+    fn get_source_indices<IndexType : PrimInt + Hash + Unsigned>(sources : &[IndexType], vertex_count : usize) -> Vec<usize> {
+        return sources.iter().map(|s| s.to_usize().unwrap()).filter(|s| *s < vertex_count).collect();
+    }
+
+    fn to_attribute_map<U : Num + PartialOrd<U> + Float>(distances : &[f64]) -> AttributeMap<U> {
+        return AttributeMap::from(distances.iter().map(|d| U::from(*d).unwrap_or(U::infinity())).collect());
+    }
+
+    /// Gradient of a function, which is linear on the triangle and has the given values at its corners
+    fn get_gradient(corners : [&Point; 3], values : [f64; 3]) -> Option<Point> {
+
+        let area_normal = cross(&sub(corners[1], corners[0]), &sub(corners[2], corners[0]));
+        let double_area = dot(&area_normal, &area_normal).sqrt();
+        if double_area <= f64::EPSILON {
+            return None;
+        }
+
+        //Sum of the values times the edges opposite of them, rotated by 90 degrees within the triangle
+        let normal = area_normal.map(|x| x / double_area);
+        let mut gradient = [0.0; 3];
+        for i in 0..3 {
+            let rotated = cross(&normal, &sub(corners[(i + 2) % 3], corners[(i + 1) % 3]));
+            (0..3).for_each(|c| gradient[c] += values[i] * rotated[c] / double_area);
+        }
+        return Some(gradient);
+    }
+
+    /// Entry of the priority queues, ordered such that the smallest distance comes first
+    struct QueueEntry<E> {
+        distance : f64,
+        element : E,
+    }
+
+    impl<E> PartialEq for QueueEntry<E> {
+        fn eq(&self, other : &Self) -> bool {
+            return self.distance == other.distance;
+        }
+    }
+
+    impl<E> Eq for QueueEntry<E> {}
+
+    impl<E> PartialOrd for QueueEntry<E> {
+        fn partial_cmp(&self, other : &Self) -> Option<Ordering> {
+            return Some(self.cmp(other));
+        }
+    }
+
+    impl<E> Ord for QueueEntry<E> {
+        fn cmp(&self, other : &Self) -> Ordering {
+            return other.distance.total_cmp(&self.distance);
+        }
+    }
+
+    /// Creates a map with the shortest distance of each vertex to the closest source vertex along the edges of the mesh,
+    /// computed with Dijkstra's algorithm. This is fast and works on any mesh, but overestimates the distance across faces,
+    /// e.g. by up to 8% on regular triangle grids. Vertices which cannot be reached have an infinite distance.
+    ///
+    /// # Examples
+    /// ```
+    /// let distances = create_graph_distances(&mesh, &[0]);
+    /// let radius = distances.as_slice().iter().copied().filter(|d| d.is_finite()).fold(0.0, f32::max);
+    /// ```
+    pub fn create_graph_distances<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType, sources : &[IndexType]) -> AttributeMap<U>
+    {
+        let points = mesh.get_vertices().iter().map(to_point).collect::<Vec<Point>>();
+        let incidence = mesh.make_incidence_map(MeshComponent::VERTEX, MeshComponent::VERTEX);
+
+        let mut distances = vec![f64::INFINITY; points.len()];
+        let mut queue = BinaryHeap::<QueueEntry<usize>>::new();
+        for source in get_source_indices(sources, points.len()) {
+            distances[source] = 0.0;
+            queue.push(QueueEntry { distance: 0.0, element: source });
+        }
+
+        while let Some(QueueEntry { distance, element : v }) = queue.pop() {
+            if distance > distances[v] {
+                continue;
+            }
+            let Some(neighbours) = incidence.get(&NumCast::from(v).unwrap()) else { continue; };
+            for w in neighbours.iter().map(|w| w.to_usize().unwrap()).filter(|w| *w != v) {
+                let candidate = distance + get_distance(&points[v], &points[w]);
+                if candidate < distances[w] {
+                    distances[w] = candidate;
+                    queue.push(QueueEntry { distance: candidate, element: w });
+                }
+            }
+        }
+        return to_attribute_map(&distances);
+    }
+
+    /// Creates a map with the distance of each vertex to the closest source vertex with the heat method by Crane et al.
+    /// Heat flows from the sources for a short time, and the distances are recovered from the direction of the heat flow by
+    /// solving a Poisson equation. The time step is the time factor times the squared mean edge length, where a factor of
+    /// one gives accurate distances on good meshes, while larger factors give smoother distances on noisy meshes.
+    ///
+    /// This is the fastest method for many vertices, as both linear systems only depend on the mesh. The distances are
+    /// approximations, which are most accurate on meshes with well-shaped triangles. Vertices which cannot be reached have an
+    /// infinite distance. Returns None, if the linear systems cannot be solved.
+    ///
+    /// # Examples
+    /// ```
+    /// let distances = create_heat_distances(&mesh, &[0, 17], 1.0).unwrap();
+    /// mesh.add_named_vertex_property(&PropertyHandle::named("distance"), distances);
+    /// ```
+    pub fn create_heat_distances<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>, sources : &[IndexType], time_factor : f64) -> Option<AttributeMap<U>>
+    {
+        let (points, triangles) = get_points_and_triangles(mesh);
+        let vertex_count = points.len();
+        let sources = get_source_indices(sources, vertex_count);
+
+        //Vertices reachable from the sources, labelled with the first source reaching them
+        let mut neighbours = vec![Vec::<usize>::new(); vertex_count];
+        for triangle in &triangles {
+            for i in 0..3 {
+                neighbours[triangle[i]].push(triangle[(i + 1) % 3]);
+                neighbours[triangle[(i + 1) % 3]].push(triangle[i]);
+            }
+        }
+        let mut components = vec![usize::MAX; vertex_count];
+        for source in &sources {
+            if components[*source] != usize::MAX {
+                continue;
+            }
+            components[*source] = *source;
+            let mut queue = VecDeque::from([*source]);
+            while let Some(v) = queue.pop_front() {
+                for w in &neighbours[v] {
+                    if components[*w] == usize::MAX {
+                        components[*w] = *source;
+                        queue.push_back(*w);
+                    }
+                }
+            }
+        }
+
+        let edges = MeshEdges::from_faces(triangles.iter());
+        if edges.get_edge_count() == 0 || sources.is_empty() {
+            return Some(to_attribute_map(&vec![f64::INFINITY; vertex_count]));
+        }
+        let mean_length = edges.get_edges().iter().map(|[a, b]| get_distance(&points[*a], &points[*b])).sum::<f64>() / edges.get_edge_count() as f64;
+        let time = time_factor * mean_length * mean_length;
+
+        //Heat flow (M + t L) u = u0 for one implicit step, with the lumped mass matrix M
+        let laplacian = create_cotangent_laplacian(mesh);
+        let areas = get_vertex_areas(mesh);
+        let mut triplets = Vec::<(usize, usize, f64)>::new();
+        for (row, area) in areas.iter().enumerate() {
+            triplets.extend(laplacian.get_row(row).map(|(column, value)| (row, column, time * value)));
+            triplets.push((row, row, *area));
+        }
+        let heat_operator = SparseMatrix::from_triplets(vertex_count, vertex_count, &triplets);
+        let mut initial = vec![0.0; vertex_count];
+        sources.iter().for_each(|s| initial[*s] = 1.0);
+        let max_iterations = 10 * vertex_count + 100;
+        let heat = heat_operator.solve_conjugate_gradient(&initial, SOLVER_TOLERANCE, max_iterations)?;
+
+        //Integrated divergence of the normalized field pointing away from the sources
+        let mut divergence = vec![0.0; vertex_count];
+        for triangle in &triangles {
+            let corners = triangle.map(|v| &points[v]);
+            let Some(gradient) = get_gradient(corners, triangle.map(|v| heat[v])) else { continue; };
+            let length = dot(&gradient, &gradient).sqrt();
+            if length <= 0.0 {
+                continue;
+            }
+            let field = gradient.map(|x| -x / length);
+
+            let area_normal = cross(&sub(corners[1], corners[0]), &sub(corners[2], corners[0]));
+            let double_area = dot(&area_normal, &area_normal).sqrt();
+            let cotangent = |i : usize| -> f64 {
+                let (u, v) = (sub(corners[(i + 1) % 3], corners[i]), sub(corners[(i + 2) % 3], corners[i]));
+                return dot(&u, &v) / double_area;
+            };
+            for i in 0..3 {
+                let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+                let to_j = dot(&sub(corners[j], corners[i]), &field);
+                let to_k = dot(&sub(corners[k], corners[i]), &field);
+                divergence[triangle[i]] += 0.5 * (cotangent(k) * to_j + cotangent(j) * to_k);
+            }
+        }
+
+        //The Poisson equation L phi = -div is singular, so the right side is made consistent within each component
+        let mut sums = HashMap::<usize, (f64, usize)>::new();
+        for v in (0..vertex_count).filter(|v| components[*v] != usize::MAX) {
+            let sum = sums.entry(components[v]).or_insert((0.0, 0));
+            sum.0 += divergence[v];
+            sum.1 += 1;
+        }
+        let rhs = (0..vertex_count).map(|v| match sums.get(&components[v]) {
+            Some((sum, count)) => -(divergence[v] - sum / *count as f64),
+            None => 0.0,
+        }).collect::<Vec<f64>>();
+        let potential = laplacian.solve_conjugate_gradient(&rhs, SOLVER_TOLERANCE, max_iterations)?;
+
+        //Distances are shifted such that the closest vertex of each component has distance zero
+        let mut minima = HashMap::<usize, f64>::new();
+        for v in (0..vertex_count).filter(|v| components[*v] != usize::MAX) {
+            let minimum = minima.entry(components[v]).or_insert(f64::INFINITY);
+            *minimum = minimum.min(potential[v]);
+        }
+        let distances = (0..vertex_count).map(|v| match minima.get(&components[v]) {
+            Some(minimum) => potential[v] - minimum,
+            None => f64::INFINITY,
+        }).collect::<Vec<f64>>();
+        return Some(to_attribute_map(&distances));
+    }
+
+    /// Part of an edge reached by straight lines from a source, when the faces between them are unfolded into a plane.
+    /// The window lies on the edge from corner k to corner k + 1 of a face, which is placed on the x-axis with the face at
+    /// positive y, and covers the interval [b0, b1] of it. The source, which may also be a saddle or boundary vertex with
+    /// the distance sigma, lies at negative y.
+    struct Window {
+        face : usize,
+        corner : usize,
+        interval : [f64; 2],
+        source : [f64; 2],
+        sigma : f64,
+    }
+
+    enum Event {
+        Window(usize),
+        Vertex(usize),
+    }
+
+    /// Propagation of windows over the faces in the order of their distance, following the improved Chen-Han algorithm by
+    /// Xin and Wang. Shortest paths only pass through saddle and boundary vertices, which become new sources once reached.
+    struct WindowPropagation<'a> {
+        points : &'a [Point],
+        triangles : &'a [[usize; 3]],
+        vertex_faces : Vec<Vec<usize>>,
+        pseudo_sources : Vec<bool>,
+        distances : Vec<f64>,
+        windows : Vec<Window>,
+        queue : BinaryHeap<QueueEntry<Event>>,
+    }
+
+    impl<'a> WindowPropagation<'a> {
+
+        /// Places the face in the plane, with corner k at the origin, corner k + 1 on the positive x-axis and corner k + 2 at positive y
+        fn get_layout(&self, face : usize, corner : usize) -> [[f64; 2]; 3] {
+            let triangle = self.triangles[face];
+            let [a, b, c] = [0, 1, 2].map(|i| &self.points[triangle[(corner + i) % 3]]);
+            let (ab, ac, bc) = (get_distance(a, b), get_distance(a, c), get_distance(b, c));
+            let x = (ac * ac - bc * bc + ab * ab) / (2.0 * ab);
+            return [[0.0, 0.0], [ab, 0.0], [x, (ac * ac - x * x).max(0.0).sqrt()]];
+        }
+
+        fn update_vertex(&mut self, v : usize, distance : f64) {
+            if distance < self.distances[v] {
+                self.distances[v] = distance;
+                if self.pseudo_sources[v] {
+                    self.queue.push(QueueEntry { distance, element: Event::Vertex(v) });
+                }
+            }
+        }
+
+        /// Checks whether one of the vertices of the window's edge is closer to all points of the window than its source.
+        /// The difference of the distance to the source and to a vertex only shrinks when moving away from the vertex, so
+        /// comparing them at the far end of the window suffices.
+        fn is_dominated(&self, face : usize, corner : usize, interval : [f64; 2], source : &[f64; 2], sigma : f64) -> bool {
+            let triangle = self.triangles[face];
+            let length = get_distance(&self.points[triangle[corner]], &self.points[triangle[(corner + 1) % 3]]);
+            let tolerance = WINDOW_TOLERANCE * length;
+            let at = |x : f64| -> f64 { sigma + get_distance_2d(source, &[x, 0.0]) };
+            return self.distances[triangle[corner]] + interval[1] <= at(interval[1]) + tolerance ||
+                self.distances[triangle[(corner + 1) % 3]] + length - interval[0] <= at(interval[0]) + tolerance;
+        }
+
+        /// Adds the window between the hit points on an edge of a face, which is propagated into the face on the other side
+        /// of the edge. All positions are given in the layout of the first face, where the third corner is not on the edge.
+        fn add_window(&mut self, from : usize, edge : [(usize, [f64; 2]); 2], third : [f64; 2], hits : [[f64; 2]; 2], source : [f64; 2], sigma : f64) {
+
+            let (p, q) = (edge[0].0, edge[1].0);
+            let Some(face) = self.vertex_faces[p].iter().copied().find(|f| *f != from && self.triangles[*f].contains(&q)) else { return; };
+            let triangle = self.triangles[face];
+            let corner = (0..3).find(|i| {
+                let (a, b) = (triangle[*i], triangle[(*i + 1) % 3]);
+                return (a == p && b == q) || (a == q && b == p);
+            }).unwrap();
+
+            //Frame of the edge as seen from the next face, whose x-axis starts at its corner k
+            let (start, end) = if triangle[corner] == p { (edge[0].1, edge[1].1) } else { (edge[1].1, edge[0].1) };
+            let length = get_distance_2d(&start, &end);
+            if length <= f64::EPSILON {
+                return;
+            }
+            let axis = [(end[0] - start[0]) / length, (end[1] - start[1]) / length];
+            let mut normal = [-axis[1], axis[0]];
+            if (third[0] - start[0]) * normal[0] + (third[1] - start[1]) * normal[1] > 0.0 {
+                normal = [-normal[0], -normal[1]];
+            }
+            let to_frame = |x : &[f64; 2]| -> [f64; 2] {
+                let d = [x[0] - start[0], x[1] - start[1]];
+                return [d[0] * axis[0] + d[1] * axis[1], d[0] * normal[0] + d[1] * normal[1]];
+            };
+
+            let source = to_frame(&source);
+            let (x0, x1) = (to_frame(&hits[0])[0].clamp(0.0, length), to_frame(&hits[1])[0].clamp(0.0, length));
+            let interval = [x0.min(x1), x0.max(x1)];
+            let tolerance = WINDOW_TOLERANCE * length;
+            if source[1] >= -tolerance || interval[1] - interval[0] <= tolerance || self.is_dominated(face, corner, interval, &source, sigma) {
+                return;
+            }
+
+            if interval[0] <= tolerance {
+                self.update_vertex(triangle[corner], sigma + get_distance_2d(&source, &[0.0, 0.0]));
+            }
+            if interval[1] >= length - tolerance {
+                self.update_vertex(triangle[(corner + 1) % 3], sigma + get_distance_2d(&source, &[length, 0.0]));
+            }
+
+            let closest = [source[0].clamp(interval[0], interval[1]), 0.0];
+            self.queue.push(QueueEntry { distance: sigma + get_distance_2d(&source, &closest), element: Event::Window(self.windows.len()) });
+            self.windows.push(Window { face, corner, interval, source, sigma });
+        }
+
+        /// Propagates a window over its face onto the two other edges, splitting it at the third corner if the corner is visible
+        fn propagate_window(&mut self, w : usize) {
+
+            let Window { face, corner, interval : [b0, b1], source, sigma } = self.windows[w];
+            if self.is_dominated(face, corner, [b0, b1], &source, sigma) {
+                return;
+            }
+
+            let triangle = self.triangles[face];
+            let [a, b, c] = [0, 1, 2].map(|i| triangle[(corner + i) % 3]);
+            let [a_2d, b_2d, c_2d] = self.get_layout(face, corner);
+
+            //Point on the edge from p to q, where the line from the source through x on the x-axis crosses it
+            let hit = |x : f64, p : &[f64; 2], q : &[f64; 2]| -> [f64; 2] {
+                let direction = [x - source[0], -source[1]];
+                let edge = [q[0] - p[0], q[1] - p[1]];
+                let denominator = edge[0] * direction[1] - edge[1] * direction[0];
+                let t = ((source[0] - p[0]) * direction[1] - (source[1] - p[1]) * direction[0]) / denominator;
+                let t = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 };
+                return [p[0] + t * edge[0], p[1] + t * edge[1]];
+            };
+
+            //Position on the x-axis, through which the line from the source to the third corner passes
+            let x_c = source[0] + (c_2d[0] - source[0]) * -source[1] / (c_2d[1] - source[1]);
+            if x_c > b0 && x_c < b1 {
+                self.update_vertex(c, sigma + get_distance_2d(&source, &c_2d));
+            }
+
+            if x_c > b0 {
+                let far = if x_c < b1 { c_2d } else { hit(b1, &a_2d, &c_2d) };
+                self.add_window(face, [(a, a_2d), (c, c_2d)], b_2d, [hit(b0, &a_2d, &c_2d), far], source, sigma);
+            }
+            if x_c < b1 {
+                let near = if x_c > b0 { c_2d } else { hit(b0, &c_2d, &b_2d) };
+                self.add_window(face, [(c, c_2d), (b, b_2d)], a_2d, [near, hit(b1, &c_2d, &b_2d)], source, sigma);
+            }
+        }
+
+        /// Starts windows from a source or saddle vertex over the opposite edges of all its faces
+        fn propagate_vertex(&mut self, v : usize) {
+
+            let sigma = self.distances[v];
+            for face in self.vertex_faces[v].clone() {
+                let triangle = self.triangles[face];
+                let corner = (triangle.iter().position(|w| *w == v).unwrap() + 1) % 3;
+                let (x, y) = (triangle[corner], triangle[(corner + 1) % 3]);
+                let [x_2d, y_2d, v_2d] = self.get_layout(face, corner);
+
+                self.update_vertex(x, sigma + get_distance(&self.points[v], &self.points[x]));
+                self.update_vertex(y, sigma + get_distance(&self.points[v], &self.points[y]));
+                self.add_window(face, [(x, x_2d), (y, y_2d)], v_2d, [x_2d, y_2d], v_2d, sigma);
+            }
+        }
+    }
+
+    /// Creates a map with the exact distance of each vertex to the closest source vertex, along the shortest paths on the
+    /// surface of the mesh, which may cross faces and pass through saddle vertices. The distances are propagated in windows
+    /// of edges reached by straight lines over the unfolded faces, following the improved Chen-Han algorithm by Xin and Wang.
+    ///
+    /// This is the slowest method, with a quadratic number of windows in the worst case, but the distances are exact for the
+    /// piecewise flat surface. Vertices which cannot be reached have an infinite distance.
+    ///
+    /// # Examples
+    /// ```
+    /// let distances = create_exact_distances(&mesh, &[start]);
+    /// let length = distances.get(end);
+    /// ```
+    pub fn create_exact_distances<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>, sources : &[IndexType]) -> AttributeMap<U>
+    {
+        let (points, triangles) = get_points_and_triangles(mesh);
+        let vertex_count = points.len();
+        let vertex_faces = get_vertex_faces(vertex_count, &triangles);
+
+        //Saddle vertices have an angle sum of at least 360 degrees, boundary vertices are treated alike
+        let mut angle_sums = vec![0.0; vertex_count];
+        for triangle in &triangles {
+            for i in 0..3 {
+                let [u, w] = [1, 2].map(|j| sub(&points[triangle[(i + j) % 3]], &points[triangle[i]]));
+                let cosine = dot(&u, &w) / (dot(&u, &u) * dot(&w, &w)).sqrt();
+                if cosine.is_finite() {
+                    angle_sums[triangle[i]] += cosine.clamp(-1.0, 1.0).acos();
+                }
+            }
+        }
+        let edges = MeshEdges::from_faces(triangles.iter());
+        let mut boundary = vec![false; vertex_count];
+        let mut edge_face_counts = vec![0; edges.get_edge_count()];
+        (0..triangles.len()).for_each(|f| edges.get_face_edges(f).iter().for_each(|e| edge_face_counts[*e] += 1));
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            if edge_face_counts[e] != 2 {
+                boundary[*a] = true;
+                boundary[*b] = true;
+            }
+        }
+        let pseudo_sources = (0..vertex_count).map(|v| boundary[v] || angle_sums[v] >= 2.0 * PI * (1.0 - WINDOW_TOLERANCE)).collect();
+
+        let mut propagation = WindowPropagation {
+            points: &points,
+            triangles: &triangles,
+            vertex_faces,
+            pseudo_sources,
+            distances: vec![f64::INFINITY; vertex_count],
+            windows: Vec::new(),
+            queue: BinaryHeap::new(),
+        };
+        for source in get_source_indices(sources, vertex_count) {
+            propagation.distances[source] = 0.0;
+            propagation.queue.push(QueueEntry { distance: 0.0, element: Event::Vertex(source) });
+        }
+
+        while let Some(QueueEntry { distance, element }) = propagation.queue.pop() {
+            match element {
+                Event::Vertex(v) if distance <= propagation.distances[v] => propagation.propagate_vertex(v),
+                Event::Vertex(_) => {},
+                Event::Window(w) => propagation.propagate_window(w),
+            }
+        }
+        return to_attribute_map(&propagation.distances);
+    }
+
+    /// Position on the path, either at a vertex or at the parameter t on the edge from a to b, entered from a face
+    #[derive(Clone, Copy)]
+    enum PathLocation {
+        Vertex(usize),
+        Edge(usize, usize, f64, usize),
+    }
+
+    /// Traces the shortest path from a vertex to the closest source of a distance map, created by one of the geodesic
+    /// distance methods, by walking down the gradient of the distances across the faces. Where the gradient leaves the face
+    /// or the distance falls fastest along an edge, the path follows the edge. Returns the path as a polyline on the surface,
+    /// which starts at the source and ends at the vertex, or an empty list if the vertex cannot be reached.
+    ///
+    /// # Examples
+    /// ```
+    /// let distances = create_heat_distances(&mesh, &[start], 1.0).unwrap();
+    /// let path = trace_geodesic_path(&mesh, &distances, end);
+    /// let length = path.windows(2).map(|p| (p[1] - p[0]).length()).sum::<f32>();
+    /// ```
+    pub fn trace_geodesic_path<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>, distances : &AttributeMap<U>, target : IndexType) -> Vec<T>
+    {
+        let (points, triangles) = get_points_and_triangles(mesh);
+        let target = target.to_usize().unwrap();
+        if distances.len() != points.len() || target >= points.len() || !distances.get(target).is_finite() {
+            return Vec::new();
+        }
+        let values = (0..points.len()).map(|v| distances.get(v).to_f64().unwrap()).collect::<Vec<f64>>();
+        let vertex_faces = get_vertex_faces(points.len(), &triangles);
+
+        let gradient = |f : usize| -> Option<Point> { get_gradient(triangles[f].map(|v| &points[v]), triangles[f].map(|v| values[v])) };
+        let normal = |f : usize| -> Point {
+            let [a, b, c] = triangles[f].map(|v| &points[v]);
+            return cross(&sub(b, a), &sub(c, a));
+        };
+        //Parameter of the point on the edge from a to b, where the ray from the point in the direction within the face crosses it
+        let intersect = |f : usize, point : &Point, direction : &Point, a : usize, b : usize| -> Option<f64> {
+            let (n, edge) = (normal(f), sub(&points[b], &points[a]));
+            let denominator = dot(&cross(&edge, direction), &n);
+            let t = dot(&cross(&sub(point, &points[a]), direction), &n) / denominator;
+            let s = dot(&cross(&sub(point, &points[a]), &edge), &n) / denominator;
+            return (t.is_finite() && (-1e-9..=1.0 + 1e-9).contains(&t) && s > 0.0).then(|| t.clamp(0.0, 1.0));
+        };
+        let edge_point = |a : usize, b : usize, t : f64| -> Point {
+            return [0, 1, 2].map(|c| (1.0 - t) * points[a][c] + t * points[b][c]);
+        };
+        //Snaps parameters at the ends of an edge to its vertices
+        let locate = |a : usize, b : usize, t : f64, f : usize| -> PathLocation {
+            return if t <= 1e-9 { PathLocation::Vertex(a) } else if t >= 1.0 - 1e-9 { PathLocation::Vertex(b) } else { PathLocation::Edge(a, b, t, f) };
+        };
+
+        let mut path = Vec::from([points[target]]);
+        let mut location = PathLocation::Vertex(target);
+        for _ in 0..4 * (points.len() + triangles.len()) {
+
+            let next = match location {
+                PathLocation::Vertex(v) => {
+                    //Steepest descent along an edge or into a face, whichever falls faster
+                    let mut best : Option<(f64, PathLocation)> = None;
+                    for f in &vertex_faces[v] {
+                        for w in triangles[*f].into_iter().filter(|w| *w != v && values[*w] < values[v]) {
+                            let slope = (values[v] - values[w]) / get_distance(&points[v], &points[w]);
+                            if best.as_ref().is_none_or(|(s, _)| slope > *s) {
+                                best = Some((slope, PathLocation::Vertex(w)));
+                            }
+                        }
+
+                        let Some(g) = gradient(*f) else { continue; };
+                        let slope = dot(&g, &g).sqrt();
+                        let corner = triangles[*f].iter().position(|w| *w == v).unwrap();
+                        let (a, b) = (triangles[*f][(corner + 1) % 3], triangles[*f][(corner + 2) % 3]);
+                        let direction = g.map(|x| -x);
+                        let is_steeper = best.as_ref().is_none_or(|(s, _)| slope > *s * (1.0 + 1e-9));
+                        if let Some(t) = intersect(*f, &points[v], &direction, a, b).filter(|t| is_steeper && *t > 1e-9 && *t < 1.0 - 1e-9) {
+                            best = Some((slope, PathLocation::Edge(a, b, t, *f)));
+                        }
+                    }
+                    best.map(|(_, next)| next)
+                }
+                PathLocation::Edge(a, b, t, from) => {
+                    let point = edge_point(a, b, t);
+                    let lower = if values[a] <= values[b] { a } else { b };
+                    match vertex_faces[a].iter().copied().find(|f| *f != from && triangles[*f].contains(&b)) {
+                        None => Some(PathLocation::Vertex(lower)),
+                        Some(f) => {
+                            let c = triangles[f].into_iter().find(|v| *v != a && *v != b).unwrap();
+                            let direction = gradient(f).map(|g| g.map(|x| -x));
+                            //Continues across the face, unless the gradient points back and the path runs down the edge
+                            let crossing = direction.and_then(|d| {
+                                return intersect(f, &point, &d, a, c).map(|s| locate(a, c, s, f))
+                                    .or_else(|| intersect(f, &point, &d, b, c).map(|s| locate(b, c, s, f)));
+                            });
+                            Some(crossing.unwrap_or(PathLocation::Vertex(lower)))
+                        }
+                    }
+                }
+            };
+
+            let Some(next) = next else { break; };
+            let point = match next {
+                PathLocation::Vertex(v) => points[v],
+                PathLocation::Edge(a, b, t, _) => edge_point(a, b, t),
+            };
+            path.push(point);
+            location = next;
+        }
+
+        path.reverse();
+        return path.iter().map(|p| from_point(*p)).collect();
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::fixtures::create_grid;
+        use crate::fixtures::create_sphere;
+        use crate::mesh::Mesh;
+        use crate::mesh_geodesics::geodesics::create_exact_distances;
+        use crate::mesh_geodesics::geodesics::create_graph_distances;
+        use crate::mesh_geodesics::geodesics::create_heat_distances;
+        use crate::mesh_geodesics::geodesics::trace_geodesic_path;
+        use crate::property_map::PropertyMap;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::FloatVector;
+        use crate::vector::Vec3d;
+        use crate::vector::Vec3;
+
+        #[test]
+        fn test_distances_on_plane() {
+
+            //On a plane, the geodesic distance is the straight distance from the corner
+            let grid = create_grid::<Vec3d, f64>(10);
+            let exact = create_exact_distances(&grid, &[0]);
+            let graph = create_graph_distances(&grid, &[0]);
+            let heat = create_heat_distances(&grid, &[0], 1.0).unwrap();
+
+            for (v, vertex) in grid.get_vertices().iter().enumerate() {
+                let distance = vertex.length();
+                assert!((exact.get(v) - distance).abs() < 1e-9);
+                assert!(graph.get(v) >= distance - 1e-9 && graph.get(v) <= 1.09 * distance);
+                assert!((heat.get(v) - distance).abs() < 0.1);
+            }
+
+            //Along the axes, the edges are the shortest paths
+            assert!((graph.get(10) - 1.0).abs() < 1e-9);
+            assert_eq!(graph.get(0), 0.0);
+        }
+
+        #[test]
+        fn test_distances_on_sphere() {
+
+            //Distances from the north pole are close to the length of the great circle arcs
+            let sphere = create_sphere::<Vec3d, f64>(4, 1.0);
+            let exact = create_exact_distances(&sphere, &[4]);
+            let heat = create_heat_distances(&sphere, &[4], 1.0).unwrap();
+            let graph = create_graph_distances(&sphere, &[4]);
+
+            for (v, vertex) in sphere.get_vertices().iter().enumerate() {
+                let arc = vertex.z().clamp(-1.0, 1.0).acos();
+                assert!(exact.get(v) <= arc + 1e-9 && exact.get(v) >= 0.98 * arc);
+                assert!(graph.get(v) >= exact.get(v) - 1e-9);
+                assert!((heat.get(v) - arc).abs() < 0.1);
+            }
+
+            //Separate components cannot be reached
+            let mut vertices = sphere.get_vertices().clone();
+            vertices.push(Vec3d::new(5.0, 0.0, 0.0));
+            let mut indices = sphere.get_indices().clone();
+            indices.extend([vertices.len() as u32 - 1; 3]);
+            let disconnected = TriangleVertexMesh::<Vec3d, f64, u32>::from(vertices, indices).unwrap();
+            assert!(create_exact_distances(&disconnected, &[4]).get(sphere.get_vertices().len()).is_infinite());
+            assert!(create_heat_distances(&disconnected, &[4], 1.0).unwrap().get(sphere.get_vertices().len()).is_infinite());
+        }
+
+        #[test]
+        fn test_geodesic_path() {
+
+            //The path over the plane is a straight line from the source to the target
+            let grid = create_grid::<Vec3d, f64>(10);
+            let target = 10 * 11 + 7;
+            let end = grid.get_vertices()[target];
+            let distances = create_exact_distances(&grid, &[0]);
+            let path = trace_geodesic_path(&grid, &distances, target as u32);
+
+            assert!(path.len() > 2);
+            assert!(path[0].length() < 1e-9);
+            assert!((path[path.len() - 1] - end).length() < 1e-9);
+            assert!(path.iter().all(|p| (p.x() * end.y() - p.y() * end.x()).abs() < 0.02));
+            let length = path.windows(2).map(|p| (p[1] - p[0]).length()).sum::<f64>();
+            assert!(length >= end.length() - 1e-9 && length < 1.01 * end.length());
+
+            //Around the sphere, the path from the heat distances is about as long as the distance
+            let sphere = create_sphere::<Vec3d, f64>(3, 1.0);
+            let distances = create_heat_distances(&sphere, &[4], 1.0).unwrap();
+            let path = trace_geodesic_path(&sphere, &distances, 5);
+            let length = path.windows(2).map(|p| (p[1] - p[0]).length()).sum::<f64>();
+            assert!((path[0] - Vec3d::new(0.0, 0.0, 1.0)).length() < 1e-9);
+            assert!((length - std::f64::consts::PI).abs() < 0.05);
+        }
+    }
+}
@@ -0,0 +1,531 @@
+pub mod mesh_holes {
+
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::mesh::Mesh;
+    use crate::property_map::ElementSources;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// How the patch closing a hole is created
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[allow(non_camel_case_types)]
+    pub enum HoleFilling {
+        /// Triangulates the boundary loop with minimal area, without adding vertices
+        TRIANGULATE = 0,
+        /// Additionally adds vertices to the patch, until its triangles match the size of the surrounding ones
+        REFINE = 1,
+        /// Additionally moves the added vertices, such that the patch forms a smooth membrane
+        REFINE_AND_FAIR = 2,
+    }
+
+    /// Extracts all boundary loops of a mesh, i.e. closed chains of edges which belong to one face only.
+    /// Each loop lists its vertices in the direction in which the adjacent faces traverse the boundary edges, as long as
+    /// these faces are consistently oriented. At vertices where several loops meet, the loops are separated arbitrarily.
+    ///
+    /// # Examples
+    /// ```
+    /// let loops = extract_boundary_loops(&mesh);
+    /// let is_watertight = loops.is_empty();
+    /// ```
+    pub fn extract_boundary_loops<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &MeshType)
+        -> Vec<Vec<usize>>
+    {
+        //Number of faces of each undirected edge, together with the direction in which the first face traverses it
+        let mut edge_faces = HashMap::<(usize, usize), (usize, (usize, usize))>::new();
+        for f in 0..mesh.get_face_count() {
+
+            let face = mesh.get_face(NumCast::from(f).unwrap());
+            let size = face.len();
+            for i in 0..size {
+                let a = face[i].to_usize().unwrap();
+                let b = face[(i + 1) % size].to_usize().unwrap();
+                if a != b {
+                    edge_faces.entry((a.min(b), a.max(b))).or_insert((0, (a, b))).0 += 1;
+                }
+            }
+        }
+
+        //An edge is on the boundary, if it belongs to one face only. Its direction only orders the loop, so interior edges
+        //between inconsistently oriented faces are no boundary.
+        let mut boundary_edges = edge_faces.values().filter(|(count, _)| *count == 1).map(|(_, edge)| *edge).collect::<Vec<(usize, usize)>>();
+        boundary_edges.sort();
+
+        let mut incident = HashMap::<usize, Vec<usize>>::new();
+        for (e, (a, b)) in boundary_edges.iter().enumerate() {
+            incident.entry(*a).or_default().push(e);
+            incident.entry(*b).or_default().push(e);
+        }
+
+        let mut loops = Vec::<Vec<usize>>::new();
+        let mut used = vec![false; boundary_edges.len()];
+
+        for start in 0..boundary_edges.len() {
+
+            if used[start] {
+                continue;
+            }
+            used[start] = true;
+
+            let (first, mut current) = boundary_edges[start];
+            let mut boundary_loop = Vec::from([first]);
+
+            while current != first {
+
+                boundary_loop.push(current);
+
+                //Edges leaving the current vertex in their direction are preferred, but the loop continues over reversed ones
+                let candidates = incident.get(&current).map(|edges| edges.as_slice()).unwrap_or(&[]);
+                let next = candidates.iter().find(|e| !used[**e] && boundary_edges[**e].0 == current)
+                    .or_else(|| candidates.iter().find(|e| !used[**e])).copied();
+                match next {
+                    Some(e) => {
+                        used[e] = true;
+                        let (a, b) = boundary_edges[e];
+                        current = if a == current { b } else { a };
+                    }
+                    None => break,
+                }
+            }
+
+            loops.push(boundary_loop);
+        }
+
+        return loops;
+    }
+
+    fn triangle_area<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(a : T, b : T, c : T) -> U {
+        return T::cross(b - a, c - a).length() / NumCast::from(2.0).unwrap();
+    }
+
+    /// Triangulates a closed polygon in 3D with minimal total area, using dynamic programming (Liepa 2003).
+    /// Since collinear boundary vertices allow triangles without area, the number of degenerate triangles is minimized first.
+    fn triangulate_minimal_area<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(points : &[T]) -> Vec<[usize; 3]> {
+
+        let n = points.len();
+        let mut weights = vec![vec![(0, U::zero()); n]; n];
+        let mut splits = vec![vec![0; n]; n];
+
+        for length in 2..n {
+            for i in 0..n - length {
+
+                let k = i + length;
+                let mut best = (usize::MAX, U::infinity());
+                for m in i + 1..k {
+
+                    let area = triangle_area(points[i], points[m], points[k]);
+                    let longest_edge = (points[k] - points[i]).length().max((points[m] - points[i]).length()).max((points[k] - points[m]).length());
+                    let degenerate = if area <= U::epsilon() * longest_edge * longest_edge { 1 } else { 0 };
+
+                    let weight = (weights[i][m].0 + weights[m][k].0 + degenerate, weights[i][m].1 + weights[m][k].1 + area);
+                    if weight.0 < best.0 || (weight.0 == best.0 && weight.1 < best.1) {
+                        best = weight;
+                        splits[i][k] = m;
+                    }
+                }
+                weights[i][k] = best;
+            }
+        }
+
+        let mut triangles = Vec::<[usize; 3]>::with_capacity(n - 2);
+        let mut stack = Vec::from([(0, n - 1)]);
+        while let Some((i, k)) = stack.pop() {
+            if k - i < 2 {
+                continue;
+            }
+            let m = splits[i][k];
+            triangles.push([i, m, k]);
+            stack.push((i, m));
+            stack.push((m, k));
+        }
+        return triangles;
+    }
+
+    struct Patch<T, U> {
+        points : Vec<T>,
+        scales : Vec<U>,
+        triangles : Vec<[usize; 3]>,
+        boundary_count : usize,
+    }
+
+    impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> Patch<T, U> {
+
+        /// Swaps interior edges of the patch, whose opposite angles sum up to more than 180 degrees (Delaunay criterion).
+        /// Edges already existing in the mesh are never created.
+        fn relax_edges(&mut self, mesh_edges : &HashSet<(usize, usize)>) {
+
+            let pi = U::from(std::f64::consts::PI).unwrap();
+
+            for _ in 0..self.triangles.len() * 4 {
+
+                let mut edges = HashMap::<(usize, usize), Vec<usize>>::new();
+                for (t, triangle) in self.triangles.iter().enumerate() {
+                    for i in 0..3 {
+                        let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                        edges.entry((a.min(b), a.max(b))).or_default().push(t);
+                    }
+                }
+
+                let mut sorted_edges = edges.iter().collect::<Vec<(&(usize, usize), &Vec<usize>)>>();
+                sorted_edges.sort_by_key(|(edge, _)| **edge);
+
+                let mut swapped = false;
+                for ((a, b), triangles) in sorted_edges {
+
+                    if triangles.len() != 2 {
+                        continue;
+                    }
+
+                    let opposite = |t : usize| *self.triangles[t].iter().find(|v| **v != *a && **v != *b).unwrap();
+                    let (t1, t2) = (triangles[0], triangles[1]);
+                    let (c, d) = (opposite(t1), opposite(t2));
+
+                    let key = (c.min(d), c.max(d));
+                    if edges.contains_key(&key) || (c < self.boundary_count && d < self.boundary_count && mesh_edges.contains(&key)) {
+                        continue;
+                    }
+
+                    let p = &self.points;
+                    let angle_c = T::angle(&(p[*a] - p[c]), &(p[*b] - p[c]));
+                    let angle_d = T::angle(&(p[*a] - p[d]), &(p[*b] - p[d]));
+                    if angle_c + angle_d <= pi {
+                        continue;
+                    }
+
+                    //Keep the winding: t1 traverses the edge from x to y, so the quad is x, d, y, c
+                    let t1_triangle = self.triangles[t1];
+                    let x_pos = (0..3).find(|i| t1_triangle[*i] != c && t1_triangle[(*i + 1) % 3] != c).unwrap();
+                    let (x, y) = (t1_triangle[x_pos], t1_triangle[(x_pos + 1) % 3]);
+
+                    self.triangles[t1] = [x, d, c];
+                    self.triangles[t2] = [d, y, c];
+                    swapped = true;
+                    break;
+                }
+
+                if !swapped {
+                    return;
+                }
+            }
+        }
+
+        /// Splits triangles at their centroid, as long as they are larger than the surrounding triangles (Liepa 2003)
+        fn refine(&mut self, mesh_edges : &HashSet<(usize, usize)>) {
+
+            let alpha = U::from(std::f64::consts::SQRT_2).unwrap();
+            let three = U::from(3.0).unwrap();
+
+            for _ in 0..100 {
+
+                let mut changed = false;
+
+                for t in 0..self.triangles.len() {
+
+                    let [i, j, k] = self.triangles[t];
+                    let centroid = (self.points[i] + self.points[j] + self.points[k]) * (U::one() / three);
+                    let scale = (self.scales[i] + self.scales[j] + self.scales[k]) / three;
+
+                    let split = [i, j, k].iter().all(|v| {
+                        let distance = alpha * (centroid - self.points[*v]).length();
+                        return distance > scale && distance > self.scales[*v];
+                    });
+
+                    if split {
+                        let c = self.points.len();
+                        self.points.push(centroid);
+                        self.scales.push(scale);
+
+                        self.triangles[t] = [i, j, c];
+                        self.triangles.push([j, k, c]);
+                        self.triangles.push([k, i, c]);
+                        changed = true;
+                    }
+                }
+
+                if !changed {
+                    return;
+                }
+                self.relax_edges(mesh_edges);
+            }
+        }
+
+        /// Moves all added vertices to the average of their neighbours, until the patch is a discrete membrane
+        fn fair(&mut self) {
+
+            let mut neighbours = vec![HashSet::<usize>::new(); self.points.len()];
+            for triangle in &self.triangles {
+                for i in 0..3 {
+                    neighbours[triangle[i]].insert(triangle[(i + 1) % 3]);
+                    neighbours[triangle[(i + 1) % 3]].insert(triangle[i]);
+                }
+            }
+
+            for _ in 0..500 {
+                for (v, vertex_neighbours) in neighbours.iter().enumerate().skip(self.boundary_count) {
+                    let mut sum = T::zero();
+                    for n in vertex_neighbours {
+                        sum += self.points[*n];
+                    }
+                    self.points[v] = sum * (U::one() / U::from(vertex_neighbours.len()).unwrap());
+                }
+            }
+        }
+    }
+
+    /// Closes a single hole, given by one of the loops returned by [extract_boundary_loops]. The patch is oriented
+    /// consistently with the surrounding faces. The properties of added vertices are interpolated from the boundary
+    /// vertices with inverse distance weights, added triangles copy those of the first face adjacent to an edge of the loop.
+    /// Loops without any edge of the mesh are not holes, so they are left open. Returns the number of added triangles.
+    ///
+    /// # Examples
+    /// ```
+    /// let loops = extract_boundary_loops(&mesh);
+    /// fill_hole(&mut mesh, &loops[0], HoleFilling::REFINE_AND_FAIR);
+    /// ```
+    pub fn fill_hole<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, boundary_loop : &[usize], method : HoleFilling) -> usize
+    {
+        if boundary_loop.len() < 3 {
+            return 0;
+        }
+
+        let vertices = mesh.get_vertices();
+        let indices = mesh.get_indices();
+
+        //The patch traverses the boundary in the opposite direction of the adjacent faces
+        let patch_loop = boundary_loop.iter().rev().copied().collect::<Vec<usize>>();
+        let loop_position = patch_loop.iter().enumerate().map(|(i, v)| (*v, i)).collect::<HashMap<usize, usize>>();
+
+        let mut mesh_edges = HashSet::<(usize, usize)>::new();
+        let mut scale_sums = vec![(U::zero(), 0); patch_loop.len()];
+        let mut adjacent_face = None;
+
+        for (f, triangle) in indices.chunks(3).enumerate() {
+            for i in 0..3 {
+
+                let a = triangle[i].to_usize().unwrap();
+                let b = triangle[(i + 1) % 3].to_usize().unwrap();
+
+                if let (Some(pa), Some(pb)) = (loop_position.get(&a), loop_position.get(&b)) {
+                    mesh_edges.insert((*pa.min(pb), *pa.max(pb)));
+                    if adjacent_face.is_none() && ((pa + 1) % patch_loop.len() == *pb || (pb + 1) % patch_loop.len() == *pa) {
+                        adjacent_face = Some(f);
+                    }
+                }
+
+                //Scales are the average length of the edges around each boundary vertex
+                let length = (vertices[a] - vertices[b]).length();
+                for v in [a, b] {
+                    if let Some(p) = loop_position.get(&v) {
+                        scale_sums[*p] = (scale_sums[*p].0 + length, scale_sums[*p].1 + 1);
+                    }
+                }
+            }
+        }
+
+        let adjacent_face = match adjacent_face {
+            Some(f) => f,
+            None => return 0,
+        };
+
+        let points = patch_loop.iter().map(|v| vertices[*v]).collect::<Vec<T>>();
+        let mut patch = Patch {
+            triangles: triangulate_minimal_area(&points),
+            scales: scale_sums.iter().map(|(sum, count)| if *count > 0 { *sum / U::from(*count).unwrap() } else { U::zero() }).collect(),
+            boundary_count: points.len(),
+            points,
+        };
+
+        if method != HoleFilling::TRIANGULATE {
+            patch.relax_edges(&mesh_edges);
+            patch.refine(&mesh_edges);
+        }
+        if method == HoleFilling::REFINE_AND_FAIR {
+            patch.fair();
+        }
+
+        //Properties of new vertices are interpolated from the boundary with inverse distance weights
+        let new_vertices = &patch.points[patch.boundary_count..];
+        let vertex_sources = new_vertices.iter().map(|p| {
+            let inverse_distances = (0..patch.boundary_count).map(|b| {
+                let distance = (patch.points[b] - *p).length().to_f64().unwrap();
+                return 1.0 / (distance * distance).max(f64::EPSILON);
+            }).collect::<Vec<f64>>();
+            let sum = inverse_distances.iter().sum::<f64>();
+            return inverse_distances.iter().enumerate().map(|(b, w)| (patch_loop[b], w / sum)).collect::<ElementSources>();
+        }).collect::<Vec<ElementSources>>();
+
+        let vertex_count = vertices.len();
+        let to_index = |v : usize| -> IndexType {
+            let idx = if v < patch.boundary_count { patch_loop[v] } else { vertex_count + v - patch.boundary_count };
+            return NumCast::from(idx).unwrap();
+        };
+
+        let new_indices = patch.triangles.iter().flat_map(|t| t.iter().map(|v| to_index(*v))).collect::<Vec<IndexType>>();
+        let face_sources = vec![adjacent_face; patch.triangles.len()];
+
+        mesh.extend(new_vertices, &vertex_sources, &new_indices, &face_sources);
+        return patch.triangles.len();
+    }
+
+    /// Closes all holes of the mesh with at most the given number of boundary vertices. This allows to keep the
+    /// intended boundary of open surfaces, which is usually much longer than the holes. Returns the number of filled holes.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut mesh = ply::read_triangle_mesh::<_, Vec3f, f32, u32>(reader)?.geometry;
+    ///
+    /// let filled = fill_holes(&mut mesh, HoleFilling::REFINE_AND_FAIR, 500);
+    /// ```
+    pub fn fill_holes<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, method : HoleFilling, max_boundary_vertices : usize) -> usize
+    {
+        let loops = extract_boundary_loops(mesh);
+
+        let mut filled = 0;
+        for boundary_loop in loops.iter().filter(|l| l.len() <= max_boundary_vertices) {
+            if fill_hole(mesh, boundary_loop, method) > 0 {
+                filled += 1;
+            }
+        }
+        return filled;
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::mesh::Mesh;
+        use crate::mesh_hole_filling::mesh_holes::HoleFilling;
+        use crate::mesh_hole_filling::mesh_holes::extract_boundary_loops;
+        use crate::mesh_hole_filling::mesh_holes::fill_hole;
+        use crate::mesh_hole_filling::mesh_holes::fill_holes;
+        use crate::mesh_validation::mesh_diagnostics::validate;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+
+        /// An open box without its top, made of a grid with 3x3 quads per side
+        fn create_open_box() -> TriangleVertexMesh<Vec3f, f32, u32> {
+
+            let mut vertices = Vec::<Vec3f>::new();
+            let mut indices = Vec::<u32>::new();
+            let mut vertex_map = std::collections::HashMap::<(i32, i32, i32), u32>::new();
+
+            let mut vertex = |p : (i32, i32, i32), vertices : &mut Vec<Vec3f>| -> u32 {
+                return *vertex_map.entry(p).or_insert_with(|| {
+                    vertices.push(Vec3f::new(p.0 as f32, p.1 as f32, p.2 as f32));
+                    return vertices.len() as u32 - 1;
+                });
+            };
+
+            //Each side is given by its origin and two axes, such that their cross product points outward
+            let sides = [
+                ((0, 0, 0), (0, 1, 0), (1, 0, 0)),
+                ((0, 0, 0), (1, 0, 0), (0, 0, 1)),
+                ((3, 0, 0), (0, 1, 0), (0, 0, 1)),
+                ((3, 3, 0), (-1, 0, 0), (0, 0, 1)),
+                ((0, 3, 0), (0, -1, 0), (0, 0, 1))];
+
+            for (o, u, v) in sides {
+                for i in 0..3 {
+                    for j in 0..3 {
+                        let p = |a : i32, b : i32| (o.0 + a * u.0 + b * v.0, o.1 + a * u.1 + b * v.1, o.2 + a * u.2 + b * v.2);
+                        let a = vertex(p(i, j), &mut vertices);
+                        let b = vertex(p(i + 1, j), &mut vertices);
+                        let c = vertex(p(i + 1, j + 1), &mut vertices);
+                        let d = vertex(p(i, j + 1), &mut vertices);
+                        indices.extend([a, b, c, a, c, d]);
+                    }
+                }
+            }
+
+            return TriangleVertexMesh::from(vertices, indices).unwrap();
+        }
+
+        #[test]
+        fn test_extract_boundary_loops() {
+
+            let mesh = create_open_box();
+            let loops = extract_boundary_loops(&mesh);
+
+            assert!(validate(&mesh).is_consistently_oriented());
+            assert_eq!(loops.len(), 1);
+            assert_eq!(loops[0].len(), 12);
+            assert!(loops[0].iter().all(|v| mesh.get_vertices()[*v].z() == 3.0));
+        }
+
+        #[test]
+        fn test_extract_boundary_loops_inconsistent() {
+
+            //A triangle at the bottom is flipped, so its edges are traversed twice in the same direction
+            let mut mesh = create_open_box();
+            let mut indices = mesh.get_indices().clone();
+            indices.swap(25, 26);
+            mesh.replace_indices(indices);
+            assert!(!validate(&mesh).is_consistently_oriented());
+
+            let loops = extract_boundary_loops(&mesh);
+            assert_eq!(loops.len(), 1);
+            assert_eq!(loops[0].len(), 12);
+
+            //A flipped triangle at the top reverses one edge of the boundary
+            let mut mesh = create_open_box();
+            let top = (0..mesh.get_face_count()).find(|f| mesh.get_face(*f as u32).iter().filter(|v| mesh.get_vertices()[**v as usize].z() == 3.0).count() == 2).unwrap();
+            let mut indices = mesh.get_indices().clone();
+            indices.swap(top * 3 + 1, top * 3 + 2);
+            mesh.replace_indices(indices);
+
+            let loops = extract_boundary_loops(&mesh);
+            assert_eq!(loops.len(), 1);
+            assert_eq!(loops[0].len(), 12);
+        }
+
+        #[test]
+        fn test_fill_holes() {
+
+            for method in [HoleFilling::TRIANGULATE, HoleFilling::REFINE, HoleFilling::REFINE_AND_FAIR] {
+
+                let mut mesh = create_open_box();
+                let vertex_count = mesh.get_vertices().len();
+
+                assert_eq!(fill_holes(&mut mesh, method, 100), 1);
+
+                let report = validate(&mesh);
+                assert!(report.is_closed());
+                assert!(report.is_valid());
+
+                if method == HoleFilling::TRIANGULATE {
+                    assert_eq!(mesh.get_vertices().len(), vertex_count);
+                } else {
+                    assert!(mesh.get_vertices().len() > vertex_count);
+                    assert!(mesh.get_vertices()[vertex_count..].iter().all(|v| (v.z() - 3.0).abs() < 1e-4));
+                }
+            }
+
+            let mut mesh = create_open_box();
+            assert_eq!(fill_holes(&mut mesh, HoleFilling::TRIANGULATE, 11), 0);
+        }
+
+        #[test]
+        fn test_fill_hole_without_edges() {
+
+            //Three corners of the top are not connected by any edge, so they do not bound a hole
+            let mut mesh = create_open_box();
+            let corner = |x : f32, y : f32| mesh.get_vertices().iter().position(|v| v.x() == x && v.y() == y && v.z() == 3.0).unwrap();
+            let corners = [corner(0.0, 0.0), corner(3.0, 0.0), corner(3.0, 3.0)];
+            let face_count = mesh.get_face_count();
+
+            assert_eq!(fill_hole(&mut mesh, &corners, HoleFilling::TRIANGULATE), 0);
+            assert_eq!(mesh.get_face_count(), face_count);
+        }
+    }
+}
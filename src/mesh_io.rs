@@ -0,0 +1,108 @@
+//! # Mesh Import / Export
+//!
+//! Readers and writers for common file formats, which describe meshes. Each format has its own module,
+//! while all of them report problems through the same error type.
+
+use std::fmt;
+use std::io;
+use std::mem;
+
+use num_traits::Float;
+use num_traits::Num;
+
+use crate::common_properties::RgbMap;
+use crate::common_properties::RgbaMap;
+use crate::common_properties::UvMap;
+use crate::property_map::FaceProperties;
+use crate::property_map::PropertyMap;
+use crate::property_map::PropertyType;
+use crate::property_map::VertexProperties;
+
+pub mod obj;
+pub mod ply;
+pub mod stl;
+pub mod gltf;
+pub mod off;
+
+mod json;
+
+#[derive(Debug)]
+pub enum MeshIoError {
+
+    /// The underlying reader or writer failed
+    Io(io::Error),
+    /// The content of a text-based file could not be parsed. Lines are counted starting at one.
+    Parse { line : usize, message : String },
+    /// The data is well-formed, but does not describe a valid mesh (e.g. indices are out of range)
+    InvalidData(String),
+}
+
+impl MeshIoError {
+
+    pub(in crate) fn parse(line : usize, message : impl Into<String>) -> Self {
+        return MeshIoError::Parse { line, message: message.into() };
+    }
+}
+
+impl fmt::Display for MeshIoError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshIoError::Io(err) => write!(f, "[CGRust]: I/O error: {}", err),
+            MeshIoError::Parse { line, message } => write!(f, "[CGRust]: Parse error in line {}: {}", line, message),
+            MeshIoError::InvalidData(message) => write!(f, "[CGRust]: Invalid mesh data: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for MeshIoError {}
+
+impl From<io::Error> for MeshIoError {
+
+    fn from(err : io::Error) -> Self {
+        return MeshIoError::Io(err);
+    }
+}
+
+/// Largest number of values reserved for a list before reading it, as counts come from the file and may be wrong
+pub(in crate) const MAX_LIST_CAPACITY : usize = 256;
+
+/// Formats a floating point number with the shortest representation, which still reads back to the same value
+pub(in crate) fn format_float<U : Float>(value : U) -> String {
+
+    if mem::size_of::<U>() == mem::size_of::<f32>() {
+        return format!("{}", value.to_f32().unwrap());
+    }
+    return format!("{}", value.to_f64().unwrap());
+}
+
+/// Returns the first channel of the [UvMap] of the vertices, if it has the given amount of entries
+pub(in crate) fn get_texture_coordinates<M : VertexProperties, U : Num + PartialOrd<U> + Float + 'static>(mesh : &M, vertex_count : usize) -> Option<&[[U; 2]]> {
+    return mesh.get_vertex_property_ref::<UvMap<U>, Vec<[U; 2]>>(PropertyType::UV)
+        .filter(|uvs| uvs.len() == vertex_count && uvs.get_channel_count() > 0)
+        .map(|uvs| uvs.get_channel(0));
+}
+
+fn get_rgba_colors<U : Num + PartialOrd<U> + Float + 'static>(rgba : Option<&RgbaMap<U>>, rgb : Option<&RgbMap<U>>, count : usize) -> Option<Vec<[U; 4]>> {
+
+    if let Some(colors) = rgba.filter(|c| c.len() == count) {
+        return Some((0..count).map(|i| colors.get(i)).collect());
+    }
+    return rgb.filter(|c| c.len() == count).map(|colors| (0..count).map(|i| colors.get_rgba(i)).collect());
+}
+
+/// Returns the colors of the vertices as RGBA, if there is an [RgbaMap] or [RgbMap] with the given amount of entries
+pub(in crate) fn get_vertex_colors<M : VertexProperties, U : Num + PartialOrd<U> + Float + 'static>(mesh : &M, vertex_count : usize) -> Option<Vec<[U; 4]>> {
+    return get_rgba_colors(
+        mesh.get_vertex_property_ref::<RgbaMap<U>, [U; 4]>(PropertyType::COLOR),
+        mesh.get_vertex_property_ref::<RgbMap<U>, [U; 3]>(PropertyType::COLOR),
+        vertex_count);
+}
+
+/// Returns the colors of the faces as RGBA, like [get_vertex_colors]
+pub(in crate) fn get_face_colors<M : FaceProperties, U : Num + PartialOrd<U> + Float + 'static>(mesh : &M, face_count : usize) -> Option<Vec<[U; 4]>> {
+    return get_rgba_colors(
+        mesh.get_face_property_ref::<RgbaMap<U>, [U; 4]>(PropertyType::COLOR),
+        mesh.get_face_property_ref::<RgbMap<U>, [U; 3]>(PropertyType::COLOR),
+        face_count);
+}
@@ -0,0 +1,734 @@
+//! # GL Transmission Format (glTF 2.0)
+//!
+//! glTF describes a scene as JSON document, while the vertex and index data is stored in binary buffers.
+//! A buffer is either a separate file (.gltf + .bin), embedded into the document as base64 data URI, or
+//! stored together with the document in one binary container (.glb).
+//!
+//! Each primitive of a glTF mesh corresponds to one Triangle-Vertex Mesh. Normals (NORMAL), texture coordinates
+//! (TEXCOORD_n) and colors (COLOR_0) are attached to the mesh as [NormalMap], [UvMap] and [RgbMap] / [RgbaMap].
+//! Node transformations, materials and animations are not read, so the geometry of each primitive is returned in its local space.
+
+use std::hash::Hash;
+use std::io::Read;
+use std::io::Write;
+use std::mem;
+
+use num_traits::Float;
+use num_traits::Num;
+use num_traits::NumCast;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+
+use crate::common_properties::NormalMap;
+use crate::common_properties::RgbMap;
+use crate::common_properties::RgbaMap;
+use crate::common_properties::UvMap;
+use crate::mesh::Mesh;
+use crate::mesh_io::MeshIoError;
+use crate::mesh_io::get_vertex_colors;
+use crate::mesh_io::json::JsonValue;
+use crate::mesh_io::json::escape_json_string;
+use crate::mesh_io::json::parse_json;
+use crate::property_map::PropertyMap;
+use crate::property_map::PropertyType;
+use crate::property_map::VertexProperties;
+use crate::triangle_vertex_mesh::TriangleVertexMesh;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+const COMPONENT_BYTE : u32 = 5120;
+const COMPONENT_UNSIGNED_BYTE : u32 = 5121;
+const COMPONENT_SHORT : u32 = 5122;
+const COMPONENT_UNSIGNED_SHORT : u32 = 5123;
+const COMPONENT_UNSIGNED_INT : u32 = 5125;
+const COMPONENT_FLOAT : u32 = 5126;
+
+const TARGET_ARRAY_BUFFER : u32 = 34962;
+const TARGET_ELEMENT_ARRAY_BUFFER : u32 = 34963;
+
+const MODE_TRIANGLES : usize = 4;
+const MODE_TRIANGLE_STRIP : usize = 5;
+const MODE_TRIANGLE_FAN : usize = 6;
+
+const GLB_MAGIC : u32 = 0x46546C67;
+const GLB_CHUNK_JSON : u32 = 0x4E4F534A;
+const GLB_CHUNK_BIN : u32 = 0x004E4942;
+
+// Base64
+
+const BASE64_ALPHABET : &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data : &[u8]) -> String {
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    return encoded;
+}
+
+fn base64_decode(text : &str) -> Result<Vec<u8>, MeshIoError> {
+
+    let mut decoded = Vec::<u8>::with_capacity(text.len() / 4 * 3);
+    let mut n = 0_u32;
+    let mut bits = 0;
+
+    for c in text.bytes() {
+
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return Err(MeshIoError::InvalidData(String::from("Invalid base64 data"))),
+        };
+
+        n = (n << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((n >> bits) as u8);
+        }
+    }
+    return Ok(decoded);
+}
+
+// Writing
+
+struct GltfBuilder {
+    buffer : Vec<u8>,
+    buffer_views : Vec<String>,
+    accessors : Vec<String>,
+}
+
+impl GltfBuilder {
+
+    fn push_view(&mut self, data : &[u8], target : u32) -> usize {
+
+        //Accessors require their data to be aligned to the size of their components
+        while self.buffer.len() % 4 != 0 {
+            self.buffer.push(0);
+        }
+
+        self.buffer_views.push(format!("{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{}}}", self.buffer.len(), data.len(), target));
+        self.buffer.extend_from_slice(data);
+        return self.buffer_views.len() - 1;
+    }
+
+    fn push_accessor(&mut self, data : &[u8], target : u32, component_type : u32, count : usize, type_name : &str, bounds : Option<([f32; 3], [f32; 3])>) -> usize {
+
+        let view = self.push_view(data, target);
+
+        let mut accessor = format!("{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"{}\"", view, component_type, count, type_name);
+        if let Some((min, max)) = bounds {
+            accessor.push_str(&format!(",\"min\":[{},{},{}],\"max\":[{},{},{}]", min[0], min[1], min[2], max[0], max[1], max[2]));
+        }
+        accessor.push('}');
+
+        self.accessors.push(accessor);
+        return self.accessors.len() - 1;
+    }
+
+    fn push_floats(&mut self, values : &[f32], count : usize, type_name : &str, bounds : Option<([f32; 3], [f32; 3])>) -> usize {
+
+        let data = values.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<u8>>();
+        return self.push_accessor(&data, TARGET_ARRAY_BUFFER, COMPONENT_FLOAT, count, type_name, bounds);
+    }
+}
+
+fn build_document<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    meshes : &[TriangleVertexMesh<T, U, IndexType>], buffer_uri : Option<&str>) -> Result<(String, Vec<u8>), MeshIoError>
+{
+    let mut builder = GltfBuilder { buffer: Vec::<u8>::new(), buffer_views: Vec::<String>::new(), accessors: Vec::<String>::new() };
+    let mut primitive_entries = Vec::<String>::with_capacity(meshes.len());
+
+    let to_f32 = |v : U| v.to_f32().unwrap();
+
+    for mesh in meshes {
+
+        let vertices = mesh.get_vertices();
+        let vertex_count = vertices.len();
+        let indices = mesh.get_indices();
+
+        //16-bit indices are sufficient for index types up to two bytes, everything else uses 32 bits
+        let index_accessor = if mem::size_of::<IndexType>() <= 2 {
+            let data = indices.iter().flat_map(|idx| idx.to_u16().unwrap().to_le_bytes()).collect::<Vec<u8>>();
+            builder.push_accessor(&data, TARGET_ELEMENT_ARRAY_BUFFER, COMPONENT_UNSIGNED_SHORT, indices.len(), "SCALAR", None)
+        } else {
+            let mut data = Vec::<u8>::with_capacity(indices.len() * 4);
+            for idx in indices {
+                let i = idx.to_u32().ok_or_else(|| MeshIoError::InvalidData(String::from("glTF indices are limited to 32 bits")))?;
+                data.extend_from_slice(&i.to_le_bytes());
+            }
+            builder.push_accessor(&data, TARGET_ELEMENT_ARRAY_BUFFER, COMPONENT_UNSIGNED_INT, indices.len(), "SCALAR", None)
+        };
+
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        let mut positions = Vec::<f32>::with_capacity(vertex_count * 3);
+        for v in vertices {
+            let p = [to_f32(v.x()), to_f32(v.y()), to_f32(v.z())];
+            for i in 0..3 {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+            positions.extend_from_slice(&p);
+        }
+        if vertex_count == 0 {
+            min = [0.0; 3];
+            max = [0.0; 3];
+        }
+
+        let mut attributes = Vec::<String>::new();
+        attributes.push(format!("\"POSITION\":{}", builder.push_floats(&positions, vertex_count, "VEC3", Some((min, max)))));
+
+        if let Some(normals) = mesh.get_vertex_property_ref::<NormalMap<T, U>, T>(PropertyType::NORMAL).filter(|n| n.len() == vertex_count) {
+            let mut values = Vec::<f32>::with_capacity(vertex_count * 3);
+            for i in 0..vertex_count {
+                let n = normals.get(i);
+                values.extend_from_slice(&[to_f32(n.x()), to_f32(n.y()), to_f32(n.z())]);
+            }
+            attributes.push(format!("\"NORMAL\":{}", builder.push_floats(&values, vertex_count, "VEC3", None)));
+        }
+
+        if let Some(uvs) = mesh.get_vertex_property_ref::<UvMap<U>, Vec<[U; 2]>>(PropertyType::UV).filter(|t| t.len() == vertex_count) {
+            for channel in 0..uvs.get_channel_count() {
+                let values = uvs.get_channel(channel).iter().flat_map(|uv| [to_f32(uv[0]), to_f32(uv[1])]).collect::<Vec<f32>>();
+                attributes.push(format!("\"TEXCOORD_{}\":{}", channel, builder.push_floats(&values, vertex_count, "VEC2", None)));
+            }
+        }
+
+        if let Some(colors) = get_vertex_colors::<_, U>(mesh, vertex_count) {
+            //RGB colors are written with three components, so they stay RGB when reading them back
+            let components = if mesh.get_vertex_property_ref::<RgbaMap<U>, [U; 4]>(PropertyType::COLOR).is_some() { 4 } else { 3 };
+            let values = colors.iter().flat_map(|c| c[0..components].iter().map(|v| to_f32(*v)).collect::<Vec<f32>>()).collect::<Vec<f32>>();
+            attributes.push(format!("\"COLOR_0\":{}", builder.push_floats(&values, vertex_count, if components == 4 { "VEC4" } else { "VEC3" }, None)));
+        }
+
+        primitive_entries.push(format!("{{\"attributes\":{{{}}},\"indices\":{},\"mode\":{}}}", attributes.join(","), index_accessor, MODE_TRIANGLES));
+    }
+
+    while builder.buffer.len() % 4 != 0 {
+        builder.buffer.push(0);
+    }
+
+    let buffer_entry = match buffer_uri {
+        Some(uri) => format!("{{\"byteLength\":{},\"uri\":{}}}", builder.buffer.len(), escape_json_string(uri)),
+        None => format!("{{\"byteLength\":{}}}", builder.buffer.len()),
+    };
+
+    let document = format!(
+        "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"cg-rust\"}},\"scene\":0,\"scenes\":[{{\"nodes\":[0]}}],\"nodes\":[{{\"mesh\":0}}],\
+        \"meshes\":[{{\"primitives\":[{}]}}],\"buffers\":[{}],\"bufferViews\":[{}],\"accessors\":[{}]}}",
+        primitive_entries.join(","), buffer_entry, builder.buffer_views.join(","), builder.accessors.join(","));
+
+    return Ok((document, builder.buffer));
+}
+
+/// Writes the meshes as primitives of one glTF mesh. The document is written to the first writer and the binary buffer
+/// to the second one, which the document references with the given URI (usually the file name of the .bin file).
+///
+/// # Examples
+/// ```
+/// let mut gltf_file = File::create("model.gltf")?;
+/// let mut bin_file = File::create("model.bin")?;
+/// gltf::write_gltf(&mut gltf_file, &mut bin_file, "model.bin", &[mesh])?;
+/// ```
+pub fn write_gltf<W : Write, B : Write, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, bin_writer : &mut B, bin_uri : &str, meshes : &[TriangleVertexMesh<T, U, IndexType>]) -> Result<(), MeshIoError>
+{
+    let (document, buffer) = build_document(meshes, Some(bin_uri))?;
+    writer.write_all(document.as_bytes())?;
+    bin_writer.write_all(&buffer)?;
+    return Ok(());
+}
+
+/// Writes the meshes as primitives of one glTF mesh into a single .gltf document, with the buffer embedded as base64 data URI
+pub fn write_gltf_embedded<W : Write, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, meshes : &[TriangleVertexMesh<T, U, IndexType>]) -> Result<(), MeshIoError>
+{
+    //The size of the buffer does not depend on the URI, so it is known before encoding
+    let (_, buffer) = build_document(meshes, None)?;
+    let uri = format!("data:application/octet-stream;base64,{}", base64_encode(&buffer));
+    let (document, _) = build_document(meshes, Some(&uri))?;
+
+    writer.write_all(document.as_bytes())?;
+    return Ok(());
+}
+
+/// Writes the meshes as primitives of one glTF mesh into a binary .glb container
+///
+/// # Examples
+/// ```
+/// let mut file = File::create("model.glb")?;
+/// gltf::write_glb(&mut file, &[mesh])?;
+/// ```
+pub fn write_glb<W : Write, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, meshes : &[TriangleVertexMesh<T, U, IndexType>]) -> Result<(), MeshIoError>
+{
+    let (document, buffer) = build_document(meshes, None)?;
+
+    let mut json = document.into_bytes();
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+
+    let total_length = 12 + 8 + json.len() + 8 + buffer.len();
+    let total_length = u32::try_from(total_length).map_err(|_| MeshIoError::InvalidData(String::from("glb files are limited to 4 GB")))?;
+
+    writer.write_all(&GLB_MAGIC.to_le_bytes())?;
+    writer.write_all(&2_u32.to_le_bytes())?;
+    writer.write_all(&total_length.to_le_bytes())?;
+
+    writer.write_all(&(json.len() as u32).to_le_bytes())?;
+    writer.write_all(&GLB_CHUNK_JSON.to_le_bytes())?;
+    writer.write_all(&json)?;
+
+    writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+    writer.write_all(&GLB_CHUNK_BIN.to_le_bytes())?;
+    writer.write_all(&buffer)?;
+
+    return Ok(());
+}
+
+// Reading
+
+fn invalid(message : &str) -> MeshIoError {
+    return MeshIoError::InvalidData(String::from(message));
+}
+
+fn get_index(value : &JsonValue, key : &str) -> Option<usize> {
+    return value.get(key).and_then(|v| v.as_usize());
+}
+
+/// Accessor types and component types, which are allowed for an attribute or the indices of a primitive
+struct AccessorLayout {
+    name : &'static str,
+    components : &'static [usize],
+    component_types : &'static [u32],
+}
+
+const ALL_COMPONENT_TYPES : &[u32] = &[COMPONENT_BYTE, COMPONENT_UNSIGNED_BYTE, COMPONENT_SHORT, COMPONENT_UNSIGNED_SHORT, COMPONENT_UNSIGNED_INT, COMPONENT_FLOAT];
+
+const POSITION_LAYOUT : AccessorLayout = AccessorLayout { name: "POSITION", components: &[3], component_types: ALL_COMPONENT_TYPES };
+const NORMAL_LAYOUT : AccessorLayout = AccessorLayout { name: "NORMAL", components: &[3], component_types: ALL_COMPONENT_TYPES };
+const TEXCOORD_LAYOUT : AccessorLayout = AccessorLayout { name: "TEXCOORD", components: &[2], component_types: ALL_COMPONENT_TYPES };
+const COLOR_LAYOUT : AccessorLayout = AccessorLayout { name: "COLOR_0", components: &[3, 4], component_types: ALL_COMPONENT_TYPES };
+const INDEX_LAYOUT : AccessorLayout = AccessorLayout {
+    name: "indices",
+    components: &[1],
+    component_types: &[COMPONENT_UNSIGNED_BYTE, COMPONENT_UNSIGNED_SHORT, COMPONENT_UNSIGNED_INT],
+};
+
+/// Reads the data of an accessor as a flat list of values, together with the amount of components per element.
+/// The accessor has to match the layout and, if given, contain the expected number of elements.
+fn read_accessor(document : &JsonValue, buffers : &[Vec<u8>], accessor_idx : usize, layout : &AccessorLayout, expected_count : Option<usize>)
+    -> Result<(Vec<f64>, usize), MeshIoError>
+{
+    let accessor = document.get("accessors").and_then(|a| a.as_array()).and_then(|a| a.get(accessor_idx))
+        .ok_or_else(|| invalid("Accessor out of range"))?;
+
+    if accessor.get("sparse").is_some() {
+        return Err(invalid("Sparse accessors are not supported"));
+    }
+
+    let count = get_index(accessor, "count").ok_or_else(|| invalid("Accessor without count"))?;
+    let component_type = get_index(accessor, "componentType").ok_or_else(|| invalid("Accessor without component type"))?;
+    let normalized = accessor.get("normalized").and_then(|n| n.as_bool()).unwrap_or(false);
+
+    let components = match accessor.get("type").and_then(|t| t.as_str()) {
+        Some("SCALAR") => 1,
+        Some("VEC2") => 2,
+        Some("VEC3") => 3,
+        Some("VEC4") => 4,
+        _ => return Err(invalid("Unsupported accessor type")),
+    };
+
+    let component_type = u32::try_from(component_type).ok().filter(|t| layout.component_types.contains(t))
+        .ok_or_else(|| MeshIoError::InvalidData(format!("Unsupported component type for {}", layout.name)))?;
+    if !layout.components.contains(&components) {
+        return Err(MeshIoError::InvalidData(format!("Unsupported accessor type for {}", layout.name)));
+    }
+    if expected_count.is_some_and(|expected| expected != count) {
+        return Err(MeshIoError::InvalidData(format!("The {} accessor does not match the number of vertices", layout.name)));
+    }
+
+    let component_size = match component_type {
+        COMPONENT_BYTE | COMPONENT_UNSIGNED_BYTE => 1,
+        COMPONENT_SHORT | COMPONENT_UNSIGNED_SHORT => 2,
+        _ => 4,
+    };
+
+    let value_count = count.checked_mul(components).ok_or_else(|| invalid("Accessor count too large"))?;
+
+    //Accessors without buffer view are initialized with zeros. Their size is not limited by a buffer, so the allocation may fail.
+    let view_idx = match get_index(accessor, "bufferView") {
+        Some(idx) => idx,
+        None => {
+            let mut values = Vec::<f64>::new();
+            values.try_reserve_exact(value_count).map_err(|_| invalid("Accessor count too large"))?;
+            values.resize(value_count, 0.0);
+            return Ok((values, components));
+        }
+    };
+
+    let view = document.get("bufferViews").and_then(|v| v.as_array()).and_then(|v| v.get(view_idx))
+        .ok_or_else(|| invalid("Buffer view out of range"))?;
+    let buffer = get_index(view, "buffer").and_then(|b| buffers.get(b)).ok_or_else(|| invalid("Buffer out of range"))?;
+
+    let view_offset = get_index(view, "byteOffset").unwrap_or(0);
+    let view_length = get_index(view, "byteLength").ok_or_else(|| invalid("Buffer view without length"))?;
+    let element_size = component_size * components;
+    let stride = get_index(view, "byteStride").unwrap_or(element_size);
+    if stride < element_size {
+        return Err(invalid("Buffer view stride smaller than its elements"));
+    }
+
+    //All offsets come from the document, so they are checked before any access to the buffer
+    let view_end = view_offset.checked_add(view_length).filter(|end| *end <= buffer.len());
+    let offset = view_offset.checked_add(get_index(accessor, "byteOffset").unwrap_or(0));
+    let end = match count {
+        0 => offset,
+        _ => offset.and_then(|o| stride.checked_mul(count - 1).and_then(|s| s.checked_add(o))).and_then(|o| o.checked_add(element_size)),
+    };
+    let (Some(offset), Some(end), Some(view_end)) = (offset, end, view_end) else {
+        return Err(invalid("Accessor exceeds its buffer"));
+    };
+    if end > view_end {
+        return Err(invalid("Accessor exceeds its buffer"));
+    }
+
+    let mut values = Vec::<f64>::with_capacity(value_count);
+    for i in 0..count {
+        for c in 0..components {
+
+            let o = offset + i * stride + c * component_size;
+            let bytes = &buffer[o..o + component_size];
+
+            let value = match component_type {
+                COMPONENT_BYTE => { let v = bytes[0] as i8 as f64; if normalized { (v / 127.0).max(-1.0) } else { v } }
+                COMPONENT_UNSIGNED_BYTE => { let v = bytes[0] as f64; if normalized { v / 255.0 } else { v } }
+                COMPONENT_SHORT => { let v = i16::from_le_bytes([bytes[0], bytes[1]]) as f64; if normalized { (v / 32767.0).max(-1.0) } else { v } }
+                COMPONENT_UNSIGNED_SHORT => { let v = u16::from_le_bytes([bytes[0], bytes[1]]) as f64; if normalized { v / 65535.0 } else { v } }
+                COMPONENT_UNSIGNED_INT => u32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+                _ => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            };
+            values.push(value);
+        }
+    }
+
+    return Ok((values, components));
+}
+
+fn read_primitives<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    document : &JsonValue, buffers : &[Vec<u8>]) -> Result<Vec<TriangleVertexMesh<T, U, IndexType>>, MeshIoError>
+{
+    let to_u = |v : f64| -> U { NumCast::from(v).unwrap() };
+    let mut primitives = Vec::<TriangleVertexMesh<T, U, IndexType>>::new();
+
+    let meshes = document.get("meshes").and_then(|m| m.as_array()).map(|m| m.as_slice()).unwrap_or(&[]);
+    for mesh in meshes {
+
+        let mesh_primitives = mesh.get("primitives").and_then(|p| p.as_array()).ok_or_else(|| invalid("Mesh without primitives"))?;
+        for primitive in mesh_primitives {
+
+            let attributes = primitive.get("attributes").ok_or_else(|| invalid("Primitive without attributes"))?;
+            let attribute = |name : &str| get_index(attributes, name);
+
+            let position_accessor = attribute("POSITION").ok_or_else(|| invalid("Primitive without positions"))?;
+            let (positions, _) = read_accessor(document, buffers, position_accessor, &POSITION_LAYOUT, None)?;
+            let vertices = positions.chunks(3).map(|p| T::new(to_u(p[0]), to_u(p[1]), to_u(p[2]))).collect::<Vec<T>>();
+            let vertex_count = vertices.len();
+
+            let corner_indices = match get_index(primitive, "indices") {
+                Some(accessor) => read_accessor(document, buffers, accessor, &INDEX_LAYOUT, None)?.0.into_iter().map(|i| i as usize).collect::<Vec<usize>>(),
+                None => (0..vertex_count).collect::<Vec<usize>>(),
+            };
+
+            let mut triangles = Vec::<usize>::with_capacity(corner_indices.len());
+            match get_index(primitive, "mode").unwrap_or(MODE_TRIANGLES) {
+                MODE_TRIANGLES => triangles.extend(corner_indices.iter().take(corner_indices.len() / 3 * 3)),
+                MODE_TRIANGLE_STRIP => {
+                    for i in 0..corner_indices.len().saturating_sub(2) {
+                        let c = &corner_indices[i..i + 3];
+                        if i % 2 == 0 { triangles.extend([c[0], c[1], c[2]]); } else { triangles.extend([c[1], c[0], c[2]]); }
+                    }
+                }
+                MODE_TRIANGLE_FAN => {
+                    for i in 1..corner_indices.len().saturating_sub(1) {
+                        triangles.extend([corner_indices[0], corner_indices[i], corner_indices[i + 1]]);
+                    }
+                }
+                _ => return Err(invalid("Only triangle primitives are supported")),
+            }
+
+            let mut indices = Vec::<IndexType>::with_capacity(triangles.len());
+            for idx in triangles {
+                if idx >= vertex_count {
+                    return Err(MeshIoError::InvalidData(format!("Vertex index {} out of range", idx)));
+                }
+                indices.push(NumCast::from(idx)
+                    .ok_or_else(|| MeshIoError::InvalidData(format!("Vertex index {} does not fit into the index type", idx)))?);
+            }
+
+            let mut result = TriangleVertexMesh::from(vertices, indices).unwrap();
+
+            if let Some(accessor) = attribute("NORMAL") {
+                let (normals, _) = read_accessor(document, buffers, accessor, &NORMAL_LAYOUT, Some(vertex_count))?;
+                let mut normal_map = NormalMap::<T, U>::with_capacity(vertex_count);
+                for n in normals.chunks(3) {
+                    normal_map.push(T::new(to_u(n[0]), to_u(n[1]), to_u(n[2])));
+                }
+                result.add_vertex_property(normal_map);
+            }
+
+            let mut uv_map : Option<UvMap<U>> = None;
+            let mut channel = 0;
+            while let Some(accessor) = attribute(&format!("TEXCOORD_{}", channel)) {
+                let (uvs, _) = read_accessor(document, buffers, accessor, &TEXCOORD_LAYOUT, Some(vertex_count))?;
+                let uvs = uvs.chunks(2).map(|uv| [to_u(uv[0]), to_u(uv[1])]).collect::<Vec<[U; 2]>>();
+                match uv_map.as_mut() {
+                    Some(uv_map) => { uv_map.add_channel(uvs); }
+                    None => uv_map = Some(UvMap::from(uvs)),
+                }
+                channel += 1;
+            }
+            if let Some(uv_map) = uv_map {
+                result.add_vertex_property(uv_map);
+            }
+
+            if let Some(accessor) = attribute("COLOR_0") {
+                let (colors, components) = read_accessor(document, buffers, accessor, &COLOR_LAYOUT, Some(vertex_count))?;
+                if components == 4 {
+                    result.add_vertex_property(RgbaMap::from(colors.chunks(4).map(|c| [to_u(c[0]), to_u(c[1]), to_u(c[2]), to_u(c[3])]).collect()));
+                } else {
+                    result.add_vertex_property(RgbMap::from(colors.chunks(components).map(|c| [to_u(c[0]), to_u(c[1]), to_u(c[2])]).collect()));
+                }
+            }
+
+            primitives.push(result);
+        }
+    }
+
+    return Ok(primitives);
+}
+
+fn load_buffers<F : FnMut(&str) -> Result<Vec<u8>, MeshIoError>>(document : &JsonValue, mut binary_chunk : Option<Vec<u8>>, mut load_buffer : F)
+    -> Result<Vec<Vec<u8>>, MeshIoError>
+{
+    let mut buffers = Vec::<Vec<u8>>::new();
+
+    let entries = document.get("buffers").and_then(|b| b.as_array()).map(|b| b.as_slice()).unwrap_or(&[]);
+    for (i, entry) in entries.iter().enumerate() {
+
+        let data = match entry.get("uri").and_then(|u| u.as_str()) {
+            Some(uri) if uri.starts_with("data:") => {
+                let (_, encoded) = uri.split_once(";base64,").ok_or_else(|| invalid("Only base64 data URIs are supported"))?;
+                base64_decode(encoded)?
+            }
+            Some(uri) => load_buffer(uri)?,
+            None if i == 0 && binary_chunk.is_some() => binary_chunk.take().unwrap(),
+            None => return Err(invalid("Buffer without URI")),
+        };
+        buffers.push(data);
+    }
+
+    return Ok(buffers);
+}
+
+/// Reads all triangle primitives of all meshes of a .gltf document. Data URIs are decoded directly, while other buffers
+/// are requested from the loader with their URI (usually a file name relative to the document).
+///
+/// # Examples
+/// ```
+/// let file = File::open("model.gltf")?;
+/// let meshes = gltf::read_gltf::<_, _, Vec3f, f32, u32>(file, |uri| Ok(std::fs::read(uri)?))?;
+/// ```
+pub fn read_gltf<R : Read, F : FnMut(&str) -> Result<Vec<u8>, MeshIoError>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    mut reader : R, load_buffer : F) -> Result<Vec<TriangleVertexMesh<T, U, IndexType>>, MeshIoError>
+{
+    let mut content = Vec::<u8>::new();
+    reader.read_to_end(&mut content)?;
+
+    let document = parse_json(&content)?;
+    let buffers = load_buffers(&document, None, load_buffer)?;
+
+    return read_primitives(&document, &buffers);
+}
+
+/// Reads all triangle primitives of all meshes of a binary .glb container
+///
+/// # Examples
+/// ```
+/// let file = File::open("model.glb")?;
+/// let meshes = gltf::read_glb::<_, Vec3f, f32, u32>(file)?;
+/// ```
+pub fn read_glb<R : Read, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    mut reader : R) -> Result<Vec<TriangleVertexMesh<T, U, IndexType>>, MeshIoError>
+{
+    let mut content = Vec::<u8>::new();
+    reader.read_to_end(&mut content)?;
+
+    let read_u32 = |offset : usize| -> Option<u32> {
+        return content.get(offset..offset + 4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+    };
+
+    if read_u32(0) != Some(GLB_MAGIC) {
+        return Err(invalid("Not a glb file"));
+    }
+    if read_u32(4) != Some(2) {
+        return Err(invalid("Only glTF version 2 is supported"));
+    }
+
+    let mut json : Option<&[u8]> = None;
+    let mut binary_chunk : Option<Vec<u8>> = None;
+
+    let mut offset = 12;
+    while offset + 8 <= content.len() {
+
+        let length = read_u32(offset).unwrap() as usize;
+        let chunk_type = read_u32(offset + 4).unwrap();
+        let data = content.get(offset + 8..offset + 8 + length).ok_or_else(|| invalid("Chunk exceeds the file"))?;
+
+        if chunk_type == GLB_CHUNK_JSON && json.is_none() {
+            json = Some(data);
+        } else if chunk_type == GLB_CHUNK_BIN && binary_chunk.is_none() {
+            binary_chunk = Some(data.to_vec());
+        }
+        offset += 8 + length;
+    }
+
+    let document = parse_json(json.ok_or_else(|| invalid("Missing JSON chunk"))?)?;
+    let buffers = load_buffers(&document, binary_chunk, |_| Err(invalid("External buffers are not supported in glb files")))?;
+
+    return read_primitives(&document, &buffers);
+}
+
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::common_properties::NormalMap;
+    use crate::common_properties::RgbaMap;
+    use crate::common_properties::UvMap;
+    use crate::mesh::Mesh;
+    use crate::mesh_io::MeshIoError;
+    use crate::mesh_io::gltf;
+    use crate::mesh_io::gltf::base64_decode;
+    use crate::mesh_io::gltf::base64_encode;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::Vec3f;
+    use crate::vector::Vec3;
+
+    fn create_primitive<IndexType : num_traits::PrimInt + std::hash::Hash + num_traits::Unsigned>() -> TriangleVertexMesh<Vec3f, f32, IndexType> {
+
+        let vertices = Vec::from([Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(1.0, 1.0, 0.0), Vec3f::new(0.0, 1.0, 0.0)]);
+        let indices = [0, 1, 2, 0, 2, 3].iter().map(|i| IndexType::from(*i).unwrap()).collect();
+
+        let mut mesh = TriangleVertexMesh::from(vertices, indices).unwrap();
+        mesh.add_vertex_property(NormalMap::<Vec3f, f32>::with_size(4, Vec3f::new(0.0, 0.0, 1.0)));
+
+        let mut uvs = UvMap::<f32>::from(Vec::from([[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]));
+        uvs.add_channel(vec![[0.5, 0.5]; 4]);
+        mesh.add_vertex_property(uvs);
+        mesh.add_vertex_property(RgbaMap::<f32>::with_size(4, [1.0, 0.5, 0.0, 1.0]));
+        return mesh;
+    }
+
+    #[test]
+    fn test_base64() {
+
+        for data in [&b""[..], b"a", b"ab", b"abc", b"abcd"] {
+            assert_eq!(base64_decode(&base64_encode(data)).unwrap(), data);
+        }
+        assert_eq!(base64_encode(b"ab"), "YWI=");
+    }
+
+    #[test]
+    fn test_glb_roundtrip() {
+
+        let primitives = [create_primitive::<u16>(), create_primitive::<u16>()];
+
+        let mut buffer = Vec::<u8>::new();
+        gltf::write_glb(&mut buffer, &primitives).unwrap();
+        assert_eq!(buffer.len() % 4, 0);
+
+        let reread = gltf::read_glb::<_, Vec3f, f32, u16>(buffer.as_slice()).unwrap();
+
+        assert_eq!(reread.len(), 2);
+        assert_eq!(reread[1].get_face(1), Vec::from([0, 2, 3]));
+
+        let uvs = reread[1].get_vertex_property_ref::<UvMap<f32>, Vec<[f32; 2]>>(PropertyType::UV).unwrap();
+        assert_eq!(uvs.get_channel_count(), 2);
+        assert_eq!(uvs.get_uv(2, 0), [1.0, 1.0]);
+        assert_eq!(uvs.get_uv(2, 1), [0.5, 0.5]);
+        assert_eq!(reread[1].get_vertex_property_ref::<RgbaMap<f32>, [f32; 4]>(PropertyType::COLOR).unwrap().get(0), [1.0, 0.5, 0.0, 1.0]);
+
+        let normals = reread[0].get_vertex_property_ref::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+        assert_eq!(normals.get(3).z(), 1.0);
+
+        //Indices of a two byte index type are stored as unsigned short
+        let document = String::from_utf8_lossy(&buffer);
+        assert!(document.contains("\"componentType\":5123"));
+    }
+
+    #[test]
+    fn test_invalid_accessors() {
+
+        let mut document = Vec::<u8>::new();
+        gltf::write_gltf_embedded(&mut document, &[create_primitive::<u32>()]).unwrap();
+        let document = String::from_utf8(document).unwrap();
+        let read = |content : String| gltf::read_gltf::<_, _, Vec3f, f32, u32>(content.as_bytes(), |_| panic!("No external buffer expected"));
+
+        //Two component colors, which are valid in a buffer but not for COLOR_0
+        assert!(matches!(read(document.replace("\"type\":\"VEC4\"", "\"type\":\"VEC2\"")), Err(MeshIoError::InvalidData(_))));
+
+        //Normals with fewer elements than positions
+        let normals = document.replacen("\"count\":4,\"type\":\"VEC3\"}", "\"count\":3,\"type\":\"VEC3\"}", 1);
+        assert!(matches!(read(normals), Err(MeshIoError::InvalidData(_))));
+
+        //Offsets and counts, which overflow when computing the accessed range
+        let overflow = document.replacen("\"count\":4", &format!("\"count\":{}", usize::MAX / 2), 1);
+        assert!(matches!(read(overflow), Err(MeshIoError::InvalidData(_))));
+        let overflow = document.replacen("\"byteOffset\":0", &format!("\"byteOffset\":{}", usize::MAX - 4), 1);
+        assert!(matches!(read(overflow), Err(MeshIoError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_gltf_roundtrip() {
+
+        let primitives = [create_primitive::<u32>()];
+
+        let mut document = Vec::<u8>::new();
+        let mut bin = Vec::<u8>::new();
+        gltf::write_gltf(&mut document, &mut bin, "model.bin", &primitives).unwrap();
+        assert!(String::from_utf8_lossy(&document).contains("\"componentType\":5125"));
+
+        let reread = gltf::read_gltf::<_, _, Vec3f, f32, u32>(document.as_slice(), |uri| {
+            assert_eq!(uri, "model.bin");
+            return Ok(bin.clone());
+        }).unwrap();
+        assert_eq!(reread[0].get_face_count(), 2);
+
+        let mut embedded = Vec::<u8>::new();
+        gltf::write_gltf_embedded(&mut embedded, &primitives).unwrap();
+
+        let reread = gltf::read_gltf::<_, _, Vec3f, f32, u32>(embedded.as_slice(), |_| panic!("No external buffer expected")).unwrap();
+        assert_eq!(reread[0].get_vertices()[2].y(), 1.0);
+    }
+}
@@ -0,0 +1,339 @@
+//! # JSON
+//!
+//! Minimal JSON parser for the formats, which store their description as JSON (e.g. glTF).
+//! Only parsing is supported, since writers produce their small documents directly.
+
+use crate::mesh_io::MeshIoError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(in crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+
+    pub fn get(&self, key : &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None
+        }
+    }
+
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            JsonValue::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(a) => Some(a),
+            _ => None
+        }
+    }
+}
+
+/// Deepest nesting of arrays and objects, which is accepted before the parser gives up instead of overflowing the stack
+const MAX_DEPTH : usize = 128;
+
+struct JsonParser<'a> {
+    content : &'a [u8],
+    pos : usize,
+    line : usize,
+    depth : usize,
+}
+
+impl<'a> JsonParser<'a> {
+
+    fn error(&self, message : &str) -> MeshIoError {
+        return MeshIoError::parse(self.line, message);
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.content.len() {
+            match self.content[self.pos] {
+                b'\n' => { self.line += 1; self.pos += 1; }
+                b' ' | b'\t' | b'\r' => self.pos += 1,
+                _ => break
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        return self.content.get(self.pos).copied();
+    }
+
+    fn expect(&mut self, c : u8) -> Result<(), MeshIoError> {
+
+        self.skip_whitespace();
+        if self.peek() != Some(c) {
+            return Err(self.error(&format!("Expected '{}'", c as char)));
+        }
+        self.pos += 1;
+        return Ok(());
+    }
+
+    fn parse_literal(&mut self, literal : &str, value : JsonValue) -> Result<JsonValue, MeshIoError> {
+
+        if self.content[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            return Ok(value);
+        }
+        return Err(self.error("Invalid literal"));
+    }
+
+    /// Parses an array or object one level deeper than the current value
+    fn parse_nested(&mut self, parse : fn(&mut Self) -> Result<JsonValue, MeshIoError>) -> Result<JsonValue, MeshIoError> {
+
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("Document nested too deeply"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        return value;
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, MeshIoError> {
+
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.parse_nested(Self::parse_object),
+            Some(b'[') => self.parse_nested(Self::parse_array),
+            Some(b'"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some(b't') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some(b'n') => self.parse_literal("null", JsonValue::Null),
+            Some(c) if c == b'-' || c.is_ascii_digit() => self.parse_number(),
+            Some(_) => Err(self.error("Unexpected character")),
+            None => Err(self.error("Unexpected end of document")),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, MeshIoError> {
+
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() || c == b'-' || c == b'+' || c == b'.' || c == b'e' || c == b'E' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        let text = std::str::from_utf8(&self.content[start..self.pos]).unwrap();
+        return text.parse::<f64>().map(JsonValue::Number).map_err(|_| self.error(&format!("Invalid number '{}'", text)));
+    }
+
+    fn parse_hex(&mut self) -> Result<u32, MeshIoError> {
+
+        if self.pos + 4 > self.content.len() {
+            return Err(self.error("Invalid unicode escape"));
+        }
+        let text = std::str::from_utf8(&self.content[self.pos..self.pos + 4]).map_err(|_| self.error("Invalid unicode escape"))?;
+        let value = u32::from_str_radix(text, 16).map_err(|_| self.error("Invalid unicode escape"))?;
+        self.pos += 4;
+        return Ok(value);
+    }
+
+    fn parse_string(&mut self) -> Result<String, MeshIoError> {
+
+        self.expect(b'"')?;
+        let mut bytes = Vec::<u8>::new();
+
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("Unterminated string"))?;
+            self.pos += 1;
+
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("Unterminated string"))?;
+                    self.pos += 1;
+
+                    let decoded = match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.parse_hex()?;
+                            //Characters outside the basic plane are encoded as surrogate pair
+                            if (0xD800..0xDC00).contains(&code) && self.content[self.pos..].starts_with(b"\\u") {
+                                self.pos += 2;
+                                let low = self.parse_hex()?;
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+                            }
+                            char::from_u32(code).unwrap_or('\u{FFFD}')
+                        }
+                        _ => return Err(self.error("Invalid escape sequence")),
+                    };
+
+                    let mut buffer = [0_u8; 4];
+                    bytes.extend_from_slice(decoded.encode_utf8(&mut buffer).as_bytes());
+                }
+                b'\n' => return Err(self.error("Unterminated string")),
+                _ => bytes.push(c),
+            }
+        }
+
+        return String::from_utf8(bytes).map_err(|_| self.error("Invalid UTF-8 in string"));
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, MeshIoError> {
+
+        self.expect(b'[')?;
+        let mut values = Vec::<JsonValue>::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(values));
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => { self.pos += 1; break; }
+                _ => return Err(self.error("Expected ',' or ']'")),
+            }
+        }
+
+        return Ok(JsonValue::Array(values));
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, MeshIoError> {
+
+        self.expect(b'{')?;
+        let mut members = Vec::<(String, JsonValue)>::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(b':')?;
+            let value = self.parse_value()?;
+            members.push((key, value));
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => { self.pos += 1; break; }
+                _ => return Err(self.error("Expected ',' or '}'")),
+            }
+        }
+
+        return Ok(JsonValue::Object(members));
+    }
+}
+
+/// Parses a complete JSON document. Errors report the line in which they occurred.
+pub(in crate) fn parse_json(content : &[u8]) -> Result<JsonValue, MeshIoError> {
+
+    let mut parser = JsonParser { content, pos: 0, line: 1, depth: 0 };
+    let value = parser.parse_value()?;
+
+    parser.skip_whitespace();
+    if parser.pos != content.len() {
+        return Err(parser.error("Unexpected content after document"));
+    }
+
+    return Ok(value);
+}
+
+/// Encloses the text in quotes and escapes all characters, which are not allowed in JSON strings
+pub(in crate) fn escape_json_string(text : &str) -> String {
+
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    return escaped;
+}
+
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::mesh_io::MeshIoError;
+    use crate::mesh_io::json::JsonValue;
+    use crate::mesh_io::json::escape_json_string;
+    use crate::mesh_io::json::parse_json;
+
+    #[test]
+    fn test_parse_document() {
+
+        let document = parse_json(b"{ \"a\": [1, -2.5e1, true, null],\n \"b\": { \"c\": \"x\\u00e4\\n\" } }").unwrap();
+
+        let a = document.get("a").unwrap().as_array().unwrap();
+        assert_eq!(a[1], JsonValue::Number(-25.0));
+        assert_eq!(a[2], JsonValue::Bool(true));
+        assert_eq!(document.get("b").unwrap().get("c").unwrap().as_str(), Some("x\u{e4}\n"));
+    }
+
+    #[test]
+    fn test_error_line() {
+
+        match parse_json(b"{\n \"a\": 1,\n \"b\" 2 }") {
+            Err(MeshIoError::Parse { line, .. }) => assert_eq!(line, 3),
+            _ => panic!("Expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_nesting_depth() {
+
+        let nested = |depth : usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse_json(nested(100).as_bytes()).is_ok());
+        assert!(matches!(parse_json(nested(100000).as_bytes()), Err(MeshIoError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_escape_roundtrip() {
+
+        let text = "quote \" and backslash \\ and\ttab";
+        let parsed = parse_json(escape_json_string(text).as_bytes()).unwrap();
+        assert_eq!(parsed.as_str(), Some(text));
+    }
+}
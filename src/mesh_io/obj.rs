@@ -0,0 +1,611 @@
+//! # Wavefront OBJ
+//!
+//! Text-based format, which lists positions (v), texture coordinates (vt) and normals (vn) separately.
+//! Faces (f) reference each of these lists with their own index, which starts at one. Negative indices
+//! are relative to the end of the respective list at the point where the face is defined.
+//!
+//! Since meshes in this crate share one index for all attributes of a vertex, a vertex is duplicated
+//! whenever it is referenced with different texture coordinates or normals.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::BufRead;
+use std::io::Write;
+
+use num_traits::Float;
+use num_traits::Num;
+use num_traits::NumCast;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+
+use crate::common_properties::NormalMap;
+use crate::common_properties::UvMap;
+use crate::face_vertex_mesh::FaceVertexMesh;
+use crate::mesh::Mesh;
+use crate::mesh_io::MeshIoError;
+use crate::mesh_io::format_float;
+use crate::mesh_io::get_texture_coordinates;
+use crate::mesh_triangulation::face_triangulation::triangulate_face;
+use crate::property_map::PropertyMap;
+use crate::property_map::PropertyType;
+use crate::property_map::VertexProperties;
+use crate::triangle_vertex_mesh::TriangleVertexMesh;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+/// A consecutive range of faces, which belongs to the same object (o) and group (g) of the file
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjGroup {
+    pub object_name : Option<String>,
+    pub group_name : Option<String>,
+    pub first_face : usize,
+    pub face_count : usize,
+}
+
+/// The result of reading an OBJ file. Normals and texture coordinates are attached to the mesh as [NormalMap] and
+/// [UvMap] vertex properties, while groups are stored alongside the mesh.
+pub struct ObjMesh<MeshType> {
+    pub mesh : MeshType,
+    pub groups : Vec<ObjGroup>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct ObjCorner {
+    position : usize,
+    texture_coordinate : Option<usize>,
+    normal : Option<usize>,
+}
+
+struct ObjFace {
+    corners : Vec<ObjCorner>,
+    object_name : Option<String>,
+    group_name : Option<String>,
+}
+
+struct ObjData<T, U> {
+    positions : Vec<T>,
+    texture_coordinates : Vec<[U; 2]>,
+    normals : Vec<T>,
+    faces : Vec<ObjFace>,
+}
+
+/// Data of the file, after each distinct combination of position, texture coordinate and normal
+/// has been assigned a vertex of its own
+struct ObjVertexData<T, U> {
+    vertices : Vec<T>,
+    texture_coordinates : Option<Vec<[U; 2]>>,
+    normals : Option<Vec<T>>,
+    faces : Vec<Vec<usize>>,
+}
+
+fn parse_float<U : Num + PartialOrd<U> + Float>(token : Option<&str>, line : usize) -> Result<U, MeshIoError> {
+
+    let t = token.ok_or_else(|| MeshIoError::parse(line, "Missing coordinate"))?;
+    let value = t.parse::<f64>().map_err(|_| MeshIoError::parse(line, format!("Invalid number '{}'", t)))?;
+
+    return <U as NumCast>::from(value).ok_or_else(|| MeshIoError::parse(line, format!("Number '{}' out of range", t)));
+}
+
+fn parse_index(token : &str, count : usize, line : usize) -> Result<usize, MeshIoError> {
+
+    let value = token.parse::<i64>().map_err(|_| MeshIoError::parse(line, format!("Invalid index '{}'", token)))?;
+
+    let idx = if value > 0 {
+        value - 1
+    } else if value < 0 {
+        count as i64 + value
+    } else {
+        return Err(MeshIoError::parse(line, "Index 0 is not allowed, indices start at 1"));
+    };
+
+    if idx < 0 || idx >= count as i64 {
+        return Err(MeshIoError::parse(line, format!("Index '{}' out of range", token)));
+    }
+
+    return Ok(idx as usize);
+}
+
+fn parse<R : BufRead, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(reader : R) -> Result<ObjData<T, U>, MeshIoError> {
+
+    let mut data = ObjData::<T, U> {
+        positions: Vec::<T>::new(),
+        texture_coordinates: Vec::<[U; 2]>::new(),
+        normals: Vec::<T>::new(),
+        faces: Vec::<ObjFace>::new(),
+    };
+
+    let mut object_name : Option<String> = None;
+    let mut group_name : Option<String> = None;
+
+    let mut content = String::new();
+    let mut content_line = 0;
+
+    for (line_idx, line) in reader.lines().enumerate() {
+
+        let line = line?;
+
+        //Lines ending with a backslash are continued in the next line
+        if content.is_empty() {
+            content_line = line_idx + 1;
+        }
+        let trimmed = line.trim_end();
+        if let Some(continued) = trimmed.strip_suffix('\\') {
+            content.push_str(continued);
+            content.push(' ');
+            continue;
+        }
+        content.push_str(trimmed);
+
+        let current = std::mem::take(&mut content);
+        let line_nr = content_line;
+
+        let without_comment = match current.find('#') {
+            Some(pos) => &current[..pos],
+            None => &current[..],
+        };
+
+        let mut tokens = without_comment.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(k) => k,
+            None => continue,
+        };
+
+        match keyword {
+            "v" => {
+                let x = parse_float::<U>(tokens.next(), line_nr)?;
+                let y = parse_float::<U>(tokens.next(), line_nr)?;
+                let z = parse_float::<U>(tokens.next(), line_nr)?;
+                data.positions.push(T::new(x, y, z));
+            }
+            "vt" => {
+                let u = parse_float::<U>(tokens.next(), line_nr)?;
+                let v = match tokens.next() {
+                    Some(t) => parse_float::<U>(Some(t), line_nr)?,
+                    None => U::zero(),
+                };
+                data.texture_coordinates.push([u, v]);
+            }
+            "vn" => {
+                let x = parse_float::<U>(tokens.next(), line_nr)?;
+                let y = parse_float::<U>(tokens.next(), line_nr)?;
+                let z = parse_float::<U>(tokens.next(), line_nr)?;
+                data.normals.push(T::new(x, y, z));
+            }
+            "f" => {
+                let mut corners = Vec::<ObjCorner>::new();
+
+                for token in tokens {
+
+                    let mut parts = token.split('/');
+
+                    let position = parse_index(parts.next().unwrap_or(""), data.positions.len(), line_nr)?;
+                    let texture_coordinate = match parts.next() {
+                        Some(t) if !t.is_empty() => Some(parse_index(t, data.texture_coordinates.len(), line_nr)?),
+                        _ => None,
+                    };
+                    let normal = match parts.next() {
+                        Some(t) if !t.is_empty() => Some(parse_index(t, data.normals.len(), line_nr)?),
+                        _ => None,
+                    };
+
+                    corners.push(ObjCorner { position, texture_coordinate, normal });
+                }
+
+                if corners.len() < 3 {
+                    return Err(MeshIoError::parse(line_nr, "A face needs at least three vertices"));
+                }
+
+                data.faces.push(ObjFace { corners, object_name: object_name.clone(), group_name: group_name.clone() });
+            }
+            "o" => {
+                let names = tokens.collect::<Vec<&str>>();
+                object_name = if names.is_empty() { None } else { Some(names.join(" ")) };
+                group_name = None;
+            }
+            "g" => {
+                let names = tokens.collect::<Vec<&str>>();
+                group_name = if names.is_empty() { None } else { Some(names.join(" ")) };
+            }
+            //Materials, smoothing groups, lines, points and free-form geometry are not supported
+            _ => {}
+        }
+    }
+
+    return Ok(data);
+}
+
+fn assign_vertices<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(data : &ObjData<T, U>) -> ObjVertexData<T, U> {
+
+    let has_texture_coordinates = data.faces.iter().any(|f| f.corners.iter().any(|c| c.texture_coordinate.is_some()));
+    let has_normals = data.faces.iter().any(|f| f.corners.iter().any(|c| c.normal.is_some()));
+
+    //The first combination referencing a position keeps its index, so files that use
+    //the same index for all attributes are read without reordering vertices
+    let mut first_corner = vec![None; data.positions.len()];
+    let mut corner_map = HashMap::<ObjCorner, usize>::new();
+
+    let mut vertices = data.positions.clone();
+    let mut texture_coordinates = vec![[U::zero(), U::zero()]; if has_texture_coordinates { vertices.len() } else { 0 }];
+    let mut normals = vec![T::zero(); if has_normals { vertices.len() } else { 0 }];
+    let mut faces = Vec::<Vec<usize>>::with_capacity(data.faces.len());
+
+    for face in &data.faces {
+
+        let mut indices = Vec::<usize>::with_capacity(face.corners.len());
+
+        for corner in &face.corners {
+
+            let idx = if first_corner[corner.position].is_none() {
+                first_corner[corner.position] = Some(*corner);
+                corner_map.insert(*corner, corner.position);
+                corner.position
+            } else if let Some(idx) = corner_map.get(corner) {
+                *idx
+            } else {
+                let idx = vertices.len();
+                vertices.push(data.positions[corner.position]);
+                if has_texture_coordinates { texture_coordinates.push([U::zero(), U::zero()]); }
+                if has_normals { normals.push(T::zero()); }
+                corner_map.insert(*corner, idx);
+                idx
+            };
+
+            if let Some(t) = corner.texture_coordinate {
+                texture_coordinates[idx] = data.texture_coordinates[t];
+            }
+            if let Some(n) = corner.normal {
+                normals[idx] = data.normals[n];
+            }
+
+            indices.push(idx);
+        }
+
+        faces.push(indices);
+    }
+
+    return ObjVertexData {
+        vertices,
+        texture_coordinates: if has_texture_coordinates { Some(texture_coordinates) } else { None },
+        normals: if has_normals { Some(normals) } else { None },
+        faces,
+    };
+}
+
+fn to_index<IndexType : PrimInt + Hash + Unsigned>(idx : usize) -> Result<IndexType, MeshIoError> {
+    return NumCast::from(idx).ok_or_else(|| MeshIoError::InvalidData(format!("Vertex index {} does not fit into the index type", idx)));
+}
+
+fn push_group(groups : &mut Vec<ObjGroup>, face : &ObjFace, face_idx : usize, face_count : usize) {
+
+    if face.object_name.is_none() && face.group_name.is_none() {
+        return;
+    }
+
+    if let Some(last) = groups.last_mut()
+        && last.object_name == face.object_name && last.group_name == face.group_name && last.first_face + last.face_count == face_idx {
+        last.face_count += face_count;
+        return;
+    }
+
+    groups.push(ObjGroup {
+        object_name: face.object_name.clone(),
+        group_name: face.group_name.clone(),
+        first_face: face_idx,
+        face_count
+    });
+}
+
+fn attach_properties<MeshType : VertexProperties, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static>(
+    mesh : &mut MeshType, normals : Option<Vec<T>>, texture_coordinates : Option<Vec<[U; 2]>>)
+{
+    if let Some(texture_coordinates) = texture_coordinates {
+        mesh.add_vertex_property(UvMap::from(texture_coordinates));
+    }
+
+    if let Some(normals) = normals {
+
+        let mut normal_map = NormalMap::<T, U>::with_capacity(normals.len());
+        for n in normals {
+            normal_map.push(n);
+        }
+        mesh.add_vertex_property(normal_map);
+    }
+}
+
+/// Reads an OBJ file into a Triangle-Vertex Mesh. Faces with more than three vertices are triangulated.
+/// The face ranges of the groups refer to the resulting triangles.
+///
+/// # Examples
+/// ```
+/// let file = File::open("model.obj")?;
+/// let obj = obj::read_triangle_mesh::<_, Vec3f, f32, u32>(BufReader::new(file))?;
+///
+/// let mesh = obj.mesh;
+/// ```
+pub fn read_triangle_mesh<R : BufRead, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(reader : R)
+    -> Result<ObjMesh<TriangleVertexMesh<T, U, IndexType>>, MeshIoError>
+{
+    let data = parse::<R, T, U>(reader)?;
+    let vertex_data = assign_vertices(&data);
+
+    let mut indices = Vec::<IndexType>::with_capacity(vertex_data.faces.len() * 3);
+    let mut groups = Vec::<ObjGroup>::new();
+    let mut triangle_count = 0;
+
+    for (face_idx, face) in vertex_data.faces.iter().enumerate() {
+
+        let mut face_indices = Vec::<IndexType>::with_capacity(face.len());
+        for idx in face {
+            face_indices.push(to_index(*idx)?);
+        }
+
+        let triangles = triangulate_face(&vertex_data.vertices, &face_indices);
+        let count = triangles.len() / 3;
+
+        push_group(&mut groups, &data.faces[face_idx], triangle_count, count);
+
+        indices.extend(triangles);
+        triangle_count += count;
+    }
+
+    let mut mesh = TriangleVertexMesh::from(vertex_data.vertices, indices).unwrap();
+    attach_properties::<_, T, U>(&mut mesh, vertex_data.normals, vertex_data.texture_coordinates);
+
+    return Ok(ObjMesh { mesh, groups });
+}
+
+/// Reads an OBJ file into a Face-Vertex Mesh, keeping all faces as they are.
+///
+/// # Examples
+/// ```
+/// let file = File::open("model.obj")?;
+/// let obj = obj::read_face_vertex_mesh::<_, Vec3f, f32, u32>(BufReader::new(file))?;
+/// ```
+pub fn read_face_vertex_mesh<R : BufRead, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(reader : R)
+    -> Result<ObjMesh<FaceVertexMesh<T, U, IndexType>>, MeshIoError>
+{
+    let data = parse::<R, T, U>(reader)?;
+    let vertex_data = assign_vertices(&data);
+
+    let mut faces = Vec::<Vec<IndexType>>::with_capacity(vertex_data.faces.len());
+    let mut groups = Vec::<ObjGroup>::new();
+
+    for (face_idx, face) in vertex_data.faces.iter().enumerate() {
+
+        let mut face_indices = Vec::<IndexType>::with_capacity(face.len());
+        for idx in face {
+            face_indices.push(to_index(*idx)?);
+        }
+
+        push_group(&mut groups, &data.faces[face_idx], face_idx, 1);
+        faces.push(face_indices);
+    }
+
+    let mut mesh = FaceVertexMesh::from(vertex_data.vertices, faces).unwrap();
+    attach_properties::<_, T, U>(&mut mesh, vertex_data.normals, vertex_data.texture_coordinates);
+
+    return Ok(ObjMesh { mesh, groups });
+}
+
+fn write_internal<W : Write, MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, mesh : &MeshType, groups : &[ObjGroup]) -> Result<(), MeshIoError>
+{
+    let vertices = mesh.get_vertices();
+    let vertex_count = vertices.len();
+
+    let normals = mesh.get_vertex_property_ref::<NormalMap<T, U>, T>(PropertyType::NORMAL)
+        .filter(|n| n.len() == vertex_count);
+    let texture_coordinates = get_texture_coordinates::<_, U>(mesh, vertex_count);
+
+    for v in vertices {
+        writeln!(writer, "v {} {} {}", format_float(v.x()), format_float(v.y()), format_float(v.z()))?;
+    }
+
+    if let Some(t) = texture_coordinates {
+        for uv in t {
+            writeln!(writer, "vt {} {}", format_float(uv[0]), format_float(uv[1]))?;
+        }
+    }
+
+    if let Some(n) = normals {
+        for i in 0..vertex_count {
+            let normal = n.get(i);
+            writeln!(writer, "vn {} {} {}", format_float(normal.x()), format_float(normal.y()), format_float(normal.z()))?;
+        }
+    }
+
+    let mut group_iter = groups.iter().peekable();
+    let mut active_group : Option<&ObjGroup> = None;
+    let mut current_object : Option<&String> = None;
+    let mut current_group : Option<&String> = None;
+
+    for f in 0..mesh.get_face_count() {
+
+        while let Some(group) = group_iter.peek() {
+
+            if group.first_face > f {
+                break;
+            }
+
+            if let Some(name) = group.object_name.as_ref().filter(|name| Some(*name) != current_object) {
+                writeln!(writer, "o {}", name)?;
+                current_object = Some(name);
+                current_group = None;
+            }
+            active_group = Some(group);
+            group_iter.next();
+        }
+
+        //Faces after the end of a group belong to the default group, which an empty g statement switches back to
+        let group_name = active_group.filter(|g| f < g.first_face + g.face_count).and_then(|g| g.group_name.as_ref());
+        if group_name != current_group {
+            match group_name {
+                Some(name) => writeln!(writer, "g {}", name)?,
+                None => writeln!(writer, "g")?,
+            }
+            current_group = group_name;
+        }
+
+        write!(writer, "f")?;
+        for idx in mesh.get_face(NumCast::from(f).unwrap()) {
+
+            let i = idx.to_usize().unwrap() + 1;
+            match (texture_coordinates.is_some(), normals.is_some()) {
+                (true, true) => write!(writer, " {}/{}/{}", i, i, i)?,
+                (true, false) => write!(writer, " {}/{}", i, i)?,
+                (false, true) => write!(writer, " {}//{}", i, i)?,
+                (false, false) => write!(writer, " {}", i)?,
+            }
+        }
+        writeln!(writer)?;
+    }
+
+    return Ok(());
+}
+
+/// Writes any mesh as OBJ file. If the mesh has [NormalMap] or [UvMap] vertex properties, normals and the first channel
+/// of the texture coordinates are written as well.
+///
+/// # Examples
+/// ```
+/// let mut file = File::create("model.obj")?;
+/// obj::write_mesh(&mut file, &mesh)?;
+/// ```
+pub fn write_mesh<W : Write, MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, mesh : &MeshType) -> Result<(), MeshIoError>
+{
+    return write_internal(writer, mesh, &[]);
+}
+
+/// Writes a mesh together with its groups as OBJ file.
+pub fn write_obj_mesh<W : Write, MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, obj : &ObjMesh<MeshType>) -> Result<(), MeshIoError>
+{
+    return write_internal(writer, &obj.mesh, &obj.groups);
+}
+
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::common_properties::NormalMap;
+    use crate::common_properties::UvMap;
+    use crate::mesh::Mesh;
+    use crate::mesh_io::MeshIoError;
+    use crate::mesh_io::obj;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
+    use crate::vector::Vec3f;
+    use crate::vector::Vec3;
+
+    const QUAD_AND_TRIANGLE : &str = "
+# A quad and a triangle, using relative indices
+o Shape
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+g quad
+f 1/1/1 2/2/1 3/3/1 4/4/1
+v 2 0 0
+g triangle
+f -4/-2/-1 -1/-4/-1 \\
+  -3/-1/-1
+";
+
+    #[test]
+    fn test_read_triangle_mesh() {
+
+        let obj = obj::read_triangle_mesh::<_, Vec3f, f32, u32>(QUAD_AND_TRIANGLE.as_bytes()).unwrap();
+        let mut mesh = obj.mesh;
+
+        assert_eq!(mesh.get_face_count(), 3);
+        assert_eq!(mesh.get_face(2), Vec::from([5, 4, 6]));
+
+        assert_eq!(obj.groups.len(), 2);
+        assert_eq!(obj.groups[1].group_name.as_deref(), Some("triangle"));
+        assert_eq!(obj.groups[1].first_face, 2);
+        assert_eq!(obj.groups[0].object_name.as_deref(), Some("Shape"));
+
+        //Vertex 1 and 2 are referenced with other texture coordinates by the triangle
+        assert_eq!(mesh.get_vertices().len(), 7);
+        let uvs = mesh.get_vertex_property::<UvMap<f32>, Vec<[f32; 2]>>(PropertyType::UV).unwrap();
+        assert_eq!(uvs.len(), 7);
+        assert_eq!(uvs.get_uv(6, 0), [0.0, 1.0]);
+
+        let normals = mesh.get_vertex_property::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+        assert_eq!(normals.len(), 7);
+        assert_eq!(normals.get(0).z(), 1.0);
+    }
+
+    #[test]
+    fn test_read_face_vertex_mesh() {
+
+        let obj = obj::read_face_vertex_mesh::<_, Vec3f, f32, u32>(QUAD_AND_TRIANGLE.as_bytes()).unwrap();
+
+        assert_eq!(obj.mesh.get_face_count(), 2);
+        assert_eq!(obj.mesh.get_face(0).len(), 4);
+    }
+
+    #[test]
+    fn test_parse_error_line() {
+
+        let content = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n";
+        let result = obj::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes());
+
+        match result {
+            Err(MeshIoError::Parse { line, .. }) => assert_eq!(line, 4),
+            _ => panic!("Expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_write_read_roundtrip() {
+
+        let obj = obj::read_face_vertex_mesh::<_, Vec3f, f32, u32>(QUAD_AND_TRIANGLE.as_bytes()).unwrap();
+
+        let mut buffer = Vec::<u8>::new();
+        obj::write_obj_mesh(&mut buffer, &obj).unwrap();
+
+        let reread = obj::read_face_vertex_mesh::<_, Vec3f, f32, u32>(buffer.as_slice()).unwrap();
+
+        assert_eq!(reread.mesh.get_vertices().len(), obj.mesh.get_vertices().len());
+        assert_eq!(reread.mesh.get_face(1), obj.mesh.get_face(1));
+        assert_eq!(reread.groups, obj.groups);
+        assert!(reread.mesh.get_vertex_property_ref::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).is_some());
+        assert!(reread.mesh.get_vertex_property_ref::<UvMap<f32>, Vec<[f32; 2]>>(PropertyType::UV).is_some());
+    }
+
+    #[test]
+    fn test_write_read_default_group() {
+
+        //The second face leaves the named group and belongs to no group
+        let content = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\ng first\nf 1 2 3\ng\nf 1 3 4\n";
+        let obj = obj::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes()).unwrap();
+        assert_eq!(obj.groups.len(), 1);
+        assert_eq!(obj.groups[0].face_count, 1);
+
+        let mut buffer = Vec::<u8>::new();
+        obj::write_obj_mesh(&mut buffer, &obj).unwrap();
+
+        let reread = obj::read_triangle_mesh::<_, Vec3f, f32, u32>(buffer.as_slice()).unwrap();
+        assert_eq!(reread.groups, obj.groups);
+    }
+
+    #[test]
+    fn test_read_unnamed_object() {
+
+        //An o statement without a name switches back to faces without an object
+        let content = "v 0 0 0\nv 1 0 0\nv 1 1 0\no first\nf 1 2 3\no\nf 1 3 2\n";
+        let obj = obj::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes()).unwrap();
+        assert_eq!(obj.groups.len(), 1);
+        assert_eq!(obj.groups[0].object_name.as_deref(), Some("first"));
+        assert_eq!(obj.groups[0].face_count, 1);
+    }
+}
@@ -0,0 +1,486 @@
+//! # Object File Format (OFF)
+//!
+//! Text-based format, which starts with a keyword, followed by the number of vertices, faces and edges.
+//! Each vertex is one line of coordinates, each face one line with its vertex count and zero-based indices,
+//! optionally followed by a color. Prefixes of the keyword announce additional data per vertex:
+//!
+//! | Keyword | Vertex line                   |
+//! |---------|-------------------------------|
+//! | OFF     | x y z                         |
+//! | NOFF    | x y z nx ny nz                |
+//! | COFF    | x y z r g b [a]               |
+//! | STOFF   | x y z s t                     |
+//!
+//! Prefixes can be combined in the order ST, C, N (e.g. CNOFF has normals before colors). Colors are either
+//! integers from 0 to 255 or floats from 0 to 1, which are told apart by a decimal point or exponent in each component.
+//! Four-dimensional and n-dimensional variants are not supported.
+//!
+//! Normals, texture coordinates and colors are attached to the mesh as [NormalMap], [UvMap] and [RgbaMap], where
+//! colors without alpha are opaque. Faces without a color in a file with face colors are white.
+
+use std::hash::Hash;
+use std::io::BufRead;
+use std::io::Write;
+
+use num_traits::Float;
+use num_traits::Num;
+use num_traits::NumCast;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+
+use crate::common_properties::NormalMap;
+use crate::common_properties::RgbaMap;
+use crate::common_properties::UvMap;
+use crate::face_vertex_mesh::FaceVertexMesh;
+use crate::mesh::Mesh;
+use crate::mesh_io::MAX_LIST_CAPACITY;
+use crate::mesh_io::MeshIoError;
+use crate::mesh_io::format_float;
+use crate::mesh_io::get_face_colors;
+use crate::mesh_io::get_texture_coordinates;
+use crate::mesh_io::get_vertex_colors;
+use crate::mesh_triangulation::face_triangulation::triangulate_face;
+use crate::property_map::FaceProperties;
+use crate::property_map::PropertyMap;
+use crate::property_map::PropertyType;
+use crate::property_map::VertexProperties;
+use crate::triangle_vertex_mesh::TriangleVertexMesh;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+struct OffData<T, U> {
+    vertices : Vec<T>,
+    normals : Option<Vec<T>>,
+    vertex_colors : Option<Vec<[U; 4]>>,
+    texture_coordinates : Option<Vec<[U; 2]>>,
+    faces : Vec<Vec<usize>>,
+    face_colors : Option<Vec<[U; 4]>>,
+}
+
+fn parse_float<U : Num + PartialOrd<U> + Float>(token : &str, line : usize) -> Result<U, MeshIoError> {
+
+    let value = token.parse::<f64>().map_err(|_| MeshIoError::parse(line, format!("Invalid number '{}'", token)))?;
+    return <U as NumCast>::from(value).ok_or_else(|| MeshIoError::parse(line, format!("Number '{}' out of range", token)));
+}
+
+/// Parses a color with three or four components. Each component is a float from 0 to 1, if it is written with a decimal
+/// point or exponent, and an integer from 0 to 255 otherwise, which is scaled to the range from 0 to 1.
+fn parse_color<U : Num + PartialOrd<U> + Float>(tokens : &[&str], line : usize) -> Result<[U; 4], MeshIoError> {
+
+    let mut color = [U::one(); 4];
+    for (i, token) in tokens.iter().enumerate() {
+        let is_float = token.contains(['.', 'e', 'E']);
+        let scale = if is_float { 1.0 } else { 255.0 };
+        let value = token.parse::<f64>().map_err(|_| MeshIoError::parse(line, format!("Invalid color component '{}'", token)))?;
+        color[i] = NumCast::from(value / scale).unwrap();
+    }
+    return Ok(color);
+}
+
+fn parse<R : BufRead, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(reader : R) -> Result<OffData<T, U>, MeshIoError> {
+
+    //Lines without content are skipped, but the original line numbers are kept for errors
+    let mut lines = Vec::<(usize, String)>::new();
+    for (line_idx, line) in reader.lines().enumerate() {
+
+        let line = line?;
+        let content = match line.find('#') {
+            Some(pos) => &line[..pos],
+            None => &line[..],
+        };
+        if !content.trim().is_empty() {
+            lines.push((line_idx + 1, String::from(content.trim())));
+        }
+    }
+    let mut lines = lines.into_iter();
+
+    let (header_line, header) = lines.next().ok_or_else(|| MeshIoError::parse(1, "Empty file"))?;
+
+    //Some datasets omit the line break between keyword and counts (e.g. "OFF8 6 0")
+    let keyword_end = header.find("OFF").ok_or_else(|| MeshIoError::parse(header_line, "Missing OFF keyword"))?;
+    let prefix = &header[..keyword_end];
+    let rest = header[keyword_end + 3..].trim();
+
+    let mut remaining_prefix = prefix;
+    let has_texture_coordinates = remaining_prefix.starts_with("ST");
+    if has_texture_coordinates { remaining_prefix = &remaining_prefix[2..]; }
+    let has_colors = remaining_prefix.starts_with('C');
+    if has_colors { remaining_prefix = &remaining_prefix[1..]; }
+    let has_normals = remaining_prefix.starts_with('N');
+    if has_normals { remaining_prefix = &remaining_prefix[1..]; }
+
+    if !remaining_prefix.is_empty() || !rest.chars().all(|c| c.is_ascii_digit() || c.is_whitespace()) {
+        return Err(MeshIoError::parse(header_line, format!("Unsupported keyword '{}'", header)));
+    }
+
+    let (count_line, counts) = if rest.is_empty() {
+        lines.next().ok_or_else(|| MeshIoError::parse(header_line, "Missing vertex and face counts"))?
+    } else {
+        (header_line, String::from(rest))
+    };
+
+    let counts = counts.split_whitespace().map(|t| t.parse::<usize>()).collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| MeshIoError::parse(count_line, "Invalid vertex and face counts"))?;
+    if counts.len() < 2 {
+        return Err(MeshIoError::parse(count_line, "Missing vertex and face counts"));
+    }
+    let (vertex_count, face_count) = (counts[0], counts[1]);
+
+    //Counts come from the file, so the vectors grow while reading instead of reserving memory for them up front
+    let mut data = OffData::<T, U> {
+        vertices: Vec::<T>::new(),
+        normals: if has_normals { Some(Vec::<T>::new()) } else { None },
+        vertex_colors: if has_colors { Some(Vec::<[U; 4]>::new()) } else { None },
+        texture_coordinates: if has_texture_coordinates { Some(Vec::<[U; 2]>::new()) } else { None },
+        faces: Vec::<Vec<usize>>::new(),
+        face_colors: None,
+    };
+
+    for _ in 0..vertex_count {
+
+        let (line_nr, line) = lines.next().ok_or_else(|| MeshIoError::parse(count_line, "Unexpected end of file, vertices are missing"))?;
+        let tokens = line.split_whitespace().collect::<Vec<&str>>();
+
+        let normal_count = if has_normals { 3 } else { 0 };
+        let texture_count = if has_texture_coordinates { 2 } else { 0 };
+        let color_count = tokens.len().saturating_sub(3 + normal_count + texture_count);
+
+        if tokens.len() < 3 + normal_count + texture_count || (has_colors && color_count != 3 && color_count != 4) {
+            return Err(MeshIoError::parse(line_nr, "Wrong number of values for a vertex"));
+        }
+
+        data.vertices.push(T::new(parse_float(tokens[0], line_nr)?, parse_float(tokens[1], line_nr)?, parse_float(tokens[2], line_nr)?));
+        let mut next = 3;
+
+        if let Some(normals) = data.normals.as_mut() {
+            normals.push(T::new(parse_float(tokens[3], line_nr)?, parse_float(tokens[4], line_nr)?, parse_float(tokens[5], line_nr)?));
+            next += 3;
+        }
+        if let Some(colors) = data.vertex_colors.as_mut() {
+            colors.push(parse_color(&tokens[next..next + color_count], line_nr)?);
+            next += color_count;
+        }
+        if let Some(texture_coordinates) = data.texture_coordinates.as_mut() {
+            texture_coordinates.push([parse_float(tokens[next], line_nr)?, parse_float(tokens[next + 1], line_nr)?]);
+        }
+    }
+
+    let mut face_colors = Vec::<Option<[U; 4]>>::new();
+
+    for _ in 0..face_count {
+
+        let (line_nr, line) = lines.next().ok_or_else(|| MeshIoError::parse(count_line, "Unexpected end of file, faces are missing"))?;
+        let tokens = line.split_whitespace().collect::<Vec<&str>>();
+
+        let corner_count = tokens[0].parse::<usize>().map_err(|_| MeshIoError::parse(line_nr, format!("Invalid vertex count '{}'", tokens[0])))?;
+        if corner_count < 3 {
+            return Err(MeshIoError::parse(line_nr, "A face needs at least three vertices"));
+        }
+        if tokens.len() < 1 + corner_count {
+            return Err(MeshIoError::parse(line_nr, "Missing vertex indices"));
+        }
+
+        let mut face = Vec::<usize>::with_capacity(corner_count.min(MAX_LIST_CAPACITY));
+        for token in &tokens[1..1 + corner_count] {
+            let idx = token.parse::<usize>().map_err(|_| MeshIoError::parse(line_nr, format!("Invalid index '{}'", token)))?;
+            if idx >= vertex_count {
+                return Err(MeshIoError::parse(line_nr, format!("Index '{}' out of range", token)));
+            }
+            face.push(idx);
+        }
+        data.faces.push(face);
+
+        //A single value after the indices is an index into a color map, which is not supported
+        let color_tokens = &tokens[1 + corner_count..];
+        face_colors.push(match color_tokens.len() {
+            3 | 4 => Some(parse_color(color_tokens, line_nr)?),
+            _ => None,
+        });
+    }
+
+    if face_colors.iter().any(|c| c.is_some()) {
+        data.face_colors = Some(face_colors.into_iter().map(|c| c.unwrap_or([U::one(); 4])).collect());
+    }
+
+    return Ok(data);
+}
+
+fn attach_properties<MeshType : VertexProperties + FaceProperties, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static>(
+    mesh : &mut MeshType, normals : Option<Vec<T>>, vertex_colors : Option<Vec<[U; 4]>>, texture_coordinates : Option<Vec<[U; 2]>>, face_colors : Option<Vec<[U; 4]>>)
+{
+    if let Some(normals) = normals {
+
+        let mut normal_map = NormalMap::<T, U>::with_capacity(normals.len());
+        for n in normals {
+            normal_map.push(n);
+        }
+        mesh.add_vertex_property(normal_map);
+    }
+
+    if let Some(colors) = vertex_colors {
+        mesh.add_vertex_property(RgbaMap::from(colors));
+    }
+    if let Some(texture_coordinates) = texture_coordinates {
+        mesh.add_vertex_property(UvMap::from(texture_coordinates));
+    }
+    if let Some(colors) = face_colors {
+        mesh.add_face_property(RgbaMap::from(colors));
+    }
+}
+
+fn to_indices<IndexType : PrimInt + Hash + Unsigned>(face : &[usize]) -> Result<Vec<IndexType>, MeshIoError> {
+
+    let mut indices = Vec::<IndexType>::with_capacity(face.len());
+    for idx in face {
+        indices.push(NumCast::from(*idx)
+            .ok_or_else(|| MeshIoError::InvalidData(format!("Vertex index {} does not fit into the index type", idx)))?);
+    }
+    return Ok(indices);
+}
+
+/// Reads an OFF file (including its C, N and ST variants) into a Triangle-Vertex Mesh.
+/// Faces with more than three vertices are triangulated and their colors are repeated for each triangle.
+///
+/// # Examples
+/// ```
+/// let file = File::open("model.off")?;
+/// let mesh = off::read_triangle_mesh::<_, Vec3f, f32, u32>(BufReader::new(file))?;
+/// ```
+pub fn read_triangle_mesh<R : BufRead, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(reader : R)
+    -> Result<TriangleVertexMesh<T, U, IndexType>, MeshIoError>
+{
+    let data = parse::<R, T, U>(reader)?;
+
+    let mut indices = Vec::<IndexType>::with_capacity(data.faces.len() * 3);
+    let mut face_colors = data.face_colors.as_ref().map(|_| Vec::<[U; 4]>::with_capacity(data.faces.len()));
+
+    for (face_idx, face) in data.faces.iter().enumerate() {
+
+        let triangles = triangulate_face(&data.vertices, &to_indices::<IndexType>(face)?);
+        if let (Some(colors), Some(source)) = (face_colors.as_mut(), data.face_colors.as_ref()) {
+            colors.extend(std::iter::repeat_n(source[face_idx], triangles.len() / 3));
+        }
+        indices.extend(triangles);
+    }
+
+    let mut mesh = TriangleVertexMesh::from(data.vertices, indices).unwrap();
+    attach_properties::<_, T, U>(&mut mesh, data.normals, data.vertex_colors, data.texture_coordinates, face_colors);
+
+    return Ok(mesh);
+}
+
+/// Reads an OFF file (including its C, N and ST variants) into a Face-Vertex Mesh, keeping all faces as they are.
+///
+/// # Examples
+/// ```
+/// let file = File::open("model.off")?;
+/// let mesh = off::read_face_vertex_mesh::<_, Vec3f, f32, u32>(BufReader::new(file))?;
+/// ```
+pub fn read_face_vertex_mesh<R : BufRead, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(reader : R)
+    -> Result<FaceVertexMesh<T, U, IndexType>, MeshIoError>
+{
+    let data = parse::<R, T, U>(reader)?;
+
+    let mut faces = Vec::<Vec<IndexType>>::with_capacity(data.faces.len());
+    for face in &data.faces {
+        faces.push(to_indices(face)?);
+    }
+
+    let mut mesh = FaceVertexMesh::from(data.vertices, faces).unwrap();
+    attach_properties::<_, T, U>(&mut mesh, data.normals, data.vertex_colors, data.texture_coordinates, data.face_colors);
+
+    return Ok(mesh);
+}
+
+fn write_color<W : Write, U : Num + PartialOrd<U> + Float>(writer : &mut W, color : &[U; 4]) -> Result<(), MeshIoError> {
+
+    for c in color {
+        let value = (c.to_f64().unwrap().clamp(0.0, 1.0) * 255.0).round() as u8;
+        write!(writer, " {}", value)?;
+    }
+    return Ok(());
+}
+
+/// Writes any mesh as OFF file. Normals, colors and the first channel of the texture coordinates are written as well,
+/// if the mesh has the corresponding [NormalMap], [RgbMap] / [RgbaMap] and [UvMap] properties, which also determines
+/// the keyword (e.g. NOFF or STCOFF). Colors are written as integers from 0 to 255.
+///
+/// # Examples
+/// ```
+/// let mut file = File::create("model.off")?;
+/// off::write_mesh(&mut file, &mesh)?;
+/// ```
+pub fn write_mesh<W : Write, MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, mesh : &MeshType) -> Result<(), MeshIoError>
+{
+    let vertices = mesh.get_vertices();
+    let vertex_count = vertices.len();
+    let face_count = mesh.get_face_count();
+
+    let normals = mesh.get_vertex_property_ref::<NormalMap<T, U>, T>(PropertyType::NORMAL)
+        .filter(|n| n.len() == vertex_count);
+    let vertex_colors = get_vertex_colors::<_, U>(mesh, vertex_count);
+    let face_colors = get_face_colors::<_, U>(mesh, face_count);
+    let texture_coordinates = get_texture_coordinates::<_, U>(mesh, vertex_count);
+
+    let mut keyword = String::new();
+    if texture_coordinates.is_some() { keyword.push_str("ST"); }
+    if vertex_colors.is_some() { keyword.push('C'); }
+    if normals.is_some() { keyword.push('N'); }
+    keyword.push_str("OFF");
+
+    writeln!(writer, "{}", keyword)?;
+    writeln!(writer, "{} {} 0", vertex_count, face_count)?;
+
+    for (i, v) in vertices.iter().enumerate() {
+
+        write!(writer, "{} {} {}", format_float(v.x()), format_float(v.y()), format_float(v.z()))?;
+        if let Some(n) = normals {
+            let normal = n.get(i);
+            write!(writer, " {} {} {}", format_float(normal.x()), format_float(normal.y()), format_float(normal.z()))?;
+        }
+        if let Some(c) = &vertex_colors {
+            write_color(writer, &c[i])?;
+        }
+        if let Some(t) = texture_coordinates {
+            write!(writer, " {} {}", format_float(t[i][0]), format_float(t[i][1]))?;
+        }
+        writeln!(writer)?;
+    }
+
+    for f in 0..face_count {
+
+        let face = mesh.get_face(NumCast::from(f).unwrap());
+        write!(writer, "{}", face.len())?;
+        for idx in face {
+            write!(writer, " {}", idx.to_usize().unwrap())?;
+        }
+        if let Some(c) = &face_colors {
+            write_color(writer, &c[f])?;
+        }
+        writeln!(writer)?;
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::common_properties::NormalMap;
+    use crate::common_properties::RgbaMap;
+    use crate::mesh::Mesh;
+    use crate::mesh_io::MeshIoError;
+    use crate::mesh_io::off;
+    use crate::property_map::FaceProperties;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
+    use crate::vector::Vec3f;
+    use crate::vector::Vec3;
+
+    const COLORED_QUAD : &str = "
+COFF
+# A quad with a colored triangle attached
+5 2 0
+0 0 0 255 0 0
+1 0 0 0 255 0
+1 1 0 0 0 255 128
+0 1 0 255 255 255
+2 0 0 0 0 0
+
+4 0 1 2 3
+3 1 4 2 0.5 0.5 0.5
+";
+
+    #[test]
+    fn test_read_colors() {
+
+        let mesh = off::read_face_vertex_mesh::<_, Vec3f, f32, u32>(COLORED_QUAD.as_bytes()).unwrap();
+
+        assert_eq!(mesh.get_face_count(), 2);
+        assert_eq!(mesh.get_face(1), Vec::from([1, 4, 2]));
+
+        let vertex_colors = mesh.get_vertex_property_ref::<RgbaMap<f32>, [f32; 4]>(PropertyType::COLOR).unwrap();
+        assert_eq!(vertex_colors.get(1), [0.0, 1.0, 0.0, 1.0]);
+        assert!((vertex_colors.get(2)[3] - 128.0 / 255.0).abs() < 1e-6);
+
+        //The quad has no color, so it is white
+        let face_colors = mesh.get_face_property_ref::<RgbaMap<f32>, [f32; 4]>(PropertyType::COLOR).unwrap();
+        assert_eq!(face_colors.get(0), [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(face_colors.get(1), [0.5, 0.5, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_read_color_forms() {
+
+        let read_color = |color : &str| -> [f32; 4] {
+            let content = format!("COFF 3 1 0\n0 0 0 {}\n1 0 0 {}\n0 1 0 {}\n3 0 1 2\n", color, color, color);
+            let mesh = off::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes()).unwrap();
+            return mesh.get_vertex_property_ref::<RgbaMap<f32>, [f32; 4]>(PropertyType::COLOR).unwrap().get(0);
+        };
+
+        //Integer components are scaled from 0..255, float components are taken as they are
+        assert_eq!(read_color("255 0 0"), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(read_color("1 0 0"), [1.0 / 255.0, 0.0, 0.0, 1.0]);
+        assert_eq!(read_color("1.0 0.0 0.0"), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(read_color("1e0 0.5 0.0 0.25"), [1.0, 0.5, 0.0, 0.25]);
+    }
+
+    #[test]
+    fn test_read_triangle_mesh() {
+
+        let content = "NOFF 3 1 0\n0 0 0 0 0 1\n1 0 0 0 0 1\n0 1 0 0 0 1\n3 0 1 2\n";
+        let mut mesh = off::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes()).unwrap();
+        assert_eq!(mesh.get_face_count(), 1);
+
+        let normals = mesh.get_vertex_property::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+        assert_eq!(normals.get(2).z(), 1.0);
+
+        let mesh = off::read_triangle_mesh::<_, Vec3f, f32, u32>(COLORED_QUAD.as_bytes()).unwrap();
+        assert_eq!(mesh.get_face_count(), 3);
+        assert_eq!(mesh.get_face_property_ref::<RgbaMap<f32>, [f32; 4]>(PropertyType::COLOR).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_error_line() {
+
+        let content = "OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n3 0 1 3\n";
+        match off::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes()) {
+            Err(MeshIoError::Parse { line, .. }) => assert_eq!(line, 6),
+            _ => panic!("Expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_large_counts() {
+
+        //The counts in the header and the face exceed the content of the file
+        assert!(off::read_triangle_mesh::<_, Vec3f, f32, u32>("OFF\n4000000000000 1 0\n".as_bytes()).is_err());
+        assert!(off::read_face_vertex_mesh::<_, Vec3f, f32, u32>("OFF\n3 4000000000000 0\n0 0 0\n1 0 0\n0 1 0\n".as_bytes()).is_err());
+
+        let content = "OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n4000000000000 0 1 2\n";
+        assert!(off::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_write_read_roundtrip() {
+
+        let mesh = off::read_face_vertex_mesh::<_, Vec3f, f32, u32>(COLORED_QUAD.as_bytes()).unwrap();
+
+        let mut buffer = Vec::<u8>::new();
+        off::write_mesh(&mut buffer, &mesh).unwrap();
+        assert!(buffer.starts_with(b"COFF\n"));
+
+        let reread = off::read_face_vertex_mesh::<_, Vec3f, f32, u32>(buffer.as_slice()).unwrap();
+        assert_eq!(reread.get_vertices().len(), mesh.get_vertices().len());
+        assert_eq!(reread.get_vertices()[4].x(), 2.0);
+        assert_eq!(reread.get_face(0), mesh.get_face(0));
+
+        let colors = mesh.get_vertex_property_ref::<RgbaMap<f32>, [f32; 4]>(PropertyType::COLOR).unwrap();
+        let reread_colors = reread.get_vertex_property_ref::<RgbaMap<f32>, [f32; 4]>(PropertyType::COLOR).unwrap();
+        for v in 0..5 {
+            assert_eq!(reread_colors.get(v), colors.get(v));
+        }
+    }
+}
@@ -0,0 +1,1200 @@
+//! # Polygon File Format (PLY)
+//!
+//! PLY files start with a text header, which declares a list of elements (e.g. vertices and faces) and
+//! the typed properties of each element. The data that follows is either stored as text or in binary
+//! with little or big endianness.
+//!
+//! Positions (x, y, z) of vertices are mapped into the geometry, while normals (nx, ny, nz), colors (red, green, blue
+//! and optionally alpha) and texture coordinates (s, t or u, v) are mapped into its [NormalMap], [RgbMap] / [RgbaMap]
+//! and [UvMap]. Face colors are mapped as well. All other properties of vertices and faces are added as [AttributeMap]
+//! property maps of doubles, or of lists of doubles for list properties, which are named after the property, so they follow
+//! the geometry through topology-changing operations. Their names and original types are kept as [PlyProperty], so they
+//! survive a round trip. Elements other than vertices and faces are skipped.
+
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::io::BufRead;
+use std::io::Read;
+use std::io::Write;
+use std::mem;
+
+use num_traits::Float;
+use num_traits::Num;
+use num_traits::NumCast;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+
+use crate::common_properties::AttributeMap;
+use crate::common_properties::NormalMap;
+use crate::common_properties::RgbMap;
+use crate::common_properties::RgbaMap;
+use crate::common_properties::UvMap;
+use crate::mesh::Mesh;
+use crate::mesh_io::MAX_LIST_CAPACITY;
+use crate::mesh_io::MeshIoError;
+use crate::mesh_io::format_float;
+use crate::mesh_io::get_face_colors;
+use crate::mesh_io::get_texture_coordinates;
+use crate::mesh_io::get_vertex_colors;
+use crate::mesh_triangulation::face_triangulation::triangulate_face;
+use crate::point_cloud::PointCloud;
+use crate::property_map::FaceProperties;
+use crate::property_map::PropertyHandle;
+use crate::property_map::PropertyMap;
+use crate::property_map::PropertyStore;
+use crate::property_map::PropertyType;
+use crate::property_map::VertexProperties;
+use crate::triangle_vertex_mesh::TriangleVertexMesh;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlyFormat {
+    ASCII = 0,
+    BINARY_LITTLE_ENDIAN = 1,
+    BINARY_BIG_ENDIAN = 2
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlyScalarType {
+    CHAR = 0,
+    UCHAR = 1,
+    SHORT = 2,
+    USHORT = 3,
+    INT = 4,
+    UINT = 5,
+    FLOAT = 6,
+    DOUBLE = 7
+}
+
+impl PlyScalarType {
+
+    fn parse(name : &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(PlyScalarType::CHAR),
+            "uchar" | "uint8" => Some(PlyScalarType::UCHAR),
+            "short" | "int16" => Some(PlyScalarType::SHORT),
+            "ushort" | "uint16" => Some(PlyScalarType::USHORT),
+            "int" | "int32" => Some(PlyScalarType::INT),
+            "uint" | "uint32" => Some(PlyScalarType::UINT),
+            "float" | "float32" => Some(PlyScalarType::FLOAT),
+            "double" | "float64" => Some(PlyScalarType::DOUBLE),
+            _ => None
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PlyScalarType::CHAR => "char",
+            PlyScalarType::UCHAR => "uchar",
+            PlyScalarType::SHORT => "short",
+            PlyScalarType::USHORT => "ushort",
+            PlyScalarType::INT => "int",
+            PlyScalarType::UINT => "uint",
+            PlyScalarType::FLOAT => "float",
+            PlyScalarType::DOUBLE => "double",
+        }
+    }
+
+    fn is_integer(&self) -> bool {
+        return !matches!(self, PlyScalarType::FLOAT | PlyScalarType::DOUBLE);
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            PlyScalarType::CHAR | PlyScalarType::UCHAR => 1,
+            PlyScalarType::SHORT | PlyScalarType::USHORT => 2,
+            PlyScalarType::INT | PlyScalarType::UINT | PlyScalarType::FLOAT => 4,
+            PlyScalarType::DOUBLE => 8,
+        }
+    }
+}
+
+/// A property of each vertex or face, which is not mapped into the geometry or one of the built-in property maps.
+/// Its values are stored in a named property map of the geometry, which is described by this property. Scalar values
+/// are stored as double, which represents all PLY types exactly, and list values as a list of doubles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlyProperty {
+    pub name : String,
+    pub scalar_type : PlyScalarType,
+    /// The type of the value count for list properties, or None for scalar properties
+    pub list_count_type : Option<PlyScalarType>,
+}
+
+impl PlyProperty {
+
+    /// Handle of the named property map, into which the values of a scalar property are read
+    pub fn get_handle(&self) -> PropertyHandle<AttributeMap<f64>> {
+        return PropertyHandle::named(self.name.clone());
+    }
+
+    /// Handle of the named property map, into which the values of a list property are read
+    pub fn get_list_handle(&self) -> PropertyHandle<AttributeMap<Vec<f64>>> {
+        return PropertyHandle::named(self.name.clone());
+    }
+
+    /// Adds the property map with the values of the given rows of the column. Float values are blended and integer values,
+    /// e.g. labels, are voted on, when elements are made of several old ones. Lists are copied from the dominant element.
+    fn add_map(&self, store : &mut PropertyStore, column : &PlyColumn, rows : &[usize]) {
+        match column {
+            PlyColumn::Scalar(values) => {
+                let map = AttributeMap::from(rows.iter().map(|row| values[*row]).collect());
+                let map = match self.scalar_type.is_integer() {
+                    true => map.with_voting(),
+                    false => map.with_blending(),
+                };
+                store.add_named_property_map(&self.get_handle(), map);
+            }
+            PlyColumn::List(lists) => {
+                store.add_named_property_map(&self.get_list_handle(), AttributeMap::from(rows.iter().map(|row| lists[*row].clone()).collect()));
+            }
+        }
+    }
+
+    /// The current values of the named property map, if the store has one for all elements
+    fn get_values<'a>(&self, store : &'a PropertyStore, count : usize) -> Option<PlyValues<'a>> {
+        return match self.list_count_type {
+            None => store.get_named_property_map_ref::<AttributeMap<f64>, f64>(&self.get_handle())
+                .filter(|map| map.len() == count).map(|map| PlyValues::Scalar(map.as_slice())),
+            Some(_) => store.get_named_property_map_ref::<AttributeMap<Vec<f64>>, Vec<f64>>(&self.get_list_handle())
+                .filter(|map| map.len() == count).map(|map| PlyValues::List(map.as_slice())),
+        };
+    }
+}
+
+/// Values of a property, which are written from its property map
+#[derive(Clone)]
+enum PlyValues<'a> {
+    Scalar(&'a [f64]),
+    List(&'a [Vec<f64>]),
+}
+
+/// The result of reading a PLY file. Depending on the reader, the geometry is either a mesh or a point cloud.
+pub struct PlyData<G> {
+    pub geometry : G,
+    pub vertex_properties : Vec<PlyProperty>,
+    pub face_properties : Vec<PlyProperty>,
+    pub comments : Vec<String>,
+}
+
+struct PlyPropertyHeader {
+    name : String,
+    scalar_type : PlyScalarType,
+    list_count_type : Option<PlyScalarType>,
+}
+
+struct PlyElementHeader {
+    name : String,
+    count : usize,
+    properties : Vec<PlyPropertyHeader>,
+}
+
+struct PlyHeader {
+    format : PlyFormat,
+    elements : Vec<PlyElementHeader>,
+    comments : Vec<String>,
+    line_count : usize,
+}
+
+enum PlyColumn {
+    Scalar(Vec<f64>),
+    List(Vec<Vec<f64>>),
+}
+
+struct PlyElement {
+    header : PlyElementHeader,
+    columns : Vec<PlyColumn>,
+}
+
+impl PlyElement {
+
+    fn scalar_column(&self, name : &str) -> Option<&Vec<f64>> {
+        for (property, column) in self.header.properties.iter().zip(self.columns.iter()) {
+            if let PlyColumn::Scalar(values) = column
+                && property.name == name {
+                return Some(values);
+            }
+        }
+        return None;
+    }
+
+    /// Returns the properties, which are not mapped into the geometry or the built-in property maps, with their values
+    fn kept_properties(&self, mapped : &[&str]) -> Vec<(PlyProperty, &PlyColumn)> {
+
+        let mut kept = Vec::<(PlyProperty, &PlyColumn)>::new();
+        for (property, column) in self.header.properties.iter().zip(self.columns.iter()) {
+            if !mapped.contains(&property.name.as_str()) {
+                kept.push((PlyProperty { name: property.name.clone(), scalar_type: property.scalar_type, list_count_type: property.list_count_type }, column));
+            }
+        }
+        return kept;
+    }
+}
+
+fn read_header<R : BufRead>(reader : &mut R) -> Result<PlyHeader, MeshIoError> {
+
+    let mut header = PlyHeader {
+        format: PlyFormat::ASCII,
+        elements: Vec::<PlyElementHeader>::new(),
+        comments: Vec::<String>::new(),
+        line_count: 0,
+    };
+
+    let mut has_format = false;
+    let mut line = String::new();
+
+    loop {
+
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(MeshIoError::parse(header.line_count, "Missing 'end_header'"));
+        }
+        header.line_count += 1;
+        let line_nr = header.line_count;
+
+        let content = line.trim();
+        if line_nr == 1 {
+            if content != "ply" {
+                return Err(MeshIoError::parse(line_nr, "Not a PLY file, expected 'ply'"));
+            }
+            continue;
+        }
+
+        let mut tokens = content.split_whitespace();
+        match tokens.next() {
+            Some("format") => {
+                header.format = match tokens.next() {
+                    Some("ascii") => PlyFormat::ASCII,
+                    Some("binary_little_endian") => PlyFormat::BINARY_LITTLE_ENDIAN,
+                    Some("binary_big_endian") => PlyFormat::BINARY_BIG_ENDIAN,
+                    _ => return Err(MeshIoError::parse(line_nr, "Unknown format")),
+                };
+                has_format = true;
+            }
+            Some("comment") | Some("obj_info") => {
+                let text = content.split_once(char::is_whitespace).map(|(_, rest)| rest.trim()).unwrap_or("");
+                header.comments.push(String::from(text));
+            }
+            Some("element") => {
+                let name = tokens.next().ok_or_else(|| MeshIoError::parse(line_nr, "Missing element name"))?;
+                let count = tokens.next().and_then(|c| c.parse::<usize>().ok())
+                    .ok_or_else(|| MeshIoError::parse(line_nr, "Missing or invalid element count"))?;
+                header.elements.push(PlyElementHeader { name: String::from(name), count, properties: Vec::<PlyPropertyHeader>::new() });
+            }
+            Some("property") => {
+                let element = header.elements.last_mut().ok_or_else(|| MeshIoError::parse(line_nr, "Property without element"))?;
+                let first = tokens.next().ok_or_else(|| MeshIoError::parse(line_nr, "Missing property type"))?;
+
+                let property = if first == "list" {
+                    let count_type = tokens.next().and_then(PlyScalarType::parse)
+                        .ok_or_else(|| MeshIoError::parse(line_nr, "Invalid list count type"))?;
+                    let scalar_type = tokens.next().and_then(PlyScalarType::parse)
+                        .ok_or_else(|| MeshIoError::parse(line_nr, "Invalid list value type"))?;
+                    let name = tokens.next().ok_or_else(|| MeshIoError::parse(line_nr, "Missing property name"))?;
+                    PlyPropertyHeader { name: String::from(name), scalar_type, list_count_type: Some(count_type) }
+                } else {
+                    let scalar_type = PlyScalarType::parse(first)
+                        .ok_or_else(|| MeshIoError::parse(line_nr, format!("Unknown property type '{}'", first)))?;
+                    let name = tokens.next().ok_or_else(|| MeshIoError::parse(line_nr, "Missing property name"))?;
+                    PlyPropertyHeader { name: String::from(name), scalar_type, list_count_type: None }
+                };
+                element.properties.push(property);
+            }
+            Some("end_header") => break,
+            None => {}
+            Some(keyword) => return Err(MeshIoError::parse(line_nr, format!("Unknown header keyword '{}'", keyword))),
+        }
+    }
+
+    if !has_format {
+        return Err(MeshIoError::parse(header.line_count, "Missing format declaration"));
+    }
+
+    return Ok(header);
+}
+
+trait PlyValueSource {
+    fn next_value(&mut self, scalar_type : PlyScalarType) -> Result<f64, MeshIoError>;
+}
+
+struct PlyAsciiSource<R : BufRead> {
+    reader : R,
+    tokens : VecDeque<String>,
+    line : usize,
+}
+
+impl<R : BufRead> PlyValueSource for PlyAsciiSource<R> {
+
+    fn next_value(&mut self, _scalar_type : PlyScalarType) -> Result<f64, MeshIoError> {
+
+        while self.tokens.is_empty() {
+
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(MeshIoError::parse(self.line, "Unexpected end of file"));
+            }
+            self.line += 1;
+            self.tokens.extend(line.split_whitespace().map(String::from));
+        }
+
+        let token = self.tokens.pop_front().unwrap();
+        return token.parse::<f64>().map_err(|_| MeshIoError::parse(self.line, format!("Invalid number '{}'", token)));
+    }
+}
+
+struct PlyBinarySource<R : Read> {
+    reader : R,
+    big_endian : bool,
+}
+
+impl<R : Read> PlyValueSource for PlyBinarySource<R> {
+
+    fn next_value(&mut self, scalar_type : PlyScalarType) -> Result<f64, MeshIoError> {
+
+        let mut buffer = [0_u8; 8];
+        let size = scalar_type.size();
+        self.reader.read_exact(&mut buffer[..size])?;
+
+        if self.big_endian {
+            buffer[..size].reverse();
+        }
+
+        let value = match scalar_type {
+            PlyScalarType::CHAR => i8::from_le_bytes([buffer[0]]) as f64,
+            PlyScalarType::UCHAR => buffer[0] as f64,
+            PlyScalarType::SHORT => i16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+            PlyScalarType::USHORT => u16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+            PlyScalarType::INT => i32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+            PlyScalarType::UINT => u32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+            PlyScalarType::FLOAT => f32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+            PlyScalarType::DOUBLE => f64::from_le_bytes(buffer),
+        };
+        return Ok(value);
+    }
+}
+
+fn read_elements<S : PlyValueSource>(source : &mut S, headers : Vec<PlyElementHeader>) -> Result<Vec<PlyElement>, MeshIoError> {
+
+    let mut elements = Vec::<PlyElement>::with_capacity(headers.len());
+
+    for header in headers {
+
+        //Counts come from the file, so the columns grow while reading instead of reserving memory for them up front
+        let mut columns = header.properties.iter().map(|p| match p.list_count_type {
+            Some(_) => PlyColumn::List(Vec::<Vec<f64>>::new()),
+            None => PlyColumn::Scalar(Vec::<f64>::new()),
+        }).collect::<Vec<PlyColumn>>();
+
+        //Elements without properties take no space in the file
+        let count = if header.properties.is_empty() { 0 } else { header.count };
+        for _ in 0..count {
+            for (property, column) in header.properties.iter().zip(columns.iter_mut()) {
+                match column {
+                    PlyColumn::Scalar(values) => values.push(source.next_value(property.scalar_type)?),
+                    PlyColumn::List(lists) => {
+                        let count = source.next_value(property.list_count_type.unwrap())? as usize;
+                        let mut list = Vec::<f64>::with_capacity(count.min(MAX_LIST_CAPACITY));
+                        for _ in 0..count {
+                            list.push(source.next_value(property.scalar_type)?);
+                        }
+                        lists.push(list);
+                    }
+                }
+            }
+        }
+
+        elements.push(PlyElement { header, columns });
+    }
+
+    return Ok(elements);
+}
+
+fn read_file<R : BufRead>(mut reader : R) -> Result<(Vec<PlyElement>, Vec<String>), MeshIoError> {
+
+    let header = read_header(&mut reader)?;
+
+    let elements = match header.format {
+        PlyFormat::ASCII => {
+            let mut source = PlyAsciiSource { reader, tokens: VecDeque::<String>::new(), line: header.line_count };
+            read_elements(&mut source, header.elements)?
+        }
+        PlyFormat::BINARY_LITTLE_ENDIAN | PlyFormat::BINARY_BIG_ENDIAN => {
+            let mut source = PlyBinarySource { reader, big_endian: header.format == PlyFormat::BINARY_BIG_ENDIAN };
+            read_elements(&mut source, header.elements)?
+        }
+    };
+
+    return Ok((elements, header.comments));
+}
+
+const COLOR_PROPERTIES : [&str; 4] = ["red", "green", "blue", "alpha"];
+const TEXTURE_COORDINATE_PROPERTIES : [[&str; 2]; 3] = [["s", "t"], ["u", "v"], ["texture_u", "texture_v"]];
+
+/// Colors of vertices or faces as RGBA, together with a flag whether the alpha channel is present
+type PlyColors<U> = (Vec<[U; 4]>, bool);
+
+/// Positions and the properties of the vertices, which are mapped into property maps
+struct PlyVertexData<'a, T, U> {
+    positions : Vec<T>,
+    normals : Option<Vec<T>>,
+    colors : Option<PlyColors<U>>,
+    texture_coordinates : Option<Vec<[U; 2]>>,
+    kept_properties : Vec<(PlyProperty, &'a PlyColumn)>,
+}
+
+/// Vertex indices of the faces and the properties of the faces, which are mapped into property maps
+struct PlyFaceData<'a, U> {
+    faces : Vec<Vec<usize>>,
+    colors : Option<PlyColors<U>>,
+    kept_properties : Vec<(PlyProperty, &'a PlyColumn)>,
+}
+
+/// Reads red, green, blue and the optional alpha channel. Integer colors are scaled to the range from 0 to 1.
+/// Returns the names of the mapped properties as well.
+fn extract_colors<U : Num + PartialOrd<U> + Float>(element : &PlyElement) -> Option<(PlyColors<U>, Vec<&'static str>)> {
+
+    let mut channels = Vec::<(Vec<f64>, f64)>::with_capacity(4);
+    for name in COLOR_PROPERTIES {
+
+        let property = element.header.properties.iter().find(|p| p.name == name && p.list_count_type.is_none());
+        match (property, element.scalar_column(name)) {
+            (Some(property), Some(values)) => {
+                let scale = match property.scalar_type {
+                    PlyScalarType::UCHAR => u8::MAX as f64,
+                    PlyScalarType::USHORT => u16::MAX as f64,
+                    PlyScalarType::UINT => u32::MAX as f64,
+                    _ => 1.0,
+                };
+                channels.push((values.clone(), scale));
+            }
+            _ => break,
+        }
+    }
+
+    if channels.len() < 3 {
+        return None;
+    }
+
+    let has_alpha = channels.len() == 4;
+    let count = channels[0].0.len();
+    let mut colors = Vec::<[U; 4]>::with_capacity(count);
+    for i in 0..count {
+        let mut color = [U::one(); 4];
+        for (c, (values, scale)) in channels.iter().enumerate() {
+            color[c] = NumCast::from(values[i] / scale).unwrap();
+        }
+        colors.push(color);
+    }
+
+    return Some(((colors, has_alpha), COLOR_PROPERTIES[0..channels.len()].to_vec()));
+}
+
+fn extract_vertices<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(element : &PlyElement) -> Result<PlyVertexData<'_, T, U>, MeshIoError> {
+
+    let make_vectors = |names : [&str; 3]| -> Option<Vec<T>> {
+
+        let x = element.scalar_column(names[0])?;
+        let y = element.scalar_column(names[1])?;
+        let z = element.scalar_column(names[2])?;
+
+        let mut vectors = Vec::<T>::with_capacity(x.len());
+        for i in 0..x.len() {
+            vectors.push(T::new(NumCast::from(x[i]).unwrap(), NumCast::from(y[i]).unwrap(), NumCast::from(z[i]).unwrap()));
+        }
+        return Some(vectors);
+    };
+
+    let mut mapped = Vec::<&str>::from(["x", "y", "z"]);
+
+    let positions = make_vectors(["x", "y", "z"])
+        .ok_or_else(|| MeshIoError::InvalidData(String::from("Vertices need the scalar properties x, y and z")))?;
+
+    let normals = make_vectors(["nx", "ny", "nz"]);
+    if normals.is_some() {
+        mapped.extend(["nx", "ny", "nz"]);
+    }
+
+    let colors = extract_colors(element).map(|(colors, names)| {
+        mapped.extend(names);
+        return colors;
+    });
+
+    let mut texture_coordinates = None;
+    for [u_name, v_name] in TEXTURE_COORDINATE_PROPERTIES {
+        if let (Some(u), Some(v)) = (element.scalar_column(u_name), element.scalar_column(v_name)) {
+            texture_coordinates = Some(u.iter().zip(v.iter()).map(|(u, v)| [NumCast::from(*u).unwrap(), NumCast::from(*v).unwrap()]).collect());
+            mapped.extend([u_name, v_name]);
+            break;
+        }
+    }
+
+    return Ok(PlyVertexData { positions, normals, colors, texture_coordinates, kept_properties: element.kept_properties(&mapped) });
+}
+
+fn extract_faces<U : Num + PartialOrd<U> + Float>(element : &PlyElement, vertex_count : usize) -> Result<PlyFaceData<'_, U>, MeshIoError> {
+    let mut list_name = "";
+    let mut lists : Option<&Vec<Vec<f64>>> = None;
+
+    for (property, column) in element.header.properties.iter().zip(element.columns.iter()) {
+        if let PlyColumn::List(values) = column
+            && (property.name == "vertex_indices" || property.name == "vertex_index") {
+            list_name = &property.name;
+            lists = Some(values);
+        }
+    }
+
+    let lists = lists.ok_or_else(|| MeshIoError::InvalidData(String::from("Faces need a list property vertex_indices")))?;
+
+    let mut faces = Vec::<Vec<usize>>::with_capacity(lists.len());
+    for list in lists {
+
+        if list.len() < 3 {
+            return Err(MeshIoError::InvalidData(String::from("A face needs at least three vertices")));
+        }
+
+        let mut face = Vec::<usize>::with_capacity(list.len());
+        for value in list {
+            if *value < 0.0 || *value as usize >= vertex_count {
+                return Err(MeshIoError::InvalidData(format!("Vertex index {} out of range", value)));
+            }
+            face.push(*value as usize);
+        }
+        faces.push(face);
+    }
+
+    let mut mapped = Vec::<&str>::from([list_name]);
+    let colors = extract_colors(element).map(|(colors, names)| {
+        mapped.extend(names);
+        return colors;
+    });
+
+    return Ok(PlyFaceData { faces, colors, kept_properties: element.kept_properties(&mapped) });
+}
+
+fn attach_vertex_properties<G : VertexProperties, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static>(
+    geometry : &mut G, normals : Option<Vec<T>>, colors : Option<PlyColors<U>>, texture_coordinates : Option<Vec<[U; 2]>>)
+{
+    if let Some(normals) = normals {
+
+        let mut normal_map = NormalMap::<T, U>::with_capacity(normals.len());
+        for n in normals {
+            normal_map.push(n);
+        }
+        geometry.add_vertex_property(normal_map);
+    }
+
+    match colors {
+        Some((colors, true)) => { geometry.add_vertex_property(RgbaMap::from(colors)); }
+        Some((colors, false)) => { geometry.add_vertex_property(RgbMap::from(colors.iter().map(|c| [c[0], c[1], c[2]]).collect())); }
+        None => {}
+    }
+
+    if let Some(texture_coordinates) = texture_coordinates {
+        geometry.add_vertex_property(UvMap::from(texture_coordinates));
+    }
+}
+
+/// Reads a PLY file in any of its encodings into a Triangle-Vertex Mesh. Faces with more than three vertices
+/// are triangulated, and the face colors and kept face properties are repeated for each of the resulting triangles.
+///
+/// # Examples
+/// ```
+/// let file = File::open("scan.ply")?;
+/// let ply = ply::read_triangle_mesh::<_, Vec3f, f32, u32>(BufReader::new(file))?;
+///
+/// let mesh = ply.geometry;
+/// ```
+pub fn read_triangle_mesh<R : BufRead, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(reader : R)
+    -> Result<PlyData<TriangleVertexMesh<T, U, IndexType>>, MeshIoError>
+{
+    let (elements, comments) = read_file(reader)?;
+
+    let vertex_element = elements.iter().find(|e| e.header.name == "vertex")
+        .ok_or_else(|| MeshIoError::InvalidData(String::from("Missing vertex element")))?;
+    let vertex_data = extract_vertices::<T, U>(vertex_element)?;
+
+    let mut indices = Vec::<IndexType>::new();
+    let mut face_colors : Option<PlyColors<U>> = None;
+    let mut face_properties = Vec::<(PlyProperty, &PlyColumn)>::new();
+    //The face of each triangle, from which its colors and kept properties are taken
+    let mut triangle_faces = Vec::<usize>::new();
+
+    if let Some(face_element) = elements.iter().find(|e| e.header.name == "face") {
+
+        let PlyFaceData { faces, colors, kept_properties } = extract_faces::<U>(face_element, vertex_data.positions.len())?;
+        face_properties = kept_properties;
+
+        for (face_idx, face) in faces.iter().enumerate() {
+
+            let mut face_indices = Vec::<IndexType>::with_capacity(face.len());
+            for idx in face {
+                face_indices.push(NumCast::from(*idx)
+                    .ok_or_else(|| MeshIoError::InvalidData(format!("Vertex index {} does not fit into the index type", idx)))?);
+            }
+
+            let triangles = triangulate_face(&vertex_data.positions, &face_indices);
+            triangle_faces.extend(std::iter::repeat_n(face_idx, triangles.len() / 3));
+            indices.extend(triangles);
+        }
+
+        face_colors = colors.map(|(colors, has_alpha)| (triangle_faces.iter().map(|f| colors[*f]).collect(), has_alpha));
+    }
+
+    let mut mesh = TriangleVertexMesh::from(vertex_data.positions, indices).unwrap();
+    attach_vertex_properties::<_, T, U>(&mut mesh, vertex_data.normals, vertex_data.colors, vertex_data.texture_coordinates);
+
+    match face_colors {
+        Some((colors, true)) => { mesh.add_face_property(RgbaMap::from(colors)); }
+        Some((colors, false)) => { mesh.add_face_property(RgbMap::from(colors.iter().map(|c| [c[0], c[1], c[2]]).collect())); }
+        None => {}
+    }
+
+    let vertex_rows = (0..mesh.get_vertices().len()).collect::<Vec<usize>>();
+    for (property, column) in &vertex_data.kept_properties {
+        property.add_map(mesh.get_vertex_properties_mut(), column, &vertex_rows);
+    }
+    for (property, column) in &face_properties {
+        property.add_map(mesh.get_face_properties_mut(), column, &triangle_faces);
+    }
+
+    return Ok(PlyData {
+        geometry: mesh,
+        vertex_properties: vertex_data.kept_properties.into_iter().map(|(property, _)| property).collect(),
+        face_properties: face_properties.into_iter().map(|(property, _)| property).collect(),
+        comments
+    });
+}
+
+/// Reads the vertices of a PLY file in any of its encodings into a point cloud. Faces are ignored.
+///
+/// # Examples
+/// ```
+/// let file = File::open("scan.ply")?;
+/// let ply = ply::read_point_cloud::<_, Vec3f, f32>(BufReader::new(file))?;
+/// ```
+pub fn read_point_cloud<R : BufRead, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static>(reader : R)
+    -> Result<PlyData<PointCloud<T, U>>, MeshIoError>
+{
+    let (elements, comments) = read_file(reader)?;
+
+    let vertex_element = elements.iter().find(|e| e.header.name == "vertex")
+        .ok_or_else(|| MeshIoError::InvalidData(String::from("Missing vertex element")))?;
+    let vertex_data = extract_vertices::<T, U>(vertex_element)?;
+
+    let mut cloud = PointCloud::from(vertex_data.positions);
+    attach_vertex_properties::<_, T, U>(&mut cloud, vertex_data.normals, vertex_data.colors, vertex_data.texture_coordinates);
+    let vertex_rows = (0..cloud.get_points().len()).collect::<Vec<usize>>();
+    for (property, column) in &vertex_data.kept_properties {
+        property.add_map(cloud.get_vertex_properties_mut(), column, &vertex_rows);
+    }
+
+    let vertex_properties = vertex_data.kept_properties.into_iter().map(|(property, _)| property).collect();
+    return Ok(PlyData { geometry: cloud, vertex_properties, face_properties: Vec::<PlyProperty>::new(), comments });
+}
+
+fn write_value<W : Write>(writer : &mut W, format : PlyFormat, scalar_type : PlyScalarType, value : f64) -> Result<(), MeshIoError> {
+
+    if format == PlyFormat::ASCII {
+        match scalar_type {
+            PlyScalarType::FLOAT => write!(writer, "{}", format_float(value as f32))?,
+            PlyScalarType::DOUBLE => write!(writer, "{}", format_float(value))?,
+            _ => write!(writer, "{}", value as i64)?,
+        }
+        return Ok(());
+    }
+
+    let mut buffer = match scalar_type {
+        PlyScalarType::CHAR => Vec::from((value as i8).to_le_bytes()),
+        PlyScalarType::UCHAR => Vec::from((value as u8).to_le_bytes()),
+        PlyScalarType::SHORT => Vec::from((value as i16).to_le_bytes()),
+        PlyScalarType::USHORT => Vec::from((value as u16).to_le_bytes()),
+        PlyScalarType::INT => Vec::from((value as i32).to_le_bytes()),
+        PlyScalarType::UINT => Vec::from((value as u32).to_le_bytes()),
+        PlyScalarType::FLOAT => Vec::from((value as f32).to_le_bytes()),
+        PlyScalarType::DOUBLE => Vec::from(value.to_le_bytes()),
+    };
+
+    if format == PlyFormat::BINARY_BIG_ENDIAN {
+        buffer.reverse();
+    }
+
+    writer.write_all(&buffer)?;
+    return Ok(());
+}
+
+fn write_separator<W : Write>(writer : &mut W, format : PlyFormat, last : bool) -> Result<(), MeshIoError> {
+
+    if format == PlyFormat::ASCII {
+        if last { writeln!(writer)?; } else { write!(writer, " ")?; }
+    }
+    return Ok(());
+}
+
+/// Attributes of the geometry, which are written from its property maps
+struct PlyAttributes<'a, T : Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> {
+    normals : Option<&'a NormalMap<T, U>>,
+    vertex_colors : Option<PlyColors<U>>,
+    texture_coordinates : Option<&'a [[U; 2]]>,
+    face_colors : Option<PlyColors<U>>,
+}
+
+fn collect_vertex_attributes<G : VertexProperties, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static>(
+    geometry : &G, vertex_count : usize) -> PlyAttributes<'_, T, U>
+{
+    let has_alpha = geometry.get_vertex_property_ref::<RgbaMap<U>, [U; 4]>(PropertyType::COLOR).is_some();
+    return PlyAttributes {
+        normals: geometry.get_vertex_property_ref::<NormalMap<T, U>, T>(PropertyType::NORMAL).filter(|n| n.len() == vertex_count),
+        vertex_colors: get_vertex_colors(geometry, vertex_count).map(|colors| (colors, has_alpha)),
+        texture_coordinates: get_texture_coordinates(geometry, vertex_count),
+        face_colors: None,
+    };
+}
+
+fn collect_mesh_attributes<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    mesh : &MeshType) -> PlyAttributes<'_, T, U>
+{
+    let mut attributes = collect_vertex_attributes(mesh, mesh.get_vertices().len());
+    let has_alpha = mesh.get_face_property_ref::<RgbaMap<U>, [U; 4]>(PropertyType::COLOR).is_some();
+    attributes.face_colors = get_face_colors(mesh, mesh.get_face_count()).map(|colors| (colors, has_alpha));
+    return attributes;
+}
+
+fn color_names<U>(colors : &Option<PlyColors<U>>) -> &'static [&'static str] {
+    match colors {
+        Some((_, true)) => &COLOR_PROPERTIES,
+        Some((_, false)) => &COLOR_PROPERTIES[0..3],
+        None => &[],
+    }
+}
+
+fn push_color<U : Num + PartialOrd<U> + Float>(values : &mut Vec<(PlyScalarType, f64)>, colors : &Option<PlyColors<U>>, idx : usize) {
+    for (c, _) in color_names(colors).iter().enumerate() {
+        let value = colors.as_ref().unwrap().0[idx][c].to_f64().unwrap();
+        values.push((PlyScalarType::UCHAR, (value * u8::MAX as f64).round().clamp(0.0, u8::MAX as f64)));
+    }
+}
+
+/// Returns the type of the vertex indices, which is int as usual unless the indices do not fit into it
+fn get_index_type(vertex_count : usize) -> Result<PlyScalarType, MeshIoError> {
+
+    if vertex_count <= i32::MAX as usize + 1 {
+        return Ok(PlyScalarType::INT);
+    }
+    if vertex_count <= u32::MAX as usize + 1 {
+        return Ok(PlyScalarType::UINT);
+    }
+    return Err(MeshIoError::InvalidData(format!("{} vertices cannot be indexed in a PLY file", vertex_count)));
+}
+
+/// Returns the type of the value count of a list property, which is the original one unless a list has become too long for it
+fn get_list_count_type(property : &PlyProperty, lists : &[Vec<f64>]) -> PlyScalarType {
+
+    let count_type = property.list_count_type.unwrap_or(PlyScalarType::UCHAR);
+    let max_count = match count_type {
+        PlyScalarType::CHAR => i8::MAX as usize,
+        PlyScalarType::UCHAR => u8::MAX as usize,
+        PlyScalarType::SHORT => i16::MAX as usize,
+        PlyScalarType::USHORT => u16::MAX as usize,
+        _ => i32::MAX as usize,
+    };
+    return if lists.iter().all(|list| list.len() <= max_count) { count_type } else { PlyScalarType::INT };
+}
+
+/// Kept properties with their current values, which are written after the mapped attributes
+type PlyKeptValues<'a> = [(&'a PlyProperty, PlyValues<'a>)];
+
+fn push_kept_values(values : &mut Vec<(PlyScalarType, f64)>, properties : &PlyKeptValues, count_types : &[PlyScalarType], idx : usize) {
+
+    for ((property, property_values), count_type) in properties.iter().zip(count_types.iter()) {
+        match property_values {
+            PlyValues::Scalar(scalars) => values.push((property.scalar_type, scalars[idx])),
+            PlyValues::List(lists) => {
+                values.push((*count_type, lists[idx].len() as f64));
+                values.extend(lists[idx].iter().map(|value| (property.scalar_type, *value)));
+            }
+        }
+    }
+}
+
+fn write_property_header<W : Write>(writer : &mut W, property : &PlyProperty, count_type : PlyScalarType) -> Result<(), MeshIoError> {
+
+    match property.list_count_type {
+        Some(_) => writeln!(writer, "property list {} {} {}", count_type.name(), property.scalar_type.name(), property.name)?,
+        None => writeln!(writer, "property {} {}", property.scalar_type.name(), property.name)?,
+    }
+    return Ok(());
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_internal<W : Write, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static>(
+    writer : &mut W, format : PlyFormat, vertices : &[T], attributes : &PlyAttributes<T, U>, faces : Option<&Vec<Vec<usize>>>,
+    vertex_properties : &PlyKeptValues, face_properties : &PlyKeptValues, comments : &[String]) -> Result<(), MeshIoError>
+{
+    let vertex_count = vertices.len();
+    let face_count = faces.map(|f| f.len()).unwrap_or(0);
+
+    let coordinate_type = if mem::size_of::<U>() == mem::size_of::<f32>() { PlyScalarType::FLOAT } else { PlyScalarType::DOUBLE };
+
+    //Mapped attributes are written first, and kept properties with the same name are left out
+    let mut vertex_names = Vec::<(PlyScalarType, &str)>::from([(coordinate_type, "x"), (coordinate_type, "y"), (coordinate_type, "z")]);
+    if attributes.normals.is_some() {
+        vertex_names.extend([(coordinate_type, "nx"), (coordinate_type, "ny"), (coordinate_type, "nz")]);
+    }
+    vertex_names.extend(color_names(&attributes.vertex_colors).iter().map(|name| (PlyScalarType::UCHAR, *name)));
+    if attributes.texture_coordinates.is_some() {
+        vertex_names.extend([(coordinate_type, "s"), (coordinate_type, "t")]);
+    }
+
+    let face_names = color_names(&attributes.face_colors);
+
+    let vertex_properties = vertex_properties.iter()
+        .filter(|(p, _)| !vertex_names.iter().any(|(_, name)| *name == p.name))
+        .map(|(p, values)| (*p, values.clone())).collect::<Vec<(&PlyProperty, PlyValues)>>();
+    let face_properties = face_properties.iter()
+        .filter(|(p, _)| p.name != "vertex_indices" && !face_names.contains(&p.name.as_str()))
+        .map(|(p, values)| (*p, values.clone())).collect::<Vec<(&PlyProperty, PlyValues)>>();
+
+    let count_types = |properties : &PlyKeptValues| -> Vec<PlyScalarType> {
+        return properties.iter().map(|(p, values)| match values {
+            PlyValues::List(lists) => get_list_count_type(p, lists),
+            PlyValues::Scalar(_) => PlyScalarType::UCHAR,
+        }).collect();
+    };
+    let vertex_count_types = count_types(&vertex_properties);
+    let face_count_types = count_types(&face_properties);
+    let index_type = get_index_type(vertex_count)?;
+
+    writeln!(writer, "ply")?;
+    match format {
+        PlyFormat::ASCII => writeln!(writer, "format ascii 1.0")?,
+        PlyFormat::BINARY_LITTLE_ENDIAN => writeln!(writer, "format binary_little_endian 1.0")?,
+        PlyFormat::BINARY_BIG_ENDIAN => writeln!(writer, "format binary_big_endian 1.0")?,
+    }
+    for comment in comments {
+        writeln!(writer, "comment {}", comment)?;
+    }
+
+    writeln!(writer, "element vertex {}", vertex_count)?;
+    for (scalar_type, name) in &vertex_names {
+        writeln!(writer, "property {} {}", scalar_type.name(), name)?;
+    }
+    for ((property, _), count_type) in vertex_properties.iter().zip(vertex_count_types.iter()) {
+        write_property_header(writer, property, *count_type)?;
+    }
+
+    let count_type = if faces.map(|f| f.iter().all(|face| face.len() <= u8::MAX as usize)).unwrap_or(true) { PlyScalarType::UCHAR } else { PlyScalarType::INT };
+    if faces.is_some() {
+        writeln!(writer, "element face {}", face_count)?;
+        writeln!(writer, "property list {} {} vertex_indices", count_type.name(), index_type.name())?;
+        for name in face_names {
+            writeln!(writer, "property {} {}", PlyScalarType::UCHAR.name(), name)?;
+        }
+        for ((property, _), count_type) in face_properties.iter().zip(face_count_types.iter()) {
+            write_property_header(writer, property, *count_type)?;
+        }
+    }
+    writeln!(writer, "end_header")?;
+
+    for i in 0..vertex_count {
+
+        let mut values = Vec::<(PlyScalarType, f64)>::with_capacity(vertex_names.len() + vertex_properties.len());
+
+        let v = vertices[i];
+        values.push((coordinate_type, v.x().to_f64().unwrap()));
+        values.push((coordinate_type, v.y().to_f64().unwrap()));
+        values.push((coordinate_type, v.z().to_f64().unwrap()));
+
+        if let Some(n) = attributes.normals {
+            let normal = n.get(i);
+            values.push((coordinate_type, normal.x().to_f64().unwrap()));
+            values.push((coordinate_type, normal.y().to_f64().unwrap()));
+            values.push((coordinate_type, normal.z().to_f64().unwrap()));
+        }
+
+        push_color(&mut values, &attributes.vertex_colors, i);
+
+        if let Some(t) = attributes.texture_coordinates {
+            values.push((coordinate_type, t[i][0].to_f64().unwrap()));
+            values.push((coordinate_type, t[i][1].to_f64().unwrap()));
+        }
+
+        push_kept_values(&mut values, &vertex_properties, &vertex_count_types, i);
+
+        let last = values.len() - 1;
+        for (j, (scalar_type, value)) in values.into_iter().enumerate() {
+            write_value(writer, format, scalar_type, value)?;
+            write_separator(writer, format, j == last)?;
+        }
+    }
+
+    if let Some(faces) = faces {
+
+        for (i, face) in faces.iter().enumerate() {
+
+            write_value(writer, format, count_type, face.len() as f64)?;
+            for idx in face {
+                write_separator(writer, format, false)?;
+                write_value(writer, format, index_type, *idx as f64)?;
+            }
+
+            let mut values = Vec::<(PlyScalarType, f64)>::with_capacity(face_names.len() + face_properties.len());
+            push_color(&mut values, &attributes.face_colors, i);
+            push_kept_values(&mut values, &face_properties, &face_count_types, i);
+
+            for (scalar_type, value) in values {
+                write_separator(writer, format, false)?;
+                write_value(writer, format, scalar_type, value)?;
+            }
+            write_separator(writer, format, true)?;
+        }
+    }
+
+    return Ok(());
+}
+
+fn collect_faces<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &MeshType) -> Vec<Vec<usize>> {
+
+    let mut faces = Vec::<Vec<usize>>::with_capacity(mesh.get_face_count());
+    for f in 0..mesh.get_face_count() {
+        faces.push(mesh.get_face(NumCast::from(f).unwrap()).iter().map(|idx| idx.to_usize().unwrap()).collect());
+    }
+    return faces;
+}
+
+/// Writes any mesh as PLY file with the given encoding. Normals, colors and the first channel of the texture coordinates
+/// are written as well, if the mesh has the corresponding [NormalMap], [RgbMap] / [RgbaMap] and [UvMap] properties.
+///
+/// # Examples
+/// ```
+/// let mut file = File::create("model.ply")?;
+/// ply::write_mesh(&mut file, &mesh, PlyFormat::BINARY_LITTLE_ENDIAN)?;
+/// ```
+pub fn write_mesh<W : Write, MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, mesh : &MeshType, format : PlyFormat) -> Result<(), MeshIoError>
+{
+    return write_internal(writer, format, mesh.get_vertices(), &collect_mesh_attributes(mesh), Some(&collect_faces(mesh)), &[], &[], &[]);
+}
+
+/// Collects the current values of the kept properties from their named property maps. Properties are left out,
+/// if their map was removed or does not have a value for each element.
+fn collect_kept_values<'a>(properties : &'a [PlyProperty], store : &'a PropertyStore, count : usize) -> Vec<(&'a PlyProperty, PlyValues<'a>)> {
+    return properties.iter().filter_map(|p| p.get_values(store, count).map(|values| (p, values))).collect();
+}
+
+/// Writes a mesh together with its kept vertex and face properties and comments as PLY file. The values of the kept
+/// properties are taken from their named property maps, properties without an up to date map are left out.
+pub fn write_ply_mesh<W : Write, MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, ply : &PlyData<MeshType>, format : PlyFormat) -> Result<(), MeshIoError>
+{
+    let mesh = &ply.geometry;
+    let vertex_properties = collect_kept_values(&ply.vertex_properties, mesh.get_vertex_properties(), mesh.get_vertices().len());
+    let face_properties = collect_kept_values(&ply.face_properties, mesh.get_face_properties(), mesh.get_face_count());
+    return write_internal(writer, format, mesh.get_vertices(), &collect_mesh_attributes(mesh), Some(&collect_faces(mesh)), &vertex_properties, &face_properties, &ply.comments);
+}
+
+/// Writes a point cloud as PLY file with the given encoding
+pub fn write_point_cloud<W : Write, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static>(
+    writer : &mut W, cloud : &PointCloud<T, U>, format : PlyFormat) -> Result<(), MeshIoError>
+{
+    let attributes = collect_vertex_attributes(cloud, cloud.get_points().len());
+    return write_internal(writer, format, cloud.get_points(), &attributes, None, &[], &[], &[]);
+}
+
+/// Writes a point cloud together with its kept vertex properties and comments as PLY file, like [write_ply_mesh]
+pub fn write_ply_point_cloud<W : Write, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static>(
+    writer : &mut W, ply : &PlyData<PointCloud<T, U>>, format : PlyFormat) -> Result<(), MeshIoError>
+{
+    let cloud = &ply.geometry;
+    let attributes = collect_vertex_attributes(cloud, cloud.get_points().len());
+    let vertex_properties = collect_kept_values(&ply.vertex_properties, cloud.get_vertex_properties(), cloud.get_points().len());
+    return write_internal(writer, format, cloud.get_points(), &attributes, None, &vertex_properties, &[], &ply.comments);
+}
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::common_properties::AttributeMap;
+    use crate::common_properties::NormalMap;
+    use crate::common_properties::RgbMap;
+    use crate::mesh::Mesh;
+    use crate::mesh_io::MeshIoError;
+    use crate::mesh_io::ply;
+    use crate::mesh_io::ply::PlyFormat;
+    use crate::mesh_io::ply::PlyScalarType;
+    use crate::property_map::FaceProperties;
+    use crate::property_map::PropertyHandle;
+    use crate::property_map::PropertyInterpolation;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
+    use crate::vector::Vec3d;
+    use crate::vector::Vec3f;
+    use crate::vector::Vec3;
+
+    const ASCII_QUAD : &str = "ply
+format ascii 1.0
+comment made by hand
+element vertex 4
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+property uchar red
+property uchar green
+property uchar blue
+property float quality
+element face 1
+property list uchar int vertex_indices
+property ushort label
+end_header
+0 0 0 0 0 1 255 0 0 0.5
+1 0 0 0 0 1 0 255 0 0.25
+1 1 0 0 0 1 0 0 255 1
+0 1 0 0 0 1 255 255 255 0
+4 0 1 2 3 7
+";
+
+    #[test]
+    fn test_read_ascii() {
+
+        let ply = ply::read_triangle_mesh::<_, Vec3f, f32, u32>(ASCII_QUAD.as_bytes()).unwrap();
+        let mut mesh = ply.geometry;
+
+        assert_eq!(mesh.get_vertices().len(), 4);
+        assert_eq!(mesh.get_face_count(), 2);
+        assert_eq!(ply.comments, Vec::from([String::from("made by hand")]));
+
+        let names = ply.vertex_properties.iter().map(|p| p.name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(names, Vec::from(["quality"]));
+
+        let colors = mesh.get_vertex_property::<RgbMap<f32>, [f32; 3]>(PropertyType::COLOR).unwrap();
+        assert_eq!(colors.get(1), [0.0, 1.0, 0.0]);
+
+        assert_eq!(ply.face_properties.len(), 1);
+
+        let quality = mesh.get_named_vertex_property_ref(&ply.vertex_properties[0].get_handle()).unwrap();
+        assert_eq!(quality.as_slice(), [0.5, 0.25, 1.0, 0.0]);
+        assert_eq!(quality.get_interpolation(), PropertyInterpolation::BLEND);
+
+        let labels = mesh.get_named_face_property_ref(&PropertyHandle::<AttributeMap<f64>>::named("label")).unwrap();
+        assert_eq!(labels.as_slice(), [7.0, 7.0]);
+        assert_eq!(labels.get_interpolation(), PropertyInterpolation::VOTE);
+
+        let normals = mesh.get_vertex_property::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+        assert_eq!(normals.get(2).z(), 1.0);
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+
+        let ply = ply::read_triangle_mesh::<_, Vec3d, f64, u32>(ASCII_QUAD.as_bytes()).unwrap();
+
+        for format in [PlyFormat::BINARY_LITTLE_ENDIAN, PlyFormat::BINARY_BIG_ENDIAN, PlyFormat::ASCII] {
+
+            let mut buffer = Vec::<u8>::new();
+            ply::write_ply_mesh(&mut buffer, &ply, format).unwrap();
+
+            let reread = ply::read_triangle_mesh::<_, Vec3d, f64, u32>(buffer.as_slice()).unwrap();
+
+            assert_eq!(reread.geometry.get_face(1), ply.geometry.get_face(1));
+            assert_eq!(reread.geometry.get_vertices()[2].y(), 1.0);
+            assert_eq!(reread.vertex_properties, ply.vertex_properties);
+            assert_eq!(reread.face_properties, ply.face_properties);
+            assert_eq!(reread.face_properties[0].scalar_type, PlyScalarType::USHORT);
+            assert!(reread.geometry.get_vertex_property_ref::<NormalMap<Vec3d, f64>, Vec3d>(PropertyType::NORMAL).is_some());
+            assert_eq!(reread.geometry.get_vertex_property_ref::<RgbMap<f64>, [f64; 3]>(PropertyType::COLOR).unwrap().get(3), [1.0, 1.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_write_current_values() {
+
+        let mut ply = ply::read_triangle_mesh::<_, Vec3d, f64, u32>(ASCII_QUAD.as_bytes()).unwrap();
+        let handle = ply.vertex_properties[0].get_handle();
+        ply.geometry.get_named_vertex_property(&handle).unwrap().set(3, 0.75);
+
+        let mut buffer = Vec::<u8>::new();
+        ply::write_ply_mesh(&mut buffer, &ply, PlyFormat::ASCII).unwrap();
+
+        let reread = ply::read_triangle_mesh::<_, Vec3d, f64, u32>(buffer.as_slice()).unwrap();
+        let quality = reread.geometry.get_named_vertex_property_ref(&handle).unwrap();
+        assert_eq!(quality.as_slice(), [0.5, 0.25, 1.0, 0.75]);
+
+        //Properties without a map are left out
+        ply.geometry.remove_vertex_property(handle.get_name());
+        let mut buffer = Vec::<u8>::new();
+        ply::write_ply_mesh(&mut buffer, &ply, PlyFormat::ASCII).unwrap();
+        assert!(ply::read_triangle_mesh::<_, Vec3d, f64, u32>(buffer.as_slice()).unwrap().vertex_properties.is_empty());
+    }
+
+    #[test]
+    fn test_list_property_roundtrip() {
+
+        let content = ASCII_QUAD.replace("property float quality\n", "property float quality\nproperty list uchar int neighbors\n")
+            .replace("0.5\n", "0.5 2 1 3\n").replace("0.25\n", "0.25 0\n").replace("255 1\n", "255 1 1 0\n").replace("255 0\n", "255 0 3 0 1 2\n");
+        let ply = ply::read_triangle_mesh::<_, Vec3d, f64, u32>(content.as_bytes()).unwrap();
+
+        let names = ply.vertex_properties.iter().map(|p| p.name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(names, Vec::from(["quality", "neighbors"]));
+        assert_eq!(ply.vertex_properties[1].list_count_type, Some(PlyScalarType::UCHAR));
+
+        let handle = ply.vertex_properties[1].get_list_handle();
+        let neighbors = ply.geometry.get_named_vertex_property_ref(&handle).unwrap();
+        assert_eq!(neighbors.get(0), Vec::from([1.0, 3.0]));
+        assert!(neighbors.get(1).is_empty());
+
+        for format in [PlyFormat::BINARY_LITTLE_ENDIAN, PlyFormat::ASCII] {
+
+            let mut buffer = Vec::<u8>::new();
+            ply::write_ply_mesh(&mut buffer, &ply, format).unwrap();
+
+            let reread = ply::read_triangle_mesh::<_, Vec3d, f64, u32>(buffer.as_slice()).unwrap();
+            assert_eq!(reread.vertex_properties, ply.vertex_properties);
+            assert_eq!(reread.geometry.get_named_vertex_property_ref(&handle).unwrap().as_slice(), neighbors.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_index_type() {
+
+        assert_eq!(ply::get_index_type(4).unwrap(), PlyScalarType::INT);
+        assert_eq!(ply::get_index_type(i32::MAX as usize + 1).unwrap(), PlyScalarType::INT);
+        assert_eq!(ply::get_index_type(i32::MAX as usize + 2).unwrap(), PlyScalarType::UINT);
+        assert!(ply::get_index_type(u32::MAX as usize + 2).is_err());
+    }
+
+    #[test]
+    fn test_large_counts() {
+
+        //The counts in the header and the lists exceed the content of the file
+        let content = ASCII_QUAD.replace("element vertex 4", "element vertex 4000000000000").replace("4 0 1 2 3 7", "4000000000 0 1 2 3 7");
+        assert!(ply::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes()).is_err());
+
+        let content = ASCII_QUAD.replace("4 0 1 2 3 7", "4000000000 0 1 2 3 7");
+        assert!(ply::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_read_point_cloud() {
+
+        let ply = ply::read_triangle_mesh::<_, Vec3f, f32, u32>(ASCII_QUAD.as_bytes()).unwrap();
+        let mut buffer = Vec::<u8>::new();
+        ply::write_mesh(&mut buffer, &ply.geometry, PlyFormat::BINARY_BIG_ENDIAN).unwrap();
+
+        let cloud = ply::read_point_cloud::<_, Vec3f, f32>(buffer.as_slice()).unwrap();
+        assert_eq!(cloud.geometry.get_points().len(), 4);
+        assert!(cloud.vertex_properties.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_index() {
+
+        let content = ASCII_QUAD.replace("4 0 1 2 3 7", "4 0 1 2 9 7");
+        let result = ply::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes());
+
+        assert!(matches!(result, Err(MeshIoError::InvalidData(_))));
+    }
+}
@@ -0,0 +1,354 @@
+//! # Stereolithography (STL)
+//!
+//! STL files store a list of unconnected triangles, each with three positions and a facet normal, either
+//! as text or in binary. Since there are no shared vertices, positions closer than a given tolerance are
+//! welded into one vertex when reading. Facet normals in the file are ignored and recalculated when writing.
+
+use std::hash::Hash;
+use std::io::BufRead;
+use std::io::Write;
+
+use num_traits::Float;
+use num_traits::Num;
+use num_traits::NumCast;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+
+use crate::mesh::Mesh;
+use crate::mesh_io::MeshIoError;
+use crate::mesh_io::format_float;
+use crate::mesh_normal_calculation::mesh_normals::calculate_face_normal;
+use crate::mesh_triangulation::face_triangulation::triangulate_face;
+use crate::spatial_hash::SpatialHash;
+use crate::triangle_vertex_mesh::TriangleVertexMesh;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StlFormat {
+    ASCII = 0,
+    BINARY = 1
+}
+
+const BINARY_HEADER_SIZE : usize = 84;
+const BINARY_TRIANGLE_SIZE : usize = 50;
+
+fn parse_ascii<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(content : &str) -> Result<Vec<T>, MeshIoError> {
+
+    let mut corners = Vec::<T>::new();
+    let mut facet_corners = 0;
+    let mut in_facet = false;
+
+    for (line_idx, line) in content.lines().enumerate() {
+
+        let line_nr = line_idx + 1;
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("facet") => {
+                if in_facet {
+                    return Err(MeshIoError::parse(line_nr, "Missing 'endfacet'"));
+                }
+                in_facet = true;
+                facet_corners = 0;
+            }
+            Some("vertex") => {
+                if !in_facet {
+                    return Err(MeshIoError::parse(line_nr, "Vertex outside of facet"));
+                }
+
+                let mut coordinates = [U::zero(); 3];
+                for c in coordinates.iter_mut() {
+                    let token = tokens.next().ok_or_else(|| MeshIoError::parse(line_nr, "Missing coordinate"))?;
+                    let value = token.parse::<f64>().map_err(|_| MeshIoError::parse(line_nr, format!("Invalid number '{}'", token)))?;
+                    *c = NumCast::from(value).ok_or_else(|| MeshIoError::parse(line_nr, format!("Number '{}' out of range", token)))?;
+                }
+
+                corners.push(T::new(coordinates[0], coordinates[1], coordinates[2]));
+                facet_corners += 1;
+            }
+            Some("endfacet") => {
+                if !in_facet || facet_corners != 3 {
+                    return Err(MeshIoError::parse(line_nr, "A facet needs exactly three vertices"));
+                }
+                in_facet = false;
+            }
+            //solid, outer loop, endloop and endsolid carry no geometry
+            _ => {}
+        }
+    }
+
+    if in_facet {
+        return Err(MeshIoError::parse(content.lines().count(), "Missing 'endfacet'"));
+    }
+
+    return Ok(corners);
+}
+
+fn parse_binary<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(content : &[u8]) -> Vec<T> {
+
+    let count = u32::from_le_bytes(content[80..84].try_into().unwrap()) as usize;
+    let mut corners = Vec::<T>::with_capacity(count * 3);
+
+    let read_f32 = |offset : usize| -> U {
+        return NumCast::from(f32::from_le_bytes(content[offset..offset + 4].try_into().unwrap())).unwrap();
+    };
+
+    for i in 0..count {
+
+        //The facet normal (12 bytes) is skipped
+        let offset = BINARY_HEADER_SIZE + i * BINARY_TRIANGLE_SIZE + 12;
+        for c in 0..3 {
+            let o = offset + c * 12;
+            corners.push(T::new(read_f32(o), read_f32(o + 4), read_f32(o + 8)));
+        }
+    }
+
+    return corners;
+}
+
+fn is_binary(content : &[u8]) -> bool {
+
+    if content.len() < BINARY_HEADER_SIZE {
+        return false;
+    }
+
+    //Many binary files start with "solid" as well, so the size is the more reliable indicator. Some exporters append
+    //trailing bytes, which are ignored. The count of a text file is made of printable characters, which would need gigabytes.
+    let count = u32::from_le_bytes(content[80..84].try_into().unwrap()) as usize;
+    return content.len() >= BINARY_HEADER_SIZE + count * BINARY_TRIANGLE_SIZE;
+}
+
+/// Welds all corners, which are at most the tolerance apart, into shared vertices and creates the index buffer.
+/// Triangles whose corners are welded together are removed.
+fn weld<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(corners : Vec<T>, tolerance : U)
+    -> Result<TriangleVertexMesh<T, U, IndexType>, MeshIoError>
+{
+    let mut hash = SpatialHash::<T, U>::new(tolerance);
+    let mut indices = Vec::<IndexType>::with_capacity(corners.len());
+
+    for triangle in corners.chunks(3) {
+
+        let mut triangle_indices = [0_usize; 3];
+        for (i, corner) in triangle.iter().enumerate() {
+            triangle_indices[i] = match hash.find_nearest(*corner, tolerance) {
+                Some(idx) => idx,
+                None => hash.insert(*corner),
+            };
+        }
+
+        if triangle_indices[0] == triangle_indices[1] || triangle_indices[1] == triangle_indices[2] || triangle_indices[0] == triangle_indices[2] {
+            continue;
+        }
+
+        for idx in triangle_indices {
+            indices.push(NumCast::from(idx)
+                .ok_or_else(|| MeshIoError::InvalidData(format!("Vertex index {} does not fit into the index type", idx)))?);
+        }
+    }
+
+    return Ok(TriangleVertexMesh::from(hash.get_points().clone(), indices).unwrap());
+}
+
+/// Reads an STL file, either ASCII or binary, into a Triangle-Vertex Mesh. Positions, which are not further apart
+/// than the tolerance, are merged into one vertex. A tolerance of zero only merges identical positions.
+///
+/// # Examples
+/// ```
+/// let file = File::open("part.stl")?;
+/// let mesh = stl::read_triangle_mesh::<_, Vec3f, f32, u32>(BufReader::new(file), 1e-5)?;
+/// ```
+pub fn read_triangle_mesh<R : BufRead, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mut reader : R, tolerance : U)
+    -> Result<TriangleVertexMesh<T, U, IndexType>, MeshIoError>
+{
+    let mut content = Vec::<u8>::new();
+    reader.read_to_end(&mut content)?;
+
+    let corners = if is_binary(&content) {
+        parse_binary::<T, U>(&content)
+    } else {
+        let text = std::str::from_utf8(&content)
+            .map_err(|_| MeshIoError::InvalidData(String::from("Neither a binary STL file nor valid text")))?;
+        if !text.trim_start().starts_with("solid") {
+            return Err(MeshIoError::parse(1, "Expected 'solid'"));
+        }
+        parse_ascii::<T, U>(text)?
+    };
+
+    return weld(corners, tolerance);
+}
+
+fn collect_triangles<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &MeshType)
+    -> Vec<[T; 3]>
+{
+    let vertices = mesh.get_vertices();
+    let mut triangles = Vec::<[T; 3]>::with_capacity(mesh.get_face_count());
+
+    for f in 0..mesh.get_face_count() {
+
+        let face = mesh.get_face(NumCast::from(f).unwrap());
+        for t in triangulate_face(vertices, &face).chunks(3) {
+            triangles.push([vertices[t[0].to_usize().unwrap()], vertices[t[1].to_usize().unwrap()], vertices[t[2].to_usize().unwrap()]]);
+        }
+    }
+
+    return triangles;
+}
+
+/// Writes any mesh as STL file with the given encoding. Faces with more than three vertices are triangulated,
+/// and facet normals are calculated from the triangles. The name is stored in the header of the file.
+///
+/// # Examples
+/// ```
+/// let mut file = File::create("part.stl")?;
+/// stl::write_mesh(&mut file, &mesh, "part", StlFormat::BINARY)?;
+/// ```
+pub fn write_mesh<W : Write, MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+    writer : &mut W, mesh : &MeshType, name : &str, format : StlFormat) -> Result<(), MeshIoError>
+{
+    let triangles = collect_triangles(mesh);
+
+    let normal_of = |triangle : &[T; 3]| -> T {
+        return calculate_face_normal(Vec::from(*triangle)).unwrap_or(T::zero());
+    };
+
+    match format {
+        StlFormat::ASCII => {
+
+            writeln!(writer, "solid {}", name)?;
+            for triangle in &triangles {
+
+                let n = normal_of(triangle);
+                writeln!(writer, "  facet normal {} {} {}", format_float(n.x()), format_float(n.y()), format_float(n.z()))?;
+                writeln!(writer, "    outer loop")?;
+                for v in triangle {
+                    writeln!(writer, "      vertex {} {} {}", format_float(v.x()), format_float(v.y()), format_float(v.z()))?;
+                }
+                writeln!(writer, "    endloop")?;
+                writeln!(writer, "  endfacet")?;
+            }
+            writeln!(writer, "endsolid {}", name)?;
+        }
+        StlFormat::BINARY => {
+
+            //The header must not start with "solid", otherwise some readers mistake the file for text
+            let mut header = [0_u8; 80];
+            let header_text = format!("binary {}", name);
+            let length = header_text.len().min(80);
+            header[..length].copy_from_slice(&header_text.as_bytes()[..length]);
+
+            let count = u32::try_from(triangles.len())
+                .map_err(|_| MeshIoError::InvalidData(String::from("Too many triangles for binary STL")))?;
+
+            writer.write_all(&header)?;
+            writer.write_all(&count.to_le_bytes())?;
+
+            let write_vector = |writer : &mut W, v : T| -> Result<(), MeshIoError> {
+                for c in [v.x(), v.y(), v.z()] {
+                    writer.write_all(&c.to_f32().unwrap().to_le_bytes())?;
+                }
+                return Ok(());
+            };
+
+            for triangle in &triangles {
+
+                write_vector(writer, normal_of(triangle))?;
+                for v in triangle {
+                    write_vector(writer, *v)?;
+                }
+                writer.write_all(&0_u16.to_le_bytes())?;
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::mesh::Mesh;
+    use crate::mesh_io::MeshIoError;
+    use crate::mesh_io::stl;
+    use crate::mesh_io::stl::StlFormat;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::Vec3f;
+    use crate::vector::Vec3;
+
+    const ASCII_SQUARE : &str = "solid square
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 1 1 0
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0.000001
+      vertex 1 1 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid square
+";
+
+    #[test]
+    fn test_read_ascii_welded() {
+
+        let mesh = stl::read_triangle_mesh::<_, Vec3f, f32, u32>(ASCII_SQUARE.as_bytes(), 1e-4).unwrap();
+
+        assert_eq!(mesh.get_vertices().len(), 4);
+        assert_eq!(mesh.get_face(1), Vec::from([0, 2, 3]));
+
+        let unwelded = stl::read_triangle_mesh::<_, Vec3f, f32, u32>(ASCII_SQUARE.as_bytes(), 0.0).unwrap();
+        assert_eq!(unwelded.get_vertices().len(), 5);
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+
+        let vertices = Vec::from([Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(0.0, 1.0, 0.0), Vec3f::new(0.0, 0.0, 1.0)]);
+        let indices = Vec::from([0, 2, 1, 0, 1, 3, 1, 2, 3, 0, 3, 2]);
+        let tetrahedron = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, indices).unwrap();
+
+        let mut buffer = Vec::<u8>::new();
+        stl::write_mesh(&mut buffer, &tetrahedron, "tetrahedron", StlFormat::BINARY).unwrap();
+        assert_eq!(buffer.len(), 84 + 4 * 50);
+
+        //The facet normal of the first triangle points downwards
+        assert_eq!(f32::from_le_bytes(buffer[92..96].try_into().unwrap()), -1.0);
+
+        let reread = stl::read_triangle_mesh::<_, Vec3f, f32, u32>(buffer.as_slice(), 0.0).unwrap();
+        assert_eq!(reread.get_vertices().len(), 4);
+        assert_eq!(reread.get_face_count(), 4);
+    }
+
+    #[test]
+    fn test_binary_trailing_bytes() {
+
+        let vertices = Vec::from([Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(0.0, 1.0, 0.0)]);
+        let triangle = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, Vec::from([0, 1, 2])).unwrap();
+
+        let mut buffer = Vec::<u8>::new();
+        stl::write_mesh(&mut buffer, &triangle, "solid triangle", StlFormat::BINARY).unwrap();
+        buffer.extend_from_slice(b"\0\0trailing");
+
+        let reread = stl::read_triangle_mesh::<_, Vec3f, f32, u32>(buffer.as_slice(), 0.0).unwrap();
+        assert_eq!(reread.get_face_count(), 1);
+        assert_eq!(reread.get_vertices()[1].x(), 1.0);
+    }
+
+    #[test]
+    fn test_ascii_error_line() {
+
+        let content = ASCII_SQUARE.replace("      vertex 1 1 0\n    endloop\n  endfacet\n  facet", "    endloop\n  endfacet\n  facet");
+        let result = stl::read_triangle_mesh::<_, Vec3f, f32, u32>(content.as_bytes(), 0.0);
+
+        match result {
+            Err(MeshIoError::Parse { line, .. }) => assert_eq!(line, 7),
+            _ => panic!("Expected a parse error"),
+        }
+    }
+}
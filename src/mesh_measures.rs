@@ -0,0 +1,228 @@
+pub mod measures {
+
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::common_properties::AttributeMap;
+    use crate::math::Point;
+    use crate::math::cross;
+    use crate::math::to_point;
+    use crate::mesh::Mesh;
+    use crate::property_map::PropertyMap;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    fn get_face_points<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType, face : usize) -> Vec<Point>
+    {
+        let vertices = mesh.get_vertices();
+        return mesh.get_face(NumCast::from(face).unwrap()).iter().map(|idx| to_point(&vertices[idx.to_usize().unwrap()])).collect();
+    }
+
+    /// Area of a face, which is exact for planar faces and the area of the projection onto the average plane otherwise
+    fn get_face_area(points : &[Point]) -> f64 {
+
+        let mut area_vector = [0.0; 3];
+        for i in 0..points.len() {
+            let product = cross(&points[i], &points[(i + 1) % points.len()]);
+            (0..3).for_each(|c| area_vector[c] += product[c]);
+        }
+        return 0.5 * (area_vector[0] * area_vector[0] + area_vector[1] * area_vector[1] + area_vector[2] * area_vector[2]).sqrt();
+    }
+
+    /// Integrals of 1, x and x x^T over the enclosed volume, summed over the signed tetrahedra between the origin and the
+    /// triangles of a fan triangulation of each face
+    struct VolumeMoments {
+        volume : f64,
+        first : Point,
+        second : [[f64; 3]; 3],
+    }
+
+    fn get_volume_moments<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType) -> VolumeMoments
+    {
+        let mut moments = VolumeMoments { volume: 0.0, first: [0.0; 3], second: [[0.0; 3]; 3] };
+
+        for f in 0..mesh.get_face_count() {
+            let points = get_face_points(mesh, f);
+            for i in 1..points.len().saturating_sub(1) {
+                let (a, b, c) = (points[0], points[i], points[i + 1]);
+                let determinant = a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0]) + a[2] * (b[0] * c[1] - b[1] * c[0]);
+                moments.volume += determinant / 6.0;
+
+                //Integrals over the tetrahedron (0, a, b, c), where the canonical one has the second moments (1 + [j == k]) / 120
+                for j in 0..3 {
+                    moments.first[j] += determinant / 24.0 * (a[j] + b[j] + c[j]);
+                    for k in 0..3 {
+                        let sum = (a[j] + b[j] + c[j]) * (a[k] + b[k] + c[k]);
+                        let diagonal = a[j] * a[k] + b[j] * b[k] + c[j] * c[k];
+                        moments.second[j][k] += determinant / 120.0 * (sum + diagonal);
+                    }
+                }
+            }
+        }
+        return moments;
+    }
+
+    /// Creates a map with the area of each face
+    ///
+    /// # Examples
+    /// ```
+    /// let areas = create_face_areas(&mesh);
+    /// mesh.add_named_face_property(&PropertyHandle::named("area"), areas);
+    /// ```
+    pub fn create_face_areas<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType) -> AttributeMap<U>
+    {
+        let mut areas = AttributeMap::<U>::with_capacity(mesh.get_face_count());
+        for f in 0..mesh.get_face_count() {
+            areas.push(U::from(get_face_area(&get_face_points(mesh, f))).unwrap());
+        }
+        return areas;
+    }
+
+    /// Calculates the total area of all faces
+    pub fn calculate_surface_area<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType) -> U
+    {
+        let area = (0..mesh.get_face_count()).map(|f| get_face_area(&get_face_points(mesh, f))).sum::<f64>();
+        return U::from(area).unwrap();
+    }
+
+    /// Calculates the volume enclosed by a closed mesh with the divergence theorem, as the sum of the signed volumes of
+    /// the tetrahedra between the origin and each face. The volume is positive for outward facing faces and negative for
+    /// inward facing ones. For open meshes, the result depends on the position of the origin.
+    ///
+    /// # Examples
+    /// ```
+    /// let volume = calculate_volume(&mesh);
+    /// let material_cost = volume.abs() * price_per_volume;
+    /// ```
+    pub fn calculate_volume<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType) -> U
+    {
+        return U::from(get_volume_moments(mesh).volume).unwrap();
+    }
+
+    /// Calculates the centroid of the volume enclosed by a closed mesh, i.e. the center of mass for uniform density.
+    /// Returns None if the mesh encloses no volume.
+    pub fn calculate_centroid<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType) -> Option<T>
+    {
+        let moments = get_volume_moments(mesh);
+        if moments.volume.abs() <= f64::EPSILON {
+            return None;
+        }
+        let centroid = moments.first.map(|x| U::from(x / moments.volume).unwrap());
+        return Some(T::new(centroid[0], centroid[1], centroid[2]));
+    }
+
+    /// Calculates the inertia tensor of the solid enclosed by a closed mesh with uniform density, relative to its centroid
+    /// and the coordinate axes. The diagonal holds the moments of inertia and the other entries the negative products of
+    /// inertia. Inward facing meshes give the same tensor as outward facing ones. Returns None if the mesh encloses no volume.
+    ///
+    /// # Examples
+    /// ```
+    /// let inertia = calculate_inertia_tensor(&mesh, density).unwrap();
+    /// let mass = calculate_volume(&mesh) * density;
+    /// ```
+    pub fn calculate_inertia_tensor<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType, density : U) -> Option<[[U; 3]; 3]>
+    {
+        let moments = get_volume_moments(mesh);
+        if moments.volume.abs() <= f64::EPSILON {
+            return None;
+        }
+
+        //Second moments relative to the centroid, with the sign of the volume removed
+        let centroid = moments.first.map(|x| x / moments.volume);
+        let sign = moments.volume.signum();
+        let mut covariance = [[0.0; 3]; 3];
+        for j in 0..3 {
+            for k in 0..3 {
+                covariance[j][k] = sign * (moments.second[j][k] - moments.volume * centroid[j] * centroid[k]);
+            }
+        }
+
+        let trace = covariance[0][0] + covariance[1][1] + covariance[2][2];
+        let density = density.to_f64().unwrap();
+        let mut inertia = [[U::zero(); 3]; 3];
+        for j in 0..3 {
+            for k in 0..3 {
+                let identity = if j == k { trace } else { 0.0 };
+                inertia[j][k] = U::from(density * (identity - covariance[j][k])).unwrap();
+            }
+        }
+        return Some(inertia);
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::fixtures::create_box;
+        use crate::mesh_measures::measures::calculate_centroid;
+        use crate::mesh_measures::measures::calculate_inertia_tensor;
+        use crate::mesh_measures::measures::calculate_surface_area;
+        use crate::mesh_measures::measures::calculate_volume;
+        use crate::mesh_measures::measures::create_face_areas;
+        use crate::property_map::PropertyMap;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::Vec3d;
+        use crate::vector::Vec3;
+
+        #[test]
+        fn test_box_measures() {
+
+            let cuboid = create_box(Vec3d::new(0.0, 0.0, 0.0), Vec3d::new(1.0, 2.0, 3.0));
+
+            assert!((calculate_surface_area(&cuboid) - 22.0).abs() < 1e-12);
+            assert_eq!(create_face_areas(&cuboid).get(0), 2.0);
+            assert!((calculate_volume(&cuboid) - 6.0).abs() < 1e-12);
+
+            let centroid = calculate_centroid(&cuboid).unwrap();
+            assert!((centroid.x() - 0.5).abs() < 1e-12 && (centroid.y() - 1.0).abs() < 1e-12 && (centroid.z() - 1.5).abs() < 1e-12);
+
+            //A cuboid with mass m has the moments of inertia m (b² + c²) / 12 around its center
+            let inertia = calculate_inertia_tensor(&cuboid, 2.0).unwrap();
+            let mass = 12.0;
+            assert!((inertia[0][0] - mass * (4.0 + 9.0) / 12.0).abs() < 1e-9);
+            assert!((inertia[1][1] - mass * (1.0 + 9.0) / 12.0).abs() < 1e-9);
+            assert!((inertia[2][2] - mass * (1.0 + 4.0) / 12.0).abs() < 1e-9);
+            assert!(inertia[0][1].abs() < 1e-9 && inertia[1][2].abs() < 1e-9 && inertia[0][2].abs() < 1e-9);
+        }
+
+        #[test]
+        fn test_tetrahedron_measures() {
+
+            //Inward facing tetrahedron, which has a negative volume but the same inertia
+            let vertices = Vec::from([
+                Vec3d::new(0.0, 0.0, 0.0), Vec3d::new(1.0, 0.0, 0.0),
+                Vec3d::new(0.0, 1.0, 0.0), Vec3d::new(0.0, 0.0, 1.0)]);
+            let outward = TriangleVertexMesh::<Vec3d, f64, u32>::from(vertices.clone(), Vec::from([0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3])).unwrap();
+            let inward = TriangleVertexMesh::<Vec3d, f64, u32>::from(vertices, Vec::from([0, 1, 2, 0, 3, 1, 0, 2, 3, 1, 3, 2])).unwrap();
+
+            assert!((calculate_volume(&outward) - 1.0 / 6.0).abs() < 1e-12);
+            assert!((calculate_volume(&inward) + 1.0 / 6.0).abs() < 1e-12);
+
+            let centroid = calculate_centroid(&inward).unwrap();
+            assert!((centroid.x() - 0.25).abs() < 1e-12);
+
+            //Known inertia of the unit tetrahedron around its centroid, for unit density
+            let inertia = calculate_inertia_tensor(&outward, 1.0).unwrap();
+            assert!((inertia[0][0] - 1.0 / 80.0).abs() < 1e-12);
+            assert!((inertia[0][1] - 1.0 / 480.0).abs() < 1e-12);
+            assert_eq!(calculate_inertia_tensor(&inward, 1.0).unwrap(), inertia);
+
+            //Flat meshes have no volume
+            let flat = TriangleVertexMesh::<Vec3d, f64, u32>::from(Vec::from([
+                Vec3d::new(0.0, 0.0, 0.0), Vec3d::new(1.0, 0.0, 0.0), Vec3d::new(0.0, 1.0, 0.0)]), Vec::from([0, 1, 2])).unwrap();
+            assert!(calculate_centroid(&flat).is_none());
+            assert!((calculate_surface_area(&flat) - 0.5).abs() < 1e-12);
+        }
+    }
+}
@@ -1,16 +1,22 @@
 
 pub mod mesh_normals {
 
+    use std::collections::HashMap;
     use std::hash::Hash;
 
     use num_traits::Num;
+    use num_traits::NumCast;
     use num_traits::PrimInt;
     use num_traits::Unsigned;
     use num_traits::Float;
 
     use crate::common_properties::NormalMap;
+    use crate::disjoint_set::DisjointSet;
     use crate::mesh_components::MeshComponent;
     use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
     use crate::vector::FloatVector;
     use crate::vector::Vec3;
     use crate::mesh::Mesh;
@@ -152,14 +158,248 @@ pub mod mesh_normals {
         return normal_map;
     }
 
+    /// Weighting of the normals of the faces around a vertex, when they are combined to a vertex normal
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum VertexNormalWeighting {
+        /// Each face has the same weight
+        UNIFORM = 0,
+        /// Faces are weighted by their area
+        AREA = 1,
+        /// Faces are weighted by the angle of their corner at the vertex (Thürmer and Wüthrich 1998)
+        ANGLE = 2,
+        /// Faces are weighted by the sine of the corner angle divided by the lengths of the adjacent edges (Max 1999),
+        /// which is exact for vertices on a sphere
+        MAX = 3,
+    }
+
+    fn get_face_vertices<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType, face : &[IndexType]) -> Vec<T>
+    {
+        let vertices = mesh.get_vertices();
+        return face.iter().map(|idx| vertices[idx.to_usize().unwrap()]).collect();
+    }
+
+    /// Weights the normal of a face for each of its corners. Corners with a zero-length edge get a zero weight.
+    fn get_weighted_corner_normals<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(
+        face_vertices : &[T], face_normal : T, weighting : VertexNormalWeighting) -> Vec<T>
+    {
+        let size = face_vertices.len();
+
+        //Twice the vector area of the face, which has the length of the area for planar faces
+        let mut area_vector = T::zero();
+        for i in 0..size {
+            area_vector += T::cross(face_vertices[i], face_vertices[(i + 1) % size]);
+        }
+        let area = area_vector.length() * U::from(0.5).unwrap();
+
+        let mut corner_normals = Vec::<T>::with_capacity(size);
+        for i in 0..size {
+
+            let vertex = face_vertices[i];
+            let next_edge = face_vertices[(i + 1) % size] - vertex;
+            let prev_edge = face_vertices[(i + size - 1) % size] - vertex;
+
+            let next_length = next_edge.length();
+            let prev_length = prev_edge.length();
+            if next_length == U::zero() || prev_length == U::zero() {
+                corner_normals.push(T::zero());
+                continue;
+            }
+
+            corner_normals.push(match weighting {
+                VertexNormalWeighting::UNIFORM => face_normal,
+                VertexNormalWeighting::AREA => face_normal * area,
+                VertexNormalWeighting::ANGLE => face_normal * T::angle(&next_edge, &prev_edge),
+                VertexNormalWeighting::MAX => T::cross(next_edge, prev_edge) * (U::one() / (next_length * next_length * prev_length * prev_length)),
+            });
+        }
+        return corner_normals;
+    }
+
+    /// Creates a normal for each face of the mesh, using [calculate_face_normal]. Degenerate faces get a zero normal.
+    ///
+    /// # Examples
+    /// ```
+    /// let normals = create_face_normals(&mesh);
+    /// mesh.add_face_property(normals);
+    /// ```
+    pub fn create_face_normals<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(mesh : &MeshType)
+        -> NormalMap<T, U>
+    {
+        let face_count = mesh.get_face_count();
+        let mut normal_map = NormalMap::<T, U>::with_capacity(face_count);
+
+        for f in 0..face_count {
+            let face = mesh.get_face(NumCast::from(f).unwrap());
+            normal_map.push(calculate_face_normal(get_face_vertices(mesh, &face)).unwrap_or(T::zero()));
+        }
+
+        return normal_map;
+    }
+
+    /// Creates normals for vertices by combining the normals of the incident faces with the given weighting.
+    /// Vertices without faces or with cancelling face normals get a zero normal.
+    ///
+    /// # Examples
+    /// ```
+    /// let normals = create_vertex_normals(&mesh, VertexNormalWeighting::AREA);
+    /// ```
+    pub fn create_vertex_normals<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &MeshType, weighting : VertexNormalWeighting) -> NormalMap<T, U>
+    {
+        let vertices = mesh.get_vertices();
+        let mut sums = vec![T::zero(); vertices.len()];
+
+        for f in 0..mesh.get_face_count() {
+
+            let face = mesh.get_face(NumCast::from(f).unwrap());
+            let face_vertices = get_face_vertices(mesh, &face);
+            let face_normal = match calculate_face_normal(face_vertices.clone()) {
+                Some(n) => n,
+                None => continue,
+            };
+
+            for (i, weighted_normal) in get_weighted_corner_normals(&face_vertices, face_normal, weighting).into_iter().enumerate() {
+                sums[face[i].to_usize().unwrap()] += weighted_normal;
+            }
+        }
+
+        let mut normal_map = NormalMap::<T, U>::with_capacity(vertices.len());
+        for sum in sums {
+            normal_map.push(sum.normalize().unwrap_or(T::zero()));
+        }
+        return normal_map;
+    }
+
+    /// Calculates the face normals and stores them in the face properties of the mesh, replacing existing ones
+    pub fn add_face_normals<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(mesh : &mut MeshType) {
+
+        let normals = create_face_normals(mesh);
+        match mesh.get_face_property::<NormalMap<T, U>, T>(PropertyType::NORMAL) {
+            Some(existing) => *existing = normals,
+            None => { mesh.add_face_property(normals); }
+        }
+    }
+
+    /// Calculates the vertex normals and stores them in the vertex properties of the mesh, replacing existing ones
+    ///
+    /// # Examples
+    /// ```
+    /// add_vertex_normals(&mut mesh, VertexNormalWeighting::ANGLE);
+    /// obj::write_mesh(&mut file, &mesh)?;
+    /// ```
+    pub fn add_vertex_normals<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut MeshType, weighting : VertexNormalWeighting)
+    {
+        let normals = create_vertex_normals(mesh, weighting);
+        match mesh.get_vertex_property::<NormalMap<T, U>, T>(PropertyType::NORMAL) {
+            Some(existing) => *existing = normals,
+            None => { mesh.add_vertex_property(normals); }
+        }
+    }
+
+    /// Creates a copy of the mesh with hard edges, where the angle between the normals of neighbouring triangles exceeds the
+    /// crease angle (in radians). Vertices on hard edges are split into one vertex per smooth fan of triangles around them,
+    /// which receives the combined normal of that fan with the given weighting. The normals are stored in the vertex
+    /// properties of the new mesh, other vertex properties are copied from the original vertices.
+    /// Degenerate triangles never form a hard edge. Returns None, if the split vertices exceed the index type.
+    ///
+    /// # Examples
+    /// ```
+    /// let cube = obj::read_triangle_mesh(reader)?.mesh;
+    ///
+    /// //Splits the cube into 24 vertices, with the normals of the sides
+    /// let hard_cube = split_normals_at_creases(&cube, std::f32::consts::FRAC_PI_4, VertexNormalWeighting::ANGLE).unwrap();
+    /// ```
+    pub fn split_normals_at_creases<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>, crease_angle : U, weighting : VertexNormalWeighting) -> Option<TriangleVertexMesh<T, U, IndexType>>
+    {
+        let vertices = mesh.get_vertices();
+        let triangles = mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect::<Vec<[usize; 3]>>();
+
+        let face_normals = triangles.iter()
+            .map(|t| calculate_face_normal(Vec::from([vertices[t[0]], vertices[t[1]], vertices[t[2]]])))
+            .collect::<Vec<Option<T>>>();
+
+        let mut edge_corners = HashMap::<(usize, usize), Vec<(usize, usize)>>::new();
+        for (f, triangle) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                let a = triangle[i];
+                let b = triangle[(i + 1) % 3];
+                if a != b {
+                    //The corners of the triangle at the lower and the higher vertex of the edge
+                    let (low, high) = if a < b { (i, (i + 1) % 3) } else { ((i + 1) % 3, i) };
+                    edge_corners.entry((a.min(b), a.max(b))).or_default().push((f * 3 + low, f * 3 + high));
+                }
+            }
+        }
+
+        //Corners belong to the same fan, if their triangles are connected through smooth edges around the vertex
+        let mut fans = DisjointSet::new(triangles.len() * 3);
+        for corners in edge_corners.values() {
+            for (i, first) in corners.iter().enumerate() {
+                for second in &corners[i + 1..] {
+
+                    let smooth = match (face_normals[first.0 / 3], face_normals[second.0 / 3]) {
+                        (Some(n), Some(m)) => T::angle(&n, &m) <= crease_angle,
+                        _ => true,
+                    };
+
+                    if smooth {
+                        fans.union(first.0, second.0);
+                        fans.union(first.1, second.1);
+                    }
+                }
+            }
+        }
+
+        let mut fan_vertices = HashMap::<usize, usize>::new();
+        let mut corner_vertices = Vec::<usize>::with_capacity(triangles.len() * 3);
+        for corner in 0..triangles.len() * 3 {
+            let next_vertex = fan_vertices.len();
+            corner_vertices.push(*fan_vertices.entry(fans.find(corner)).or_insert(next_vertex));
+        }
+
+        let mut sums = vec![T::zero(); fan_vertices.len()];
+        for (f, triangle) in triangles.iter().enumerate() {
+            if let Some(face_normal) = face_normals[f] {
+                let face_vertices = [vertices[triangle[0]], vertices[triangle[1]], vertices[triangle[2]]];
+                for (i, weighted_normal) in get_weighted_corner_normals(&face_vertices, face_normal, weighting).into_iter().enumerate() {
+                    sums[corner_vertices[f * 3 + i]] += weighted_normal;
+                }
+            }
+        }
+
+        let mut normal_map = NormalMap::<T, U>::with_capacity(sums.len());
+        for sum in sums {
+            normal_map.push(sum.normalize().unwrap_or(T::zero()));
+        }
+
+        let mut split_mesh = mesh.split_corners(&corner_vertices)?;
+        match split_mesh.get_vertex_property::<NormalMap<T, U>, T>(PropertyType::NORMAL) {
+            Some(existing) => *existing = normal_map,
+            None => { split_mesh.add_vertex_property(normal_map); }
+        }
+        return Some(split_mesh);
+    }
+
     #[cfg(test)]
     mod unit_tests {
 
         use crate::common_properties::NormalMap;
+        use crate::mesh_normal_calculation::mesh_normals::VertexNormalWeighting;
+        use crate::mesh_normal_calculation::mesh_normals::add_face_normals;
+        use crate::mesh_normal_calculation::mesh_normals::add_vertex_normals;
         use crate::mesh_normal_calculation::mesh_normals::create_angle_weighted_pseudo_vertex_normals;
+        use crate::mesh_normal_calculation::mesh_normals::create_vertex_normals;
+        use crate::mesh_normal_calculation::mesh_normals::split_normals_at_creases;
+        use crate::property_map::FaceProperties;
         use crate::property_map::PropertyMap;
         use crate::property_map::PropertyType;
         use crate::property_map::VertexProperties;
+        use crate::mesh::Mesh;
         use crate::vector::Vec3f;
         use crate::vector::Vec3;
         use crate::triangle_vertex_mesh::TriangleVertexMesh;
@@ -201,6 +441,104 @@ pub mod mesh_normals {
             assert!(map.is_some());
         }
 
+        #[test]
+        fn test_add_normals() {
+
+            let mut usquare = create_unit_square();
+
+            add_face_normals(&mut usquare);
+            add_vertex_normals(&mut usquare, VertexNormalWeighting::UNIFORM);
+            add_vertex_normals(&mut usquare, VertexNormalWeighting::AREA);
+
+            let face_normals = usquare.get_face_property::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+            assert_eq!(face_normals.len(), 2);
+            assert_eq!(face_normals.get(1).z(), 1.0);
+
+            let vertex_normals = usquare.get_vertex_property::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+            assert_eq!(vertex_normals.len(), 4);
+            assert_eq!(vertex_normals.get(3).z(), 1.0);
+        }
+
+        #[test]
+        fn test_vertex_normal_weightings() {
+
+            //Two perpendicular triangles meeting at the origin, the second one has twice the area
+            let vertices = Vec::from(
+                [Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(0.0, 0.0, 2.0)]
+            );
+            let mesh = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, Vec::from([0, 1, 2, 0, 2, 3])).unwrap();
+
+            let uniform = create_vertex_normals(&mesh, VertexNormalWeighting::UNIFORM).get(0);
+            let area = create_vertex_normals(&mesh, VertexNormalWeighting::AREA).get(0);
+            let angle = create_vertex_normals(&mesh, VertexNormalWeighting::ANGLE).get(0);
+            let max = create_vertex_normals(&mesh, VertexNormalWeighting::MAX).get(0);
+
+            assert!((uniform.x() - uniform.z()).abs() < 1e-6);
+            assert!((angle.x() - angle.z()).abs() < 1e-6);
+            assert!((area.x() - 2.0 * area.z()).abs() < 1e-6);
+
+            //The longer edge of the second triangle reduces its weight
+            assert!((max.z() - 2.0 * max.x()).abs() < 1e-6);
+        }
+
+        fn create_unit_cube() -> TriangleVertexMesh<Vec3f, f32, u32> {
+
+            let vertices = Vec::from(
+                [Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(1.0, 1.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(0.0, 0.0, 1.0),
+                Vec3f::new(1.0, 0.0, 1.0),
+                Vec3f::new(1.0, 1.0, 1.0),
+                Vec3f::new(0.0, 1.0, 1.0)]
+            );
+
+            let indices = Vec::from(
+                [
+                    0, 2, 1, 0, 3, 2,
+                    4, 5, 6, 4, 6, 7,
+                    0, 1, 5, 0, 5, 4,
+                    1, 2, 6, 1, 6, 5,
+                    2, 3, 7, 2, 7, 6,
+                    3, 0, 4, 3, 4, 7
+                ]
+            );
+
+            return TriangleVertexMesh::from(vertices, indices).unwrap();
+        }
+
+        #[test]
+        fn test_split_normals_at_creases() {
+
+            let cube = create_unit_cube();
+
+            let hard_cube = split_normals_at_creases(&cube, std::f32::consts::FRAC_PI_3, VertexNormalWeighting::ANGLE).unwrap();
+            assert_eq!(hard_cube.get_vertices().len(), 24);
+            assert_eq!(hard_cube.get_face_count(), 12);
+
+            //Every corner has the normal of its side
+            let normals = hard_cube.get_vertex_property_ref::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+            for f in 0..12 {
+                let face = hard_cube.get_face(f);
+                let side = hard_cube.get_vertices()[face[0] as usize] + hard_cube.get_vertices()[face[1] as usize] + hard_cube.get_vertices()[face[2] as usize];
+                for idx in face {
+                    let normal = normals.get(idx as usize);
+                    assert_eq!(normal.x() * normal.x() + normal.y() * normal.y() + normal.z() * normal.z(), 1.0);
+                    assert!(normal.x() * (side.x() - 1.5) + normal.y() * (side.y() - 1.5) + normal.z() * (side.z() - 1.5) > 0.0);
+                }
+            }
+
+            //Above the crease angle, the cube stays smooth
+            let smooth_cube = split_normals_at_creases(&cube, 100.0f32.to_radians(), VertexNormalWeighting::ANGLE).unwrap();
+            assert_eq!(smooth_cube.get_vertices().len(), 8);
+            let normals = smooth_cube.get_vertex_property_ref::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).unwrap();
+            assert!((normals.get(6).x() - normals.get(6).z()).abs() < 1e-6);
+        }
+
     }
 
 }
\ No newline at end of file
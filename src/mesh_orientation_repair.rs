@@ -0,0 +1,301 @@
+pub mod mesh_orientation {
+
+    use std::collections::HashMap;
+    use std::collections::VecDeque;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::mesh::Mesh;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Summary of the changes made while orienting a mesh
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct OrientationResult {
+        /// Number of triangles, whose winding differs from the original one
+        pub flipped_faces : usize,
+        /// Number of components, where faces are connected through manifold edges
+        pub components : usize,
+        /// Components without consistent orientation (e.g. a Möbius strip). Some of their edges stay inconsistent.
+        pub non_orientable_components : usize,
+        /// Closed components, which were turned inside out to face outward
+        pub reversed_components : usize,
+    }
+
+    struct Component {
+        faces : Vec<usize>,
+        closed : bool,
+        orientable : bool,
+    }
+
+    fn has_directed_edge(triangle : &[usize; 3], a : usize, b : usize) -> bool {
+        return (0..3).any(|i| triangle[i] == a && triangle[(i + 1) % 3] == b);
+    }
+
+    /// Flips triangles, such that neighbouring triangles traverse their shared edge in opposite directions.
+    /// Each component keeps the orientation of its first triangle.
+    fn orient_components(triangles : &mut [[usize; 3]], flipped : &mut [bool]) -> Vec<Component> {
+
+        let mut edges = HashMap::<(usize, usize), Vec<usize>>::new();
+        for (f, triangle) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                let a = triangle[i];
+                let b = triangle[(i + 1) % 3];
+                if a != b {
+                    edges.entry((a.min(b), a.max(b))).or_default().push(f);
+                }
+            }
+        }
+
+        let mut components = Vec::<Component>::new();
+        let mut visited = vec![false; triangles.len()];
+        let mut queue = VecDeque::<usize>::new();
+
+        for seed in 0..triangles.len() {
+
+            if visited[seed] {
+                continue;
+            }
+            visited[seed] = true;
+            queue.push_back(seed);
+
+            let mut component = Component { faces: Vec::<usize>::new(), closed: true, orientable: true };
+
+            while let Some(f) = queue.pop_front() {
+
+                component.faces.push(f);
+
+                for i in 0..3 {
+
+                    let a = triangles[f][i];
+                    let b = triangles[f][(i + 1) % 3];
+                    if a == b {
+                        continue;
+                    }
+
+                    //Orientation is only propagated over manifold edges
+                    let neighbours = &edges[&(a.min(b), a.max(b))];
+                    if neighbours.len() != 2 {
+                        component.closed = false;
+                        continue;
+                    }
+
+                    let g = if neighbours[0] == f { neighbours[1] } else { neighbours[0] };
+                    if g == f {
+                        continue;
+                    }
+
+                    let same_direction = has_directed_edge(&triangles[g], a, b);
+
+                    if !visited[g] {
+                        visited[g] = true;
+                        if same_direction {
+                            triangles[g].swap(1, 2);
+                            flipped[g] = !flipped[g];
+                        }
+                        queue.push_back(g);
+                    } else if same_direction {
+                        component.orientable = false;
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        return components;
+    }
+
+    fn orient<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, outward : bool) -> OrientationResult
+    {
+        let mut triangles = mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect::<Vec<[usize; 3]>>();
+        let mut flipped = vec![false; triangles.len()];
+
+        let components = orient_components(&mut triangles, &mut flipped);
+
+        let mut result = OrientationResult {
+            components: components.len(),
+            non_orientable_components: components.iter().filter(|c| !c.orientable).count(),
+            ..Default::default()
+        };
+
+        if outward {
+
+            let vertices = mesh.get_vertices();
+
+            for component in components.iter().filter(|c| c.closed && c.orientable) {
+
+                //Six times the signed volume enclosed by the component, which is negative if the faces point inward
+                let mut volume = U::zero();
+                for f in &component.faces {
+                    let [a, b, c] = triangles[*f];
+                    volume = volume + T::dot(&vertices[a], &T::cross(vertices[b], vertices[c]));
+                }
+
+                if volume < U::zero() {
+                    for f in &component.faces {
+                        triangles[*f].swap(1, 2);
+                        flipped[*f] = !flipped[*f];
+                    }
+                    result.reversed_components += 1;
+                }
+            }
+        }
+
+        result.flipped_faces = flipped.iter().filter(|f| **f).count();
+
+        //Flipped triangles change the order in which edges appear, so the edge properties are carried over by their vertices
+        if result.flipped_faces > 0 {
+            mesh.replace_indices(triangles.iter().flatten().map(|v| NumCast::from(*v).unwrap()).collect());
+        }
+
+        return result;
+    }
+
+    /// Walks through the triangles over their shared edges and flips them, such that neighbours agree on their winding.
+    /// Each connected component keeps the winding of its triangle with the lowest index. Non-manifold edges do not
+    /// connect triangles. Existing vertex normals are not updated.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut mesh = ...;
+    ///
+    /// let result = orient_consistently(&mut mesh);
+    /// println!("Flipped {} faces", result.flipped_faces);
+    /// ```
+    pub fn orient_consistently<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>) -> OrientationResult
+    {
+        return orient(mesh, false);
+    }
+
+    /// Orients the triangles consistently like [orient_consistently] and additionally turns closed components
+    /// inside out, if their signed volume is negative, such that their face normals point outward.
+    /// Open and non-orientable components only receive a consistent orientation.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut mesh = stl::read_triangle_mesh(reader, 1e-6)?;
+    /// orient_outward(&mut mesh);
+    ///
+    /// let normals = create_angle_weighted_pseudo_vertex_normals(&mesh);
+    /// ```
+    pub fn orient_outward<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>) -> OrientationResult
+    {
+        return orient(mesh, true);
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::common_properties::AttributeMap;
+        use crate::mesh::Mesh;
+        use crate::mesh_edges::MeshEdges;
+        use crate::mesh_normal_calculation::mesh_normals::calculate_face_normal;
+        use crate::mesh_orientation_repair::mesh_orientation::orient_consistently;
+        use crate::mesh_orientation_repair::mesh_orientation::orient_outward;
+        use crate::mesh_validation::mesh_diagnostics::validate;
+        use crate::property_map::EdgeProperties;
+        use crate::property_map::PropertyHandle;
+        use crate::property_map::PropertyMap;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+
+        fn create_inward_tetrahedron() -> TriangleVertexMesh<Vec3f, f32, u32> {
+
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(0.0, 0.0, 1.0)]);
+
+            let indices = Vec::from([
+                0, 1, 2,
+                0, 3, 1,
+                1, 3, 2,
+                0, 2, 3]);
+
+            return TriangleVertexMesh::from(vertices, indices).unwrap();
+        }
+
+        #[test]
+        fn test_orient_consistently() {
+
+            let mut mesh = create_inward_tetrahedron();
+            let mut indices = mesh.get_indices().clone();
+            indices.swap(4, 5);
+            indices.swap(10, 11);
+            mesh.replace_indices(indices);
+            assert!(!validate(&mesh).is_consistently_oriented());
+
+            let result = orient_consistently(&mut mesh);
+
+            assert_eq!(result.flipped_faces, 2);
+            assert_eq!(result.components, 1);
+            assert!(validate(&mesh).is_consistently_oriented());
+
+            //The first face keeps its winding
+            assert_eq!(mesh.get_face(0), Vec::from([0, 1, 2]));
+        }
+
+        #[test]
+        fn test_orient_keeps_edge_properties() {
+
+            //Unit cube, whose faces point inward except for the two triangles of the front side
+            let vertices = [0.0, 1.0].iter().flat_map(|z| [0.0, 1.0].iter().flat_map(move |y| [0.0, 1.0].iter().map(move |x| Vec3f::new(*x, *y, *z))))
+                .collect::<Vec<Vec3f>>();
+            let indices = Vec::from([
+                0, 3, 1, 0, 2, 3, 5, 4, 6, 5, 6, 7, 4, 0, 2, 4, 2, 6,
+                1, 5, 7, 1, 7, 3, 2, 3, 7, 2, 7, 6, 4, 5, 1, 4, 1, 0]);
+            let mut mesh = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, indices).unwrap();
+            let edges = MeshEdges::from_mesh(&mesh);
+
+            //Each edge stores its own vertices, which have to match the edge with the same id after the repair
+            let vertex_pairs = PropertyHandle::<AttributeMap<[usize; 2]>>::named("vertex_pairs");
+            mesh.add_named_edge_property(&vertex_pairs, AttributeMap::from(edges.get_edges().to_vec()));
+
+            let result = orient_outward(&mut mesh);
+            assert_eq!(result.flipped_faces, 10);
+            assert!(validate(&mesh).is_consistently_oriented());
+
+            //The flipped triangles list their edges in another order, which changes the edge ids
+            let new_edges = MeshEdges::from_mesh(&mesh);
+            assert_ne!(new_edges.get_edges(), edges.get_edges());
+            let pairs = mesh.get_named_edge_property_ref(&vertex_pairs).unwrap();
+            assert_eq!(pairs.len(), new_edges.get_edge_count());
+            for (e, edge) in new_edges.get_edges().iter().enumerate() {
+                assert_eq!(pairs.get(e), *edge);
+            }
+        }
+
+        #[test]
+        fn test_orient_outward() {
+
+            let mut mesh = create_inward_tetrahedron();
+            let mut indices = mesh.get_indices().clone();
+            indices.swap(7, 8);
+            mesh.replace_indices(indices);
+
+            let result = orient_outward(&mut mesh);
+
+            assert_eq!(result.reversed_components, 1);
+            assert_eq!(result.flipped_faces, 3);
+
+            //The bottom face lies in the xy-plane and has to point downwards
+            let vertices = mesh.get_vertices();
+            let bottom = mesh.get_face(0).iter().map(|i| vertices[*i as usize]).collect::<Vec<Vec3f>>();
+            assert_eq!(calculate_face_normal(bottom).unwrap().z(), -1.0);
+        }
+    }
+}
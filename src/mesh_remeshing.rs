@@ -0,0 +1,813 @@
+pub mod remeshing {
+
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::common_properties::AttributeMap;
+    use crate::math::Point;
+    use crate::math::cross;
+    use crate::math::dot;
+    use crate::math::from_point;
+    use crate::math::get_distance;
+    use crate::math::normalize;
+    use crate::math::sub;
+    use crate::math::to_point;
+    use crate::mesh::Mesh;
+    use crate::mesh_edges::MeshEdges;
+    use crate::mesh_edges::get_edge_sources;
+    use crate::property_map::EdgeProperties;
+    use crate::property_map::ElementSources;
+    use crate::property_map::PropertyHandle;
+    use crate::property_map::PropertyMap;
+    use crate::triangle_topology::TriangleTopology;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Maximum number of steps when walking over the original surface to the closest point
+    const MAX_PROJECTION_STEPS : usize = 100;
+
+    /// Interpolation weights below this value are dropped from the sources of projected vertices
+    const MIN_SOURCE_WEIGHT : f64 = 1e-9;
+
+    /// Options of [remesh_isotropic]
+    #[derive(Clone)]
+    pub struct RemeshingOptions {
+        /// Length all edges should have after remeshing
+        pub target_length : f64,
+        /// Number of rounds of splitting, collapsing, flipping and relaxation
+        pub iterations : usize,
+        /// Edges whose triangle normals differ by a larger angle, in radians, are kept as feature edges. The same angle
+        /// marks the corners of feature lines and boundaries.
+        pub feature_angle : f64,
+        /// Additional feature edges, for which this edge property map is true
+        pub features : Option<PropertyHandle<AttributeMap<bool>>>,
+    }
+
+    impl RemeshingOptions {
+
+        /// Creates options with ten iterations, which keep edges sharper than 45 degrees as feature edges
+        pub fn new(target_length : f64) -> Self {
+            return Self {
+                target_length,
+                iterations: 10,
+                feature_angle: 45.0_f64.to_radians(),
+                features: None,
+            };
+        }
+    }
+
+    /// Statistics of [remesh_isotropic]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct RemeshingResult {
+        pub split_edges : usize,
+        pub collapsed_edges : usize,
+        pub flipped_edges : usize,
+    }
+
+    fn get_normal(corners : [&Point; 3]) -> Option<Point> {
+        return normalize(cross(&sub(corners[1], corners[0]), &sub(corners[2], corners[0])));
+    }
+
+    fn interpolate<const N : usize>(corners : [&Point; N], weights : [f64; N]) -> Point {
+        return [0, 1, 2].map(|c| corners.iter().zip(weights).map(|(p, w)| p[c] * w).sum());
+    }
+
+    /// Barycentric coordinates of the point on the triangle closest to p, following Ericson, Real-Time Collision Detection
+    fn get_closest_weights(p : &Point, a : &Point, b : &Point, c : &Point) -> [f64; 3] {
+
+        let (ab, ac, ap) = (sub(b, a), sub(c, a), sub(p, a));
+        let (d1, d2) = (dot(&ab, &ap), dot(&ac, &ap));
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return [1.0, 0.0, 0.0];
+        }
+
+        let bp = sub(p, b);
+        let (d3, d4) = (dot(&ab, &bp), dot(&ac, &bp));
+        if d3 >= 0.0 && d4 <= d3 {
+            return [0.0, 1.0, 0.0];
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            let v = d1 / (d1 - d3);
+            return [1.0 - v, v, 0.0];
+        }
+
+        let cp = sub(p, c);
+        let (d5, d6) = (dot(&ab, &cp), dot(&ac, &cp));
+        if d6 >= 0.0 && d5 <= d6 {
+            return [0.0, 0.0, 1.0];
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            let w = d2 / (d2 - d6);
+            return [1.0 - w, 0.0, w];
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+            return [0.0, 1.0 - w, w];
+        }
+
+        let denominator = va + vb + vc;
+        let (v, w) = (vb / denominator, vc / denominator);
+        return match v.is_finite() && w.is_finite() {
+            true => [1.0 - v - w, v, w],
+            false => [1.0, 0.0, 0.0],
+        };
+    }
+
+    /// Sources of a point interpolated from original vertices, without negligible weights
+    fn get_sources(vertices : &[usize], weights : &[f64]) -> ElementSources {
+        let sources = vertices.iter().zip(weights).filter(|(_, w)| **w > MIN_SOURCE_WEIGHT).map(|(v, w)| (*v, *w)).collect::<ElementSources>();
+        let sum = sources.iter().map(|(_, w)| w).sum::<f64>();
+        return sources.into_iter().map(|(v, w)| (v, w / sum)).collect();
+    }
+
+    /// Closest point on the original surface, with the original face or feature edge it lies on
+    struct Projection {
+        element : usize,
+        point : Point,
+        sources : ElementSources,
+    }
+
+    /// Hierarchy of uniform grids of cubic cells, which lists the elements whose bounding boxes overlap each cell. The cells
+    /// double in size from each level to the next, and each element is stored in the finest level with cells at least as large
+    /// as its bounding box. So it overlaps at most eight cells, and a few large elements do not blow up the memory.
+    struct ElementGrid {
+        cell_size : f64,
+        cells : HashMap<(usize, [i64; 3]), Vec<usize>>,
+        /// Range of the occupied cells of each level
+        bounds : Vec<[[i64; 3]; 2]>,
+    }
+
+    impl ElementGrid {
+
+        /// Cell sizes of the finest level, which are not strictly positive, are replaced by one. Elements with coordinates,
+        /// which are not finite, are left out.
+        fn new<const N : usize>(points : &[Point], elements : &[[usize; N]], cell_size : f64) -> Self {
+
+            let mut grid = Self {
+                cell_size: if cell_size > 0.0 { cell_size } else { 1.0 },
+                cells: HashMap::new(),
+                bounds: Vec::new(),
+            };
+            for (e, element) in elements.iter().enumerate() {
+
+                let [min, max] = [f64::min, f64::max].map(|f| [0, 1, 2].map(|c| element.iter().map(|v| points[*v][c]).reduce(f).unwrap()));
+                let extent = (0..3).map(|c| max[c] - min[c]).fold(0.0, f64::max);
+                if !extent.is_finite() {
+                    continue;
+                }
+                let level = if extent > grid.cell_size { (extent / grid.cell_size).log2().ceil() as usize } else { 0 };
+                if grid.bounds.len() <= level {
+                    grid.bounds.resize(level + 1, [[i64::MAX; 3], [i64::MIN; 3]]);
+                }
+
+                let [lo, hi] = [min, max].map(|p| grid.cell_of(&p, level));
+                for x in lo[0]..=hi[0] {
+                    for y in lo[1]..=hi[1] {
+                        for z in lo[2]..=hi[2] {
+                            grid.cells.entry((level, [x, y, z])).or_default().push(e);
+                        }
+                    }
+                }
+                let bounds = &mut grid.bounds[level];
+                *bounds = [[0, 1, 2].map(|c| bounds[0][c].min(lo[c])), [0, 1, 2].map(|c| bounds[1][c].max(hi[c]))];
+            }
+            return grid;
+        }
+
+        fn cell_of(&self, point : &Point, level : usize) -> [i64; 3] {
+            let size = self.cell_size * (level as f64).exp2();
+            return point.map(|x| (x / size).floor() as i64);
+        }
+
+        /// Elements in the cells around the point, which include all elements closer to it than the radius
+        fn get_candidates(&self, point : &Point, radius : f64) -> HashSet<usize> {
+
+            let mut candidates = HashSet::new();
+            for (level, bounds) in self.bounds.iter().enumerate() {
+
+                let lo = self.cell_of(&point.map(|x| x - radius), level);
+                let hi = self.cell_of(&point.map(|x| x + radius), level);
+                let [lo, hi] = [[0, 1, 2].map(|c| lo[c].max(bounds[0][c])), [0, 1, 2].map(|c| hi[c].min(bounds[1][c]))];
+
+                for x in lo[0]..=hi[0] {
+                    for y in lo[1]..=hi[1] {
+                        for z in lo[2]..=hi[2] {
+                            candidates.extend(self.cells.get(&(level, [x, y, z])).into_iter().flatten());
+                        }
+                    }
+                }
+            }
+            return candidates;
+        }
+    }
+
+    /// Original surface, onto which the vertices are projected. Vertices on feature lines are projected onto the original
+    /// feature edges, called segments, instead of the triangles.
+    struct Surface {
+        points : Vec<Point>,
+        triangles : Vec<[usize; 3]>,
+        vertex_faces : Vec<Vec<usize>>,
+        segments : Vec<[usize; 2]>,
+        vertex_segments : Vec<Vec<usize>>,
+        triangle_grid : ElementGrid,
+        segment_grid : ElementGrid,
+    }
+
+    impl Surface {
+
+        /// Walks from the triangle hint to the closest triangle, by moving to the closest triangle sharing a vertex with the
+        /// current one as long as it gets closer. As the vertices only move a little in each step of the remeshing, the hint
+        /// from their previous projection is close to the result. The walk can stop in a local minimum, so all triangles
+        /// within the distance it reached are checked afterwards.
+        fn project(&self, point : &Point, hint : usize) -> Projection {
+
+            let evaluate = |f : usize| -> (f64, [f64; 3]) {
+                let [a, b, c] = self.triangles[f].map(|v| &self.points[v]);
+                let weights = get_closest_weights(point, a, b, c);
+                return (get_distance(point, &interpolate([a, b, c], weights)), weights);
+            };
+
+            let (mut best, (mut distance, mut weights)) = (hint, evaluate(hint));
+            for _ in 0..MAX_PROJECTION_STEPS {
+                let current = best;
+                for f in self.triangles[current].iter().flat_map(|v| self.vertex_faces[*v].iter()) {
+                    let (d, w) = evaluate(*f);
+                    if d < distance {
+                        (best, distance, weights) = (*f, d, w);
+                    }
+                }
+                if best == current {
+                    break;
+                }
+            }
+            for f in self.triangle_grid.get_candidates(point, distance) {
+                let (d, w) = evaluate(f);
+                if d < distance {
+                    (best, distance, weights) = (f, d, w);
+                }
+            }
+
+            let triangle = self.triangles[best];
+            return Projection {
+                element: best,
+                point: interpolate(triangle.map(|v| &self.points[v]), weights),
+                sources: get_sources(&triangle, &weights),
+            };
+        }
+
+        /// Walks from the segment hint to the closest segment, like [Surface::project]
+        fn project_onto_segments(&self, point : &Point, hint : usize) -> Projection {
+
+            let evaluate = |s : usize| -> (f64, f64) {
+                let [a, b] = self.segments[s].map(|v| &self.points[v]);
+                let direction = sub(b, a);
+                let t = (dot(&sub(point, a), &direction) / dot(&direction, &direction)).clamp(0.0, 1.0);
+                let t = if t.is_finite() { t } else { 0.0 };
+                return (get_distance(point, &interpolate([a, b], [1.0 - t, t])), t);
+            };
+
+            let (mut best, (mut distance, mut t)) = (hint, evaluate(hint));
+            for _ in 0..MAX_PROJECTION_STEPS {
+                let current = best;
+                for s in self.segments[current].iter().flat_map(|v| self.vertex_segments[*v].iter()) {
+                    let (d, u) = evaluate(*s);
+                    if d < distance {
+                        (best, distance, t) = (*s, d, u);
+                    }
+                }
+                if best == current {
+                    break;
+                }
+            }
+            for s in self.segment_grid.get_candidates(point, distance) {
+                let (d, u) = evaluate(s);
+                if d < distance {
+                    (best, distance, t) = (s, d, u);
+                }
+            }
+
+            let segment = self.segments[best];
+            return Projection {
+                element: best,
+                point: interpolate(segment.map(|v| &self.points[v]), [1.0 - t, t]),
+                sources: get_sources(&segment, &[1.0 - t, t]),
+            };
+        }
+    }
+
+    /// How a vertex may move. Vertices on feature lines and boundaries only move along them, while their corners stay in place.
+    #[derive(Clone, Copy, PartialEq, PartialOrd)]
+    enum Constraint {
+        Free,
+        Line,
+        Corner,
+    }
+
+    /// State of the remeshing, where each vertex keeps the original triangle and segment of its last projection
+    struct Remeshing<'a> {
+        surface : &'a Surface,
+        topology : TriangleTopology,
+        points : Vec<Point>,
+        constraints : Vec<Constraint>,
+        faces : Vec<usize>,
+        segments : Vec<usize>,
+        constrained_edges : HashSet<(usize, usize)>,
+        result : RemeshingResult,
+    }
+
+    fn get_key(a : usize, b : usize) -> (usize, usize) {
+        return (a.min(b), a.max(b));
+    }
+
+    impl<'a> Remeshing<'a> {
+
+        fn get_length(&self, a : usize, b : usize) -> f64 {
+            return get_distance(&self.points[a], &self.points[b]);
+        }
+
+        fn get_projection(&self, v : usize) -> Option<Projection> {
+            return match self.constraints[v] {
+                Constraint::Free => Some(self.surface.project(&self.points[v], self.faces[v])),
+                Constraint::Line => Some(self.surface.project_onto_segments(&self.points[v], self.segments[v])),
+                Constraint::Corner => None,
+            };
+        }
+
+        fn project(&mut self, v : usize) {
+            match (self.constraints[v], self.get_projection(v)) {
+                (Constraint::Free, Some(projection)) => {
+                    self.faces[v] = projection.element;
+                    self.points[v] = projection.point;
+                }
+                (Constraint::Line, Some(projection)) => {
+                    self.faces[v] = self.surface.vertex_faces[self.surface.segments[projection.element][0]][0];
+                    self.segments[v] = projection.element;
+                    self.points[v] = projection.point;
+                }
+                _ => {}
+            }
+        }
+
+        /// Splits edges longer than the maximum at their midpoint, until all edges are short enough
+        fn split_long_edges(&mut self, max_length : f64) {
+
+            loop {
+                let mut changed = false;
+                for [a, b] in self.topology.get_edges() {
+                    if self.get_length(a, b) <= max_length {
+                        continue;
+                    }
+                    let Some(m) = self.topology.split_edge(a, b) else { continue; };
+
+                    let midpoint = interpolate([&self.points[a], &self.points[b]], [0.5, 0.5]);
+                    self.points.push(midpoint);
+                    self.faces.push(self.faces[a]);
+                    self.segments.push(self.segments[a]);
+                    if self.constrained_edges.remove(&get_key(a, b)) {
+                        self.constrained_edges.insert(get_key(a, m));
+                        self.constrained_edges.insert(get_key(m, b));
+                        self.constraints.push(Constraint::Line);
+                    } else {
+                        self.constraints.push(Constraint::Free);
+                    }
+
+                    self.project(m);
+                    self.result.split_edges += 1;
+                    changed = true;
+                }
+                if !changed {
+                    break;
+                }
+            }
+        }
+
+        /// Finds the vertex to keep, the vertex to remove and the new position of a collapse. Vertices are only merged into
+        /// vertices with the same or a stronger constraint, and vertices on lines only along the line.
+        fn get_collapse(&self, a : usize, b : usize) -> Option<(usize, usize, Point)> {
+
+            let (kept, removed) = if self.constraints[b] > self.constraints[a] { (b, a) } else { (a, b) };
+            return match (self.constraints[kept], self.constraints[removed]) {
+                (_, Constraint::Corner) => None,
+                (_, Constraint::Line) if !self.constrained_edges.contains(&get_key(a, b)) => None,
+                (Constraint::Free, _) => Some((kept, removed, interpolate([&self.points[a], &self.points[b]], [0.5, 0.5]))),
+                _ => Some((kept, removed, self.points[kept])),
+            };
+        }
+
+        /// Checks whether moving both vertices to the point flips or degenerates any of their other triangles
+        fn flips_triangles(&self, kept : usize, removed : usize, point : &Point) -> bool {
+
+            let shared = self.topology.get_shared_faces(kept, removed);
+            for f in self.topology.get_vertex_faces(kept).iter().chain(self.topology.get_vertex_faces(removed)).filter(|f| !shared.contains(f)) {
+                let triangle = self.topology.get_triangle(*f);
+                let before = get_normal(triangle.map(|v| &self.points[v]));
+                let after = get_normal(triangle.map(|v| if v == kept || v == removed { point } else { &self.points[v] }));
+                match (before, after) {
+                    (Some(before), Some(after)) if dot(&before, &after) > 0.0 => {},
+                    (None, _) => {},
+                    _ => return true,
+                }
+            }
+            return false;
+        }
+
+        /// Collapses edges shorter than the minimum, unless this would create edges longer than the maximum
+        fn collapse_short_edges(&mut self, min_length : f64, max_length : f64) {
+
+            for [a, b] in self.topology.get_edges() {
+
+                let exists = self.topology.is_vertex_alive(a) && self.topology.is_vertex_alive(b) && !self.topology.get_shared_faces(a, b).is_empty();
+                if !exists || self.get_length(a, b) >= min_length {
+                    continue;
+                }
+                let Some((kept, removed, point)) = self.get_collapse(a, b) else { continue; };
+
+                let neighbours = self.topology.get_neighbours(removed);
+                let is_too_long = neighbours.iter().chain(self.topology.get_neighbours(kept).iter())
+                    .any(|v| *v != kept && *v != removed && get_distance(&point, &self.points[*v]) > max_length);
+                if is_too_long || !self.topology.can_collapse_edge(kept, removed) || self.flips_triangles(kept, removed, &point) {
+                    continue;
+                }
+
+                for v in neighbours {
+                    if self.constrained_edges.remove(&get_key(removed, v)) && v != kept {
+                        self.constrained_edges.insert(get_key(kept, v));
+                    }
+                }
+                self.topology.collapse_edge(kept, removed);
+                self.points[kept] = point;
+                self.result.collapsed_edges += 1;
+            }
+        }
+
+        /// Squared deviation from the optimal valence, which is six inside and four on the boundary
+        fn get_valence_deviation(&self, v : usize, valence : usize) -> usize {
+            let optimal = if self.topology.is_boundary_vertex(v) { 4 } else { 6 };
+            return valence.abs_diff(optimal).pow(2);
+        }
+
+        /// Flips edges, if this brings the valences of their four vertices closer to the optimum without folding the surface
+        fn flip_edges(&mut self) {
+
+            for [a, b] in self.topology.get_edges() {
+
+                if self.constrained_edges.contains(&get_key(a, b)) || !self.topology.can_flip_edge(a, b) {
+                    continue;
+                }
+                let opposite = self.topology.get_opposite_vertices(a, b);
+                let (c, d) = (opposite[0], opposite[1]);
+
+                let valences = [a, b, c, d].map(|v| self.topology.get_valence(v));
+                let before = (0..4).map(|i| self.get_valence_deviation([a, b, c, d][i], valences[i])).sum::<usize>();
+                let after = self.get_valence_deviation(a, valences[0] - 1) + self.get_valence_deviation(b, valences[1] - 1) +
+                    self.get_valence_deviation(c, valences[2] + 1) + self.get_valence_deviation(d, valences[3] + 1);
+                if after >= before {
+                    continue;
+                }
+
+                let shared = self.topology.get_shared_faces(a, b);
+                let normals_before = shared.iter().map(|f| get_normal(self.topology.get_triangle(*f).map(|v| &self.points[v]))).collect::<Vec<Option<Point>>>();
+                let Some([c, d]) = self.topology.flip_edge(a, b) else { continue; };
+
+                //The new triangles must face in the same direction as both old ones, otherwise the flip is undone
+                let folds = shared.iter().any(|f| {
+                    let after = get_normal(self.topology.get_triangle(*f).map(|v| &self.points[v]));
+                    return normals_before.iter().any(|before| match (before, after) {
+                        (Some(before), Some(after)) => dot(before, &after) <= 0.0,
+                        _ => true,
+                    });
+                });
+                if folds {
+                    self.topology.flip_edge(c, d);
+                    continue;
+                }
+                self.result.flipped_edges += 1;
+            }
+        }
+
+        /// Moves free vertices towards the centroid of their neighbours within the tangent plane, then projects them back
+        /// onto the original surface
+        fn relax(&mut self) {
+
+            let mut normals = vec![[0.0; 3]; self.points.len()];
+            for (_, triangle) in self.topology.get_triangles() {
+                let [p, q, r] = triangle.map(|v| &self.points[v]);
+                let area_normal = cross(&sub(q, p), &sub(r, p));
+                for v in triangle {
+                    (0..3).for_each(|c| normals[v][c] += area_normal[c]);
+                }
+            }
+
+            let mut moved = Vec::<(usize, Point)>::new();
+            for v in (0..self.points.len()).filter(|v| self.topology.is_vertex_alive(*v) && self.constraints[*v] == Constraint::Free) {
+                let neighbours = self.topology.get_neighbours(v);
+                let Some(normal) = normalize(normals[v]) else { continue; };
+                if neighbours.is_empty() {
+                    continue;
+                }
+
+                let weight = 1.0 / neighbours.len() as f64;
+                let centroid = [0, 1, 2].map(|c| neighbours.iter().map(|w| self.points[*w][c] * weight).sum::<f64>());
+                let offset = sub(&self.points[v], &centroid);
+                let height = dot(&offset, &normal);
+                moved.push((v, [0, 1, 2].map(|c| centroid[c] + height * normal[c])));
+            }
+
+            for (v, point) in moved {
+                self.points[v] = point;
+                self.project(v);
+            }
+        }
+    }
+
+    /// Remeshes a triangle mesh, such that all edges have about the target length and the triangles are close to equilateral,
+    /// as proposed by Botsch and Kobbelt. Each iteration splits edges longer than 4/3 of the target length, collapses edges
+    /// shorter than 4/5 of it, flips edges towards valence six (four on the boundary) and moves the vertices within their
+    /// tangent planes towards the centroid of their neighbours. New and moved vertices are projected onto the original surface.
+    ///
+    /// Boundaries and feature edges are preserved: their vertices only move along them, and the corners, where they turn by
+    /// more than the feature angle or meet other feature lines, stay in place.
+    ///
+    /// Vertex properties are interpolated at the projection of each vertex onto the original surface. Faces take the
+    /// properties of the original triangle their center lies on, and edges on original edges keep their properties.
+    /// Returns None and leaves the mesh unchanged, if the new vertices cannot be addressed with the index type.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut options = RemeshingOptions::new(0.01);
+    /// options.feature_angle = 30.0_f64.to_radians();
+    ///
+    /// let result = remesh_isotropic(&mut scan, &options).unwrap();
+    /// ```
+    pub fn remesh_isotropic<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, options : &RemeshingOptions) -> Option<RemeshingResult>
+    {
+        if options.target_length.is_nan() || options.target_length <= 0.0 {
+            return Some(RemeshingResult::default());
+        }
+
+        let points = mesh.get_vertices().iter().map(to_point).collect::<Vec<Point>>();
+        let triangles = mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect::<Vec<[usize; 3]>>();
+        let vertex_count = points.len();
+
+        let mut vertex_faces = vec![Vec::<usize>::new(); vertex_count];
+        for (f, triangle) in triangles.iter().enumerate() {
+            triangle.iter().for_each(|v| vertex_faces[*v].push(f));
+        }
+
+        //Boundary, non-manifold, sharp and marked edges become segments
+        let edges = MeshEdges::from_faces(triangles.iter());
+        let mut edge_faces = vec![Vec::<usize>::new(); edges.get_edge_count()];
+        for f in 0..triangles.len() {
+            edges.get_face_edges(f).iter().for_each(|e| edge_faces[*e].push(f));
+        }
+        let marked = options.features.as_ref()
+            .and_then(|handle| mesh.get_named_edge_property_ref(handle))
+            .filter(|map| map.len() == edges.get_edge_count());
+
+        let mut segments = Vec::<[usize; 2]>::new();
+        let mut vertex_segments = vec![Vec::<usize>::new(); vertex_count];
+        for (e, edge) in edges.get_edges().iter().enumerate() {
+            let is_feature = match edge_faces[e].as_slice() {
+                [f, g] => {
+                    let normals = [f, g].map(|f| get_normal(triangles[*f].map(|v| &points[v])));
+                    let is_sharp = match normals {
+                        [Some(n), Some(m)] => dot(&n, &m).clamp(-1.0, 1.0).acos() > options.feature_angle,
+                        _ => false,
+                    };
+                    is_sharp || marked.is_some_and(|map| map.get(e))
+                }
+                _ => true,
+            };
+            if is_feature {
+                edge.iter().for_each(|v| vertex_segments[*v].push(segments.len()));
+                segments.push(*edge);
+            }
+        }
+
+        //Vertices on exactly two segments lie on a line, unless it turns too sharply there
+        let constraints = (0..vertex_count).map(|v| {
+            return match vertex_segments[v].as_slice() {
+                [] if vertex_faces[v].is_empty() => Constraint::Corner,
+                [] => Constraint::Free,
+                [s, t] => {
+                    let [p, q] = [s, t].map(|s| segments[*s].iter().copied().find(|w| *w != v).unwrap());
+                    let (u, w) = (sub(&points[p], &points[v]), sub(&points[v], &points[q]));
+                    let turn = match (normalize(u), normalize(w)) {
+                        (Some(u), Some(w)) => dot(&u, &w).clamp(-1.0, 1.0).acos(),
+                        _ => 0.0,
+                    };
+                    if turn > options.feature_angle { Constraint::Corner } else { Constraint::Line }
+                }
+                _ => Constraint::Corner,
+            };
+        }).collect::<Vec<Constraint>>();
+
+        //The grids use cells of the mean edge length
+        let cell_size = edges.get_edges().iter().map(|[a, b]| get_distance(&points[*a], &points[*b])).sum::<f64>() / edges.get_edge_count() as f64;
+        let surface = Surface {
+            triangle_grid: ElementGrid::new(&points, &triangles, cell_size),
+            segment_grid: ElementGrid::new(&points, &segments, cell_size),
+            points: points.clone(),
+            triangles: triangles.clone(),
+            vertex_faces,
+            segments,
+            vertex_segments,
+        };
+
+        let mut remeshing = Remeshing {
+            surface: &surface,
+            topology: TriangleTopology::new(vertex_count, triangles),
+            faces: (0..vertex_count).map(|v| surface.vertex_faces[v].first().copied().unwrap_or(usize::MAX)).collect(),
+            segments: (0..vertex_count).map(|v| surface.vertex_segments[v].first().copied().unwrap_or(usize::MAX)).collect(),
+            points,
+            constraints,
+            constrained_edges: surface.segments.iter().map(|[a, b]| get_key(*a, *b)).collect(),
+            result: RemeshingResult::default(),
+        };
+
+        let (min_length, max_length) = (0.8 * options.target_length, 4.0 / 3.0 * options.target_length);
+        for _ in 0..options.iterations {
+            remeshing.split_long_edges(max_length);
+            remeshing.collapse_short_edges(min_length, max_length);
+            remeshing.flip_edges();
+            remeshing.relax();
+        }
+
+        //Remaining vertices are compacted and take their sources from their projection onto the original surface
+        let mut new_index = vec![usize::MAX; remeshing.points.len()];
+        let mut vertices = Vec::<T>::new();
+        let mut vertex_sources = Vec::<ElementSources>::new();
+        for v in (0..remeshing.points.len()).filter(|v| remeshing.topology.is_vertex_alive(*v)) {
+            new_index[v] = vertices.len();
+            vertices.push(from_point(remeshing.points[v]));
+            vertex_sources.push(match remeshing.get_projection(v) {
+                Some(projection) => projection.sources,
+                None => Vec::from([(v, 1.0)]),
+            });
+        }
+        <IndexType as NumCast>::from(vertices.len())?;
+
+        let mut new_triangles = Vec::<[usize; 3]>::new();
+        let mut face_sources = Vec::<ElementSources>::new();
+        for (_, triangle) in remeshing.topology.get_triangles() {
+            let center = interpolate(triangle.map(|v| &remeshing.points[v]), [1.0 / 3.0; 3]);
+            let hint = triangle.iter().map(|v| remeshing.faces[*v]).find(|f| *f != usize::MAX).unwrap();
+            face_sources.push(Vec::from([(surface.project(&center, hint).element, 1.0)]));
+            new_triangles.push(triangle.map(|v| new_index[v]));
+        }
+
+        let edge_sources = match mesh.get_edge_properties().is_empty() {
+            true => None,
+            false => Some(get_edge_sources(&edges, &MeshEdges::from_faces(new_triangles.iter()), &vertex_sources)),
+        };
+        let indices = new_triangles.iter().flat_map(|t| t.map(|v| NumCast::from(v).unwrap())).collect::<Vec<IndexType>>();
+        mesh.replace(vertices, &vertex_sources, indices, &face_sources, edge_sources.as_deref());
+        return Some(remeshing.result);
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::common_properties::UvMap;
+        use crate::fixtures::create_box;
+        use crate::fixtures::create_grid;
+        use crate::fixtures::create_sphere;
+        use crate::math::get_distance;
+        use crate::mesh::Mesh;
+        use crate::mesh_edges::MeshEdges;
+        use crate::mesh_measures::measures::calculate_surface_area;
+        use crate::mesh_measures::measures::calculate_volume;
+        use crate::mesh_remeshing::remeshing::ElementGrid;
+        use crate::mesh_remeshing::remeshing::RemeshingOptions;
+        use crate::mesh_remeshing::remeshing::Surface;
+        use crate::mesh_remeshing::remeshing::remesh_isotropic;
+        use crate::property_map::PropertyMap;
+        use crate::property_map::PropertyType;
+        use crate::property_map::VertexProperties;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::FloatVector;
+        use crate::vector::Vec3d;
+        use crate::vector::Vec3;
+
+        fn get_edge_lengths(mesh : &TriangleVertexMesh<Vec3d, f64, u32>) -> Vec<f64> {
+            let vertices = mesh.get_vertices();
+            return MeshEdges::from_mesh(mesh).get_edges().iter().map(|[a, b]| (vertices[*a] - vertices[*b]).length()).collect();
+        }
+
+        #[test]
+        fn test_remesh_sphere() {
+
+            let mut sphere = create_sphere::<Vec3d, f64>(4, 1.0);
+            let volume = calculate_volume(&sphere);
+
+            let result = remesh_isotropic(&mut sphere, &RemeshingOptions::new(0.1)).unwrap();
+            assert!(result.split_edges > 0 && result.collapsed_edges > 0 && result.flipped_edges > 0);
+
+            //The sphere stays closed, keeps its shape and has edges close to the target length
+            let lengths = get_edge_lengths(&sphere);
+            assert_eq!(lengths.len() * 2, sphere.get_face_count() * 3);
+            assert!((calculate_volume(&sphere) - volume).abs() < 0.01 * volume);
+            assert!(sphere.get_vertices().iter().all(|v| (v.length() - 1.0).abs() < 0.01));
+            assert!(lengths.iter().all(|l| *l > 0.5 * 0.1 && *l < 1.5 * 0.1));
+            let mean = lengths.iter().sum::<f64>() / lengths.len() as f64;
+            assert!(mean > 0.08 && mean < 0.12);
+        }
+
+        #[test]
+        fn test_project_past_local_minimum() {
+
+            //A strip along x, which dips down between x = 1 and x = 4, so the walk from its start gets stuck on the slope
+            let heights = [0.0, 0.0, -5.0, -5.0, 0.0];
+            let points = (0..10).map(|v| [(v / 2) as f64, (v % 2) as f64, heights[v / 2]]).collect::<Vec<[f64; 3]>>();
+            let triangles = (0..4).flat_map(|i| [[2 * i, 2 * i + 2, 2 * i + 3], [2 * i, 2 * i + 3, 2 * i + 1]]).collect::<Vec<[usize; 3]>>();
+            let mut vertex_faces = vec![Vec::<usize>::new(); points.len()];
+            for (f, triangle) in triangles.iter().enumerate() {
+                triangle.iter().for_each(|v| vertex_faces[*v].push(f));
+            }
+            let surface = Surface {
+                triangle_grid: ElementGrid::new(&points, &triangles, 1.0),
+                segment_grid: ElementGrid::new::<2>(&points, &[], 1.0),
+                vertex_segments: vec![Vec::new(); points.len()],
+                segments: Vec::new(),
+                points,
+                triangles,
+                vertex_faces,
+            };
+
+            let point = [3.9, 0.5, 0.1];
+            let projection = surface.project(&point, 0);
+            assert!(projection.element == 6 || projection.element == 7);
+            assert!(get_distance(&point, &projection.point) < 0.2);
+        }
+
+        #[test]
+        fn test_element_grid_large_elements() {
+
+            //Small triangles along x and a single triangle, which is a million times larger
+            let mut points = (0..20).map(|v| [(v / 2) as f64, (v % 2) as f64, 0.0]).collect::<Vec<[f64; 3]>>();
+            let mut triangles = (0..9).map(|i| [2 * i, 2 * i + 2, 2 * i + 1]).collect::<Vec<[usize; 3]>>();
+            points.extend([[0.0, 0.0, 1.0], [1e6, 0.0, 1.0], [0.0, 1e6, 1.0]]);
+            triangles.push([20, 21, 22]);
+
+            let grid = ElementGrid::new(&points, &triangles, 1.0);
+            assert!(grid.cells.values().map(|elements| elements.len()).sum::<usize>() <= triangles.len() * 8);
+
+            let candidates = grid.get_candidates(&[4.5, 0.5, 0.5], 0.6);
+            assert!(candidates.contains(&4) && candidates.contains(&9));
+            assert!(!candidates.contains(&0));
+        }
+
+        #[test]
+        fn test_remesh_cube_features() {
+
+            let mut cube = create_box(Vec3d::new(-1.0, -1.0, -1.0), Vec3d::new(1.0, 1.0, 1.0)).triangulate();
+            remesh_isotropic(&mut cube, &RemeshingOptions::new(0.3)).unwrap();
+
+            //All vertices stay on the faces, the corners are kept and no triangle cuts across an edge of the cube
+            assert!(cube.get_face_count() > 100);
+            assert!(cube.get_vertices().iter().all(|v| (v.x().abs().max(v.y().abs()).max(v.z().abs()) - 1.0).abs() < 1e-9));
+            assert_eq!(cube.get_vertices().iter().filter(|v| v.x().abs() == 1.0 && v.y().abs() == 1.0 && v.z().abs() == 1.0).count(), 8);
+            assert!((calculate_volume(&cube) - 8.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn test_remesh_plane_with_properties() {
+
+            //Unit square of 2 x 2 quads, with texture coordinates equal to the position
+            let mut plane = create_grid::<Vec3d, f64>(2);
+            let uvs = plane.get_vertices().iter().map(|v| [v.x(), v.y()]).collect::<Vec<[f64; 2]>>();
+            plane.add_vertex_property(UvMap::from(uvs));
+
+            remesh_isotropic(&mut plane, &RemeshingOptions::new(0.1)).unwrap();
+
+            //The boundary and its corners are kept, so that the area stays the same
+            assert!(plane.get_face_count() > 100);
+            assert!((calculate_surface_area(&plane) - 1.0).abs() < 1e-9);
+            assert!(plane.get_vertices().iter().all(|v| v.x() >= 0.0 && v.x() <= 1.0 && v.y() >= 0.0 && v.y() <= 1.0 && v.z() == 0.0));
+
+            let uvs = plane.get_vertex_property_ref::<UvMap<f64>, Vec<[f64; 2]>>(PropertyType::UV).unwrap();
+            assert_eq!(uvs.len(), plane.get_vertices().len());
+            for (v, vertex) in plane.get_vertices().iter().enumerate() {
+                let uv = uvs.get(v)[0];
+                assert!((uv[0] - vertex.x()).abs() < 1e-9 && (uv[1] - vertex.y()).abs() < 1e-9);
+            }
+        }
+    }
+}
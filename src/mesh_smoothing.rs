@@ -0,0 +1,359 @@
+pub mod smoothing {
+
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::common_properties::AttributeMap;
+    use crate::math::Point;
+    use crate::math::from_point;
+    use crate::math::to_point;
+    use crate::mesh::Mesh;
+    use crate::mesh_components::MeshComponent;
+    use crate::mesh_edges::MeshEdges;
+    use crate::property_map::PropertyHandle;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::VertexProperties;
+    use crate::sparse_matrix::SparseMatrix;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Relative residual, at which the linear systems of implicit smoothing are solved
+    const SOLVER_TOLERANCE : f64 = 1e-10;
+
+    /// Vertices kept in place by the smoothing operators
+    #[derive(Clone)]
+    pub struct SmoothingConstraints {
+        /// Keeps vertices on the boundary in place, so that open meshes do not shrink from their boundary
+        pub fix_boundary : bool,
+        /// Only moves the vertices, for which this vertex property map is true
+        pub selection : Option<PropertyHandle<AttributeMap<bool>>>,
+    }
+
+    impl SmoothingConstraints {
+
+        /// Creates constraints, which keep the boundary in place and move all other vertices
+        pub fn new() -> Self {
+            return Self { fix_boundary: true, selection: None };
+        }
+    }
+
+    impl Default for SmoothingConstraints {
+        fn default() -> Self {
+            return Self::new();
+        }
+    }
+
+    fn get_triangles<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &TriangleVertexMesh<T, U, IndexType>)
+        -> Vec<[usize; 3]>
+    {
+        return mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect();
+    }
+
+    /// Decides for each vertex whether it may be moved, where vertices without faces are never moved
+    fn get_movable_vertices<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>, constraints : &SmoothingConstraints) -> Vec<bool>
+    {
+        let vertex_count = mesh.get_vertices().len();
+        let mut movable = vec![false; vertex_count];
+        for idx in mesh.get_indices() {
+            movable[idx.to_usize().unwrap()] = true;
+        }
+
+        if constraints.fix_boundary {
+            let edges = MeshEdges::from_mesh(mesh);
+            let mut face_counts = vec![0; edges.get_edge_count()];
+            for f in 0..edges.get_face_count() {
+                edges.get_face_edges(f).iter().for_each(|e| face_counts[*e] += 1);
+            }
+            for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+                if face_counts[e] != 2 {
+                    movable[*a] = false;
+                    movable[*b] = false;
+                }
+            }
+        }
+
+        let selection = constraints.selection.as_ref()
+            .and_then(|handle| mesh.get_named_vertex_property_ref(handle))
+            .filter(|selection| selection.len() == vertex_count);
+        if let Some(selection) = selection {
+            movable.iter_mut().enumerate().for_each(|(v, movable)| *movable &= selection.get(v));
+        }
+        return movable;
+    }
+
+    /// Moves each movable vertex by the factor towards the average of its neighbours
+    fn apply_uniform_laplacian<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(vertices : &mut [T], neighbours : &[Vec<usize>],
+        movable : &[bool], factor : f64)
+    {
+        let factor = U::from(factor).unwrap();
+        let moved = (0..vertices.len()).map(|v| {
+            if !movable[v] || neighbours[v].is_empty() {
+                return vertices[v];
+            }
+            let mut average = T::zero();
+            for n in &neighbours[v] {
+                average += vertices[*n];
+            }
+            average = average * (U::one() / U::from(neighbours[v].len()).unwrap());
+            return vertices[v] + (average - vertices[v]) * factor;
+        }).collect::<Vec<T>>();
+
+        vertices.copy_from_slice(&moved);
+    }
+
+    fn get_neighbours<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &TriangleVertexMesh<T, U, IndexType>)
+        -> Vec<Vec<usize>>
+    {
+        let incidence = mesh.make_incidence_map(MeshComponent::VERTEX, MeshComponent::VERTEX);
+        return (0..mesh.get_vertices().len()).map(|v| {
+            let mut neighbours = incidence.get(&NumCast::from(v).unwrap())
+                .map(|set| set.iter().map(|n| n.to_usize().unwrap()).filter(|n| *n != v).collect::<Vec<usize>>())
+                .unwrap_or_default();
+            neighbours.sort();
+            return neighbours;
+        }).collect();
+    }
+
+    /// Creates the cotangent Laplacian of a triangle mesh, a symmetric positive semi-definite matrix, where the entry of
+    /// each edge is minus half the sum of the cotangents of the angles opposite to it, and the diagonal entries are the
+    /// negative row sums. Degenerate triangles are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// let laplacian = create_cotangent_laplacian(&mesh);
+    /// let x = mesh.get_vertices().iter().map(|v| v.x() as f64).collect::<Vec<f64>>();
+    ///
+    /// let mean_curvature_normal_x = laplacian.multiply(&x);
+    /// ```
+    pub fn create_cotangent_laplacian<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> SparseMatrix
+    {
+        let vertex_count = mesh.get_vertices().len();
+        let points = mesh.get_vertices().iter().map(to_point).collect::<Vec<Point>>();
+
+        let mut triplets = Vec::<(usize, usize, f64)>::with_capacity(mesh.get_indices().len() * 4);
+        for triangle in get_triangles(mesh) {
+            for i in 0..3 {
+                let (corner, j, k) = (triangle[i], triangle[(i + 1) % 3], triangle[(i + 2) % 3]);
+                let u = [0, 1, 2].map(|c| points[j][c] - points[corner][c]);
+                let v = [0, 1, 2].map(|c| points[k][c] - points[corner][c]);
+                let cross = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+                let sine = (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt();
+                if sine <= f64::EPSILON {
+                    continue;
+                }
+
+                let weight = 0.5 * (u[0] * v[0] + u[1] * v[1] + u[2] * v[2]) / sine;
+                triplets.extend([(j, k, -weight), (k, j, -weight), (j, j, weight), (k, k, weight)]);
+            }
+        }
+        return SparseMatrix::from_triplets(vertex_count, vertex_count, &triplets);
+    }
+
+    /// Returns the area of each vertex, i.e. a third of the area of all triangles it belongs to
+    pub fn get_vertex_areas<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>) -> Vec<f64>
+    {
+        let points = mesh.get_vertices().iter().map(to_point).collect::<Vec<Point>>();
+        let mut areas = vec![0.0; points.len()];
+        for triangle in get_triangles(mesh) {
+            let [a, b, c] = triangle.map(|v| points[v]);
+            let u = [0, 1, 2].map(|i| b[i] - a[i]);
+            let v = [0, 1, 2].map(|i| c[i] - a[i]);
+            let cross = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+            let area = 0.5 * (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt();
+            triangle.iter().for_each(|v| areas[*v] += area / 3.0);
+        }
+        return areas;
+    }
+
+    /// Smooths a mesh by moving each vertex towards the average of its neighbours by the factor lambda in each iteration,
+    /// where a factor of one moves it onto the average. Removes noise quickly, but also shrinks the mesh.
+    ///
+    /// # Examples
+    /// ```
+    /// smooth_laplacian(&mut mesh, 10, 0.5, &SmoothingConstraints::new());
+    /// ```
+    pub fn smooth_laplacian<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, iterations : usize, lambda : f64, constraints : &SmoothingConstraints)
+    {
+        let neighbours = get_neighbours(mesh);
+        let movable = get_movable_vertices(mesh, constraints);
+        for _ in 0..iterations {
+            apply_uniform_laplacian(mesh.get_mut_vertices(), &neighbours, &movable, lambda);
+        }
+    }
+
+    /// Smooths a mesh with the λ|μ scheme of Taubin, which alternates a Laplacian step with the positive factor lambda
+    /// and one with the negative factor mu, such that the mesh hardly shrinks. The magnitude of mu should be slightly
+    /// larger than lambda, e.g. lambda = 0.5 and mu = -0.53.
+    ///
+    /// # Examples
+    /// ```
+    /// smooth_taubin(&mut mesh, 10, 0.5, -0.53, &SmoothingConstraints::new());
+    /// ```
+    pub fn smooth_taubin<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, iterations : usize, lambda : f64, mu : f64, constraints : &SmoothingConstraints)
+    {
+        let neighbours = get_neighbours(mesh);
+        let movable = get_movable_vertices(mesh, constraints);
+        for _ in 0..iterations {
+            apply_uniform_laplacian(mesh.get_mut_vertices(), &neighbours, &movable, lambda);
+            apply_uniform_laplacian(mesh.get_mut_vertices(), &neighbours, &movable, mu);
+        }
+    }
+
+    /// Smooths a mesh by mean curvature flow with implicit time steps, as proposed by Desbrun et al. Each iteration solves
+    /// (M + t L) x' = M x with the cotangent Laplacian L and the vertex areas M, which is stable for large time steps.
+    /// The time step is an area, e.g. the squared average edge length removes noise of about that size.
+    /// Unlike the uniform Laplacian, vertices are only moved along the normal of flat regions, so the triangles keep their shape.
+    ///
+    /// Returns false if a linear system could not be solved, in which case the vertices of the previous iterations are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// let edge_length = ...;
+    ///
+    /// smooth_cotangent(&mut mesh, 3, edge_length * edge_length, &SmoothingConstraints::new());
+    /// ```
+    pub fn smooth_cotangent<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, iterations : usize, time_step : f64, constraints : &SmoothingConstraints) -> bool
+    {
+        let movable = get_movable_vertices(mesh, constraints);
+        let free = (0..movable.len()).filter(|v| movable[*v]).collect::<Vec<usize>>();
+        let mut free_index = vec![usize::MAX; movable.len()];
+        free.iter().enumerate().for_each(|(i, v)| free_index[*v] = i);
+
+        for _ in 0..iterations {
+
+            let laplacian = create_cotangent_laplacian(mesh);
+            let areas = get_vertex_areas(mesh);
+            let points = mesh.get_vertices().iter().map(to_point).collect::<Vec<Point>>();
+
+            //Fixed vertices are moved to the right hand side
+            let mut triplets = Vec::<(usize, usize, f64)>::new();
+            let mut right_hand_sides = [0, 1, 2].map(|c| free.iter().map(|v| areas[*v] * points[*v][c]).collect::<Vec<f64>>());
+            for (i, v) in free.iter().enumerate() {
+                triplets.push((i, i, areas[*v]));
+                for (column, value) in laplacian.get_row(*v) {
+                    match free_index[column] {
+                        usize::MAX => (0..3).for_each(|c| right_hand_sides[c][i] -= time_step * value * points[column][c]),
+                        j => triplets.push((i, j, time_step * value)),
+                    }
+                }
+            }
+
+            let system = SparseMatrix::from_triplets(free.len(), free.len(), &triplets);
+            let max_iterations = 1000.max(4 * free.len());
+            let mut solution = Vec::with_capacity(3);
+            for right_hand_side in &right_hand_sides {
+                match system.solve_conjugate_gradient(right_hand_side, SOLVER_TOLERANCE, max_iterations) {
+                    Some(coordinates) => solution.push(coordinates),
+                    None => return false,
+                }
+            }
+
+            let vertices = mesh.get_mut_vertices();
+            for (i, v) in free.iter().enumerate() {
+                vertices[*v] = from_point([solution[0][i], solution[1][i], solution[2][i]]);
+            }
+        }
+        return true;
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::common_properties::AttributeMap;
+        use crate::fixtures::create_grid;
+        use crate::fixtures::create_noisy_grid;
+        use crate::fixtures::create_sphere;
+        use crate::mesh::Mesh;
+        use crate::mesh_smoothing::smoothing::SmoothingConstraints;
+        use crate::mesh_smoothing::smoothing::create_cotangent_laplacian;
+        use crate::mesh_smoothing::smoothing::smooth_cotangent;
+        use crate::mesh_smoothing::smoothing::smooth_laplacian;
+        use crate::mesh_smoothing::smoothing::smooth_taubin;
+        use crate::property_map::PropertyHandle;
+        use crate::property_map::VertexProperties;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::FloatVector;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+
+        fn get_max_height(mesh : &TriangleVertexMesh<Vec3f, f32, u32>, interior : impl Fn(&Vec3f) -> bool) -> f32 {
+            return mesh.get_vertices().iter().filter(|v| interior(v)).fold(0.0, |max, v| max.max(v.z().abs()));
+        }
+
+        fn get_mean_radius(mesh : &TriangleVertexMesh<Vec3f, f32, u32>) -> f32 {
+            return mesh.get_vertices().iter().map(|v| v.length()).sum::<f32>() / mesh.get_vertices().len() as f32;
+        }
+
+        #[test]
+        fn test_laplacian_and_taubin() {
+
+            let interior = |v : &Vec3f| v.x() > 0.0 && v.x() < 1.0 && v.y() > 0.0 && v.y() < 1.0;
+            let mut grid = create_noisy_grid::<Vec3f, f32>(10, 0.1);
+            let boundary = grid.get_vertices()[0];
+            let noise = get_max_height(&grid, interior);
+
+            smooth_laplacian(&mut grid, 10, 0.5, &SmoothingConstraints::new());
+            assert!(get_max_height(&grid, interior) < 0.5 * noise);
+            assert_eq!(grid.get_vertices()[0].z(), boundary.z());
+
+            //Taubin smoothing shrinks a sphere much less than Laplacian smoothing
+            let mut laplacian = create_sphere::<Vec3f, f32>(2, 1.0);
+            let mut taubin = create_sphere::<Vec3f, f32>(2, 1.0);
+            smooth_laplacian(&mut laplacian, 10, 0.5, &SmoothingConstraints::new());
+            smooth_taubin(&mut taubin, 10, 0.5, -0.53, &SmoothingConstraints::new());
+            assert!(get_mean_radius(&laplacian) < 0.95);
+            assert!((get_mean_radius(&taubin) - 1.0).abs() < 0.5 * (1.0 - get_mean_radius(&laplacian)));
+        }
+
+        #[test]
+        fn test_cotangent_smoothing() {
+
+            //Flat meshes are not changed by the cotangent Laplacian
+            let mut flat = create_grid::<Vec3f, f32>(6);
+            let laplacian = create_cotangent_laplacian(&flat);
+            let x = flat.get_vertices().iter().map(|v| v.x() as f64).collect::<Vec<f64>>();
+            assert!(laplacian.multiply(&x)[8].abs() < 1e-9);
+
+            let original = flat.get_vertices().clone();
+            assert!(smooth_cotangent(&mut flat, 2, 0.1, &SmoothingConstraints::new()));
+            for (v, vertex) in flat.get_vertices().iter().enumerate() {
+                assert!((vertex.x() - original[v].x()).abs() < 1e-5 && vertex.z().abs() < 1e-6);
+            }
+
+            let interior = |v : &Vec3f| v.x() > 0.0 && v.x() < 1.0 && v.y() > 0.0 && v.y() < 1.0;
+            let mut grid = create_noisy_grid::<Vec3f, f32>(10, 0.1);
+            let noise = get_max_height(&grid, interior);
+            assert!(smooth_cotangent(&mut grid, 1, 0.01, &SmoothingConstraints::new()));
+            assert!(get_max_height(&grid, interior) < 0.5 * noise);
+
+            //Only selected vertices are moved
+            let mut grid = create_noisy_grid::<Vec3f, f32>(10, 0.1);
+            let original = grid.get_vertices().clone();
+            let selection = PropertyHandle::<AttributeMap<bool>>::named("selection");
+            let selected = original.iter().map(|v| v.x() < 0.5).collect::<Vec<bool>>();
+            grid.add_named_vertex_property(&selection, AttributeMap::from(selected.clone()));
+
+            let mut constraints = SmoothingConstraints::new();
+            constraints.selection = Some(selection);
+            assert!(smooth_cotangent(&mut grid, 1, 0.01, &constraints));
+            for (v, vertex) in grid.get_vertices().iter().enumerate() {
+                assert!(selected[v] || vertex.z() == original[v].z());
+            }
+            assert!(grid.get_vertices().iter().zip(&original).any(|(a, b)| a.z() != b.z()));
+        }
+    }
+}
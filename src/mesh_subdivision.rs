@@ -0,0 +1,856 @@
+pub mod subdivision {
+
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::f64::consts::PI;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::common_properties::AttributeMap;
+    use crate::face_vertex_mesh::FaceVertexMesh;
+    use crate::mesh::Mesh;
+    use crate::mesh_edges::MeshEdges;
+    use crate::property_map::EdgeProperties;
+    use crate::property_map::ElementSources;
+    use crate::property_map::PropertyHandle;
+    use crate::property_map::PropertyMap;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Weight of each neighbour of a smooth vertex with the given valence, as proposed by Loop
+    fn get_loop_beta(valence : usize) -> f64 {
+
+        let n = valence as f64;
+        let c = 3.0 / 8.0 + 0.25 * (2.0 * PI / n).cos();
+        return (5.0 / 8.0 - c * c) / n;
+    }
+
+    /// Weighted sum of vertices, where the weights sum up to one
+    fn combine<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(vertices : &[T], stencil : &[(usize, f64)]) -> T {
+
+        let mut sum = T::zero();
+        for (v, weight) in stencil {
+            sum += vertices[*v] * U::from(*weight).unwrap();
+        }
+        return sum;
+    }
+
+    /// Result of one subdivision step, where the vertex and face sources refer to the elements before the step
+    struct SubdivisionStep<T, F, C> {
+        vertices : Vec<T>,
+        vertex_sources : Vec<ElementSources>,
+        faces : Vec<F>,
+        face_sources : Vec<ElementSources>,
+        creases : C,
+    }
+
+    /// Composes the sources of a step with the accumulated sources of the previous steps, such that the result refers to
+    /// the elements before the first step
+    fn compose_sources(step_sources : &[ElementSources], previous : &[ElementSources]) -> Vec<ElementSources> {
+
+        return step_sources.iter().map(|sources| {
+            let mut combined = ElementSources::new();
+            for (element, weight) in sources {
+                for (original, original_weight) in &previous[*element] {
+                    match combined.iter_mut().find(|(o, _)| o == original) {
+                        Some(source) => source.1 += weight * original_weight,
+                        None => combined.push((*original, weight * original_weight)),
+                    }
+                }
+            }
+            return combined;
+        }).collect();
+    }
+
+    /// Performs one step of Loop subdivision
+    fn subdivide_loop_step<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(vertices : &[T], triangles : &[[usize; 3]], creases : &HashSet<(usize, usize)>)
+        -> SubdivisionStep<T, [usize; 3], HashSet<(usize, usize)>>
+    {
+        let vertex_count = vertices.len();
+        let edges = MeshEdges::from_faces(triangles.iter());
+
+        //Vertices opposite to each edge in its triangles
+        let mut opposite = vec![Vec::<usize>::new(); edges.get_edge_count()];
+        for (f, triangle) in triangles.iter().enumerate() {
+            for (i, e) in edges.get_face_edges(f).iter().enumerate() {
+                opposite[*e].push(triangle[(i + 2) % 3]);
+            }
+        }
+
+        //Boundary and non-manifold edges are kept sharp like creases
+        let sharp = (0..edges.get_edge_count()).map(|e| {
+            let [a, b] = edges.get_edge(e);
+            return opposite[e].len() != 2 || creases.contains(&(a, b));
+        }).collect::<Vec<bool>>();
+
+        let mut neighbours = vec![Vec::<usize>::new(); vertex_count];
+        let mut sharp_neighbours = vec![Vec::<usize>::new(); vertex_count];
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            neighbours[*a].push(*b);
+            neighbours[*b].push(*a);
+            if sharp[e] {
+                sharp_neighbours[*a].push(*b);
+                sharp_neighbours[*b].push(*a);
+            }
+        }
+
+        let mut new_vertices = Vec::<T>::with_capacity(vertex_count + edges.get_edge_count());
+        let mut vertex_sources = Vec::<ElementSources>::with_capacity(vertex_count + edges.get_edge_count());
+
+        //Old vertices are moved by the smooth rule, the crease rule for two sharp edges, or kept at corners and darts
+        for v in 0..vertex_count {
+            let stencil = match sharp_neighbours[v].len() {
+                0 | 1 if !neighbours[v].is_empty() => {
+                    let beta = get_loop_beta(neighbours[v].len());
+                    let mut stencil = Vec::from([(v, 1.0 - beta * neighbours[v].len() as f64)]);
+                    stencil.extend(neighbours[v].iter().map(|n| (*n, beta)));
+                    stencil
+                },
+                2 => Vec::from([(v, 0.75), (sharp_neighbours[v][0], 0.125), (sharp_neighbours[v][1], 0.125)]),
+                _ => Vec::from([(v, 1.0)]),
+            };
+            new_vertices.push(combine(vertices, &stencil));
+            vertex_sources.push(Vec::from([(v, 1.0)]));
+        }
+
+        //Edge points are placed between the vertices of the edge and the opposite vertices of smooth edges
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            let stencil = match sharp[e] {
+                true => Vec::from([(*a, 0.5), (*b, 0.5)]),
+                false => Vec::from([(*a, 0.375), (*b, 0.375), (opposite[e][0], 0.125), (opposite[e][1], 0.125)]),
+            };
+            new_vertices.push(combine(vertices, &stencil));
+            vertex_sources.push(Vec::from([(*a, 0.5), (*b, 0.5)]));
+        }
+
+        let mut new_triangles = Vec::<[usize; 3]>::with_capacity(triangles.len() * 4);
+        for (f, [a, b, c]) in triangles.iter().enumerate() {
+            let face_edges = edges.get_face_edges(f);
+            let (ab, bc, ca) = (vertex_count + face_edges[0], vertex_count + face_edges[1], vertex_count + face_edges[2]);
+            new_triangles.extend([[*a, ab, ca], [*b, bc, ab], [*c, ca, bc], [ab, bc, ca]]);
+        }
+
+        let mut new_creases = HashSet::<(usize, usize)>::new();
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            if creases.contains(&(*a, *b)) {
+                new_creases.insert((*a, vertex_count + e));
+                new_creases.insert((*b, vertex_count + e));
+            }
+        }
+
+        return SubdivisionStep {
+            vertices: new_vertices,
+            vertex_sources,
+            face_sources: (0..new_triangles.len()).map(|f| Vec::from([(f / 4, 1.0)])).collect(),
+            faces: new_triangles,
+            creases: new_creases,
+        };
+    }
+
+    /// Subdivides a triangle mesh with Loop's scheme, where each iteration splits every triangle into four and smooths
+    /// the vertices. Boundary edges and the optional crease edges, given by an edge property map, stay sharp: vertices on
+    /// them only follow the curve of the sharp edges, while vertices with more than two sharp edges are kept in place.
+    ///
+    /// Vertex properties are interpolated linearly, i.e. new vertices on an edge receive the average of the edge's vertices,
+    /// so that e.g. texture coordinates are not distorted. Edge properties are kept on both halves of split edges, while the
+    /// new edges inside the triangles receive the default value of each map. Returns false and leaves the mesh unchanged,
+    /// if the new vertices cannot be addressed with the index type.
+    ///
+    /// # Examples
+    /// ```
+    /// let creases = PropertyHandle::<AttributeMap<bool>>::named("creases");
+    /// mesh.add_named_edge_property(&creases, AttributeMap::with_size(MeshEdges::from_mesh(&mesh).get_edge_count(), false));
+    ///
+    /// subdivide_loop(&mut mesh, 2, Some(&creases));
+    /// ```
+    pub fn subdivide_loop<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, iterations : usize, creases : Option<&PropertyHandle<AttributeMap<bool>>>) -> bool
+    {
+        let mut vertices = mesh.get_vertices().clone();
+        let mut triangles = mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect::<Vec<[usize; 3]>>();
+
+        let mut crease_edges = HashSet::<(usize, usize)>::new();
+        if let Some(crease_map) = creases.and_then(|handle| mesh.get_named_edge_property_ref(handle)) {
+            let edges = MeshEdges::from_faces(triangles.iter());
+            if crease_map.len() == edges.get_edge_count() {
+                crease_edges = (0..edges.get_edge_count()).filter(|e| crease_map.get(*e)).map(|e| {
+                    let [a, b] = edges.get_edge(e);
+                    return (a, b);
+                }).collect();
+            }
+        }
+
+        //Vertex sources of all iterations are accumulated, such that each final vertex refers to the original ones
+        let mut vertex_sources = (0..vertices.len()).map(|v| Vec::from([(v, 1.0)])).collect::<Vec<ElementSources>>();
+        let mut face_sources = (0..triangles.len()).map(|f| Vec::from([(f, 1.0)])).collect::<Vec<ElementSources>>();
+
+        for _ in 0..iterations {
+
+            let step = subdivide_loop_step(&vertices, &triangles, &crease_edges);
+            if <IndexType as NumCast>::from(step.vertices.len()).is_none() {
+                return false;
+            }
+
+            vertex_sources = compose_sources(&step.vertex_sources, &vertex_sources);
+            face_sources = compose_sources(&step.face_sources, &face_sources);
+
+            vertices = step.vertices;
+            triangles = step.faces;
+            crease_edges = step.creases;
+        }
+
+        if iterations == 0 {
+            return true;
+        }
+
+        let indices = triangles.iter().flat_map(|t| t.iter().map(|v| NumCast::from(*v).unwrap())).collect::<Vec<IndexType>>();
+        mesh.replace(vertices, &vertex_sources, indices, &face_sources, None);
+        return true;
+    }
+
+    /// Mixes two stencils, such that t = 0 gives the first and t = 1 the second
+    fn mix_stencils(first : &[(usize, f64)], second : &[(usize, f64)], t : f64) -> Vec<(usize, f64)> {
+
+        let mut stencil = first.iter().map(|(v, weight)| (*v, weight * (1.0 - t))).collect::<Vec<(usize, f64)>>();
+        stencil.extend(second.iter().map(|(v, weight)| (*v, weight * t)));
+        return stencil;
+    }
+
+    /// Reads the faces of a polygon mesh as lists of vertex indices
+    fn get_polygons<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &FaceVertexMesh<T, U, IndexType>)
+        -> Vec<Vec<usize>>
+    {
+        return (0..mesh.get_face_count()).map(|f| {
+            return mesh.get_face(NumCast::from(f).unwrap()).iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>();
+        }).collect();
+    }
+
+    /// Performs one step of Catmull-Clark subdivision, where edges have a crease sharpness. Boundary and non-manifold
+    /// edges are infinitely sharp.
+    fn subdivide_catmull_clark_step<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(vertices : &[T], faces : &[Vec<usize>],
+        sharpness : &HashMap<(usize, usize), f32>) -> SubdivisionStep<T, Vec<usize>, HashMap<(usize, usize), f32>>
+    {
+        let vertex_count = vertices.len();
+        let face_count = faces.len();
+        let edges = MeshEdges::from_faces(faces.iter());
+
+        let mut edge_faces = vec![Vec::<usize>::new(); edges.get_edge_count()];
+        let mut vertex_faces = vec![Vec::<usize>::new(); vertex_count];
+        for (f, face) in faces.iter().enumerate() {
+            for e in edges.get_face_edges(f) {
+                edge_faces[*e].push(f);
+            }
+            for v in face {
+                vertex_faces[*v].push(f);
+            }
+        }
+
+        let edge_sharpness = (0..edges.get_edge_count()).map(|e| {
+            let [a, b] = edges.get_edge(e);
+            return match edge_faces[e].len() {
+                2 => sharpness.get(&(a, b)).map(|s| *s as f64).unwrap_or(0.0).max(0.0),
+                _ => f64::INFINITY,
+            };
+        }).collect::<Vec<f64>>();
+
+        let mut vertex_edges = vec![Vec::<usize>::new(); vertex_count];
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            vertex_edges[*a].push(e);
+            vertex_edges[*b].push(e);
+        }
+
+        let face_stencils = faces.iter().map(|face| {
+            return face.iter().map(|v| (*v, 1.0 / face.len() as f64)).collect::<Vec<(usize, f64)>>();
+        }).collect::<Vec<Vec<(usize, f64)>>>();
+
+        let total = vertex_count + face_count + edges.get_edge_count();
+        let mut new_vertices = Vec::<T>::with_capacity(total);
+        let mut vertex_sources = Vec::<ElementSources>::with_capacity(total);
+
+        //Old vertices are moved by the smooth rule, the crease rule for two sharp edges or kept at corners. Semi-sharp
+        //vertices, whose sharp edges have an average sharpness below one, blend the sharp rule with the smooth one.
+        for v in 0..vertex_count {
+
+            let valence = vertex_edges[v].len();
+            let mut smooth = Vec::from([(v, (valence as f64 - 3.0) / valence as f64)]);
+            for f in &vertex_faces[v] {
+                let weight = 1.0 / (valence * vertex_faces[v].len()) as f64;
+                smooth.extend(face_stencils[*f].iter().map(|(w, face_weight)| (*w, face_weight * weight)));
+            }
+            for e in &vertex_edges[v] {
+                let weight = 1.0 / (valence * valence) as f64;
+                smooth.extend(edges.get_edge(*e).iter().map(|w| (*w, weight)));
+            }
+
+            let sharp_edges = vertex_edges[v].iter().filter(|e| edge_sharpness[**e] > 0.0).copied().collect::<Vec<usize>>();
+            let sharp = match sharp_edges.len() {
+                0 | 1 => None,
+                2 => {
+                    let mut stencil = Vec::from([(v, 0.75)]);
+                    stencil.extend(sharp_edges.iter().map(|e| (edges.get_edge(*e).into_iter().find(|w| *w != v).unwrap(), 0.125)));
+                    Some(stencil)
+                },
+                _ => Some(Vec::from([(v, 1.0)])),
+            };
+
+            let stencil = match (valence, sharp) {
+                (0, _) => Vec::from([(v, 1.0)]),
+                (_, None) => smooth,
+                (_, Some(sharp)) => {
+                    let vertex_sharpness = sharp_edges.iter().map(|e| edge_sharpness[*e]).sum::<f64>() / sharp_edges.len() as f64;
+                    match vertex_sharpness >= 1.0 {
+                        true => sharp,
+                        false => mix_stencils(&smooth, &sharp, vertex_sharpness),
+                    }
+                },
+            };
+            new_vertices.push(combine(vertices, &stencil));
+            vertex_sources.push(Vec::from([(v, 1.0)]));
+        }
+
+        for stencil in &face_stencils {
+            new_vertices.push(combine(vertices, stencil));
+            vertex_sources.push(stencil.clone());
+        }
+
+        //Edge points of smooth edges are the average of the edge's vertices and the adjacent face points, while those of
+        //sharp edges are the midpoints
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+
+            let midpoint = Vec::from([(*a, 0.5), (*b, 0.5)]);
+            let stencil = match edge_sharpness[e] >= 1.0 {
+                true => midpoint.clone(),
+                false => {
+                    let mut smooth = Vec::from([(*a, 0.25), (*b, 0.25)]);
+                    for f in &edge_faces[e] {
+                        smooth.extend(face_stencils[*f].iter().map(|(w, weight)| (*w, weight * 0.25)));
+                    }
+                    mix_stencils(&smooth, &midpoint, edge_sharpness[e])
+                },
+            };
+            new_vertices.push(combine(vertices, &stencil));
+            vertex_sources.push(midpoint);
+        }
+
+        //Each face is split into quads, one for each corner
+        let mut new_faces = Vec::<Vec<usize>>::new();
+        let mut face_sources = Vec::<ElementSources>::new();
+        for (f, face) in faces.iter().enumerate() {
+            let face_edges = edges.get_face_edges(f);
+            for i in 0..face.len() {
+                let previous = face_edges[(i + face.len() - 1) % face.len()];
+                new_faces.push(Vec::from([face[i], vertex_count + face_count + face_edges[i], vertex_count + f, vertex_count + face_count + previous]));
+                face_sources.push(Vec::from([(f, 1.0)]));
+            }
+        }
+
+        //Both halves of semi-sharp edges lose one level of sharpness
+        let mut new_sharpness = HashMap::<(usize, usize), f32>::new();
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            if edge_sharpness[e].is_finite() && edge_sharpness[e] > 1.0 {
+                let edge_point = vertex_count + face_count + e;
+                new_sharpness.insert((*a, edge_point), (edge_sharpness[e] - 1.0) as f32);
+                new_sharpness.insert((*b, edge_point), (edge_sharpness[e] - 1.0) as f32);
+            }
+        }
+
+        return SubdivisionStep {
+            vertices: new_vertices,
+            vertex_sources,
+            faces: new_faces,
+            face_sources,
+            creases: new_sharpness,
+        };
+    }
+
+    /// Subdivides a polygon mesh with the scheme of Catmull and Clark, where each iteration splits every face with n
+    /// corners into n quads and smooths the vertices. Boundary edges stay sharp, such that the boundary follows a cubic
+    /// B-spline curve.
+    ///
+    /// The optional crease map gives the sharpness of each edge, following DeRose et al.: edges with a sharpness of at least
+    /// one are subdivided like boundary edges, and both halves keep the sharpness reduced by one, while fractional values
+    /// blend the sharp and the smooth rule. After subdividing, the map contains the remaining sharpness of the new edges.
+    ///
+    /// Vertex properties are interpolated linearly, i.e. face points receive the average of the face's vertices and edge
+    /// points the average of the edge's vertices. Edge properties are kept on both halves of split edges, while the new edges
+    /// inside the faces receive the default value of each map. Returns false and leaves the mesh unchanged, if the new
+    /// vertices cannot be addressed with the index type.
+    ///
+    /// # Examples
+    /// ```
+    /// let sharpness = PropertyHandle::<AttributeMap<f32>>::named("crease sharpness");
+    /// mesh.add_named_edge_property(&sharpness, AttributeMap::with_size(MeshEdges::from_mesh(&mesh).get_edge_count(), 0.0));
+    /// mesh.get_named_edge_property(&sharpness).unwrap().set(edge, 2.5);
+    ///
+    /// subdivide_catmull_clark(&mut mesh, 3, Some(&sharpness));
+    /// ```
+    pub fn subdivide_catmull_clark<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut FaceVertexMesh<T, U, IndexType>, iterations : usize, creases : Option<&PropertyHandle<AttributeMap<f32>>>) -> bool
+    {
+        let mut vertices = mesh.get_vertices().clone();
+        let mut faces = get_polygons(mesh);
+
+        let mut sharpness = HashMap::<(usize, usize), f32>::new();
+        if let Some(crease_map) = creases.and_then(|handle| mesh.get_named_edge_property_ref(handle)) {
+            let edges = MeshEdges::from_faces(faces.iter());
+            if crease_map.len() == edges.get_edge_count() {
+                sharpness = (0..edges.get_edge_count()).filter(|e| crease_map.get(*e) > 0.0).map(|e| {
+                    let [a, b] = edges.get_edge(e);
+                    return ((a, b), crease_map.get(e));
+                }).collect();
+            }
+        }
+
+        let mut vertex_sources = (0..vertices.len()).map(|v| Vec::from([(v, 1.0)])).collect::<Vec<ElementSources>>();
+        let mut face_sources = (0..faces.len()).map(|f| Vec::from([(f, 1.0)])).collect::<Vec<ElementSources>>();
+
+        for _ in 0..iterations {
+
+            let step = subdivide_catmull_clark_step(&vertices, &faces, &sharpness);
+            if <IndexType as NumCast>::from(step.vertices.len()).is_none() {
+                return false;
+            }
+
+            vertex_sources = compose_sources(&step.vertex_sources, &vertex_sources);
+            face_sources = compose_sources(&step.face_sources, &face_sources);
+
+            vertices = step.vertices;
+            faces = step.faces;
+            sharpness = step.creases;
+        }
+
+        if iterations == 0 {
+            return true;
+        }
+
+        let indices = faces.iter().map(|face| face.iter().map(|v| NumCast::from(*v).unwrap()).collect()).collect::<Vec<Vec<IndexType>>>();
+        mesh.replace(vertices, &vertex_sources, &indices, &face_sources);
+
+        //The halves of split edges carry the original sharpness, which is replaced by the remaining one
+        if let Some(crease_map) = creases.and_then(|handle| mesh.get_named_edge_property(handle)) {
+            let edges = MeshEdges::from_faces(faces.iter());
+            if crease_map.len() == edges.get_edge_count() {
+                for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+                    crease_map.set(e, sharpness.get(&(*a, *b)).copied().unwrap_or(0.0));
+                }
+            }
+        }
+        return true;
+    }
+
+    /// Performs one step of Doo-Sabin subdivision. Each corner of a face becomes a new vertex, which are connected to
+    /// new faces for each old face, interior edge and interior vertex. Boundary vertices receive no face, as the corners
+    /// around them do not form a closed ring.
+    fn subdivide_doo_sabin_step<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(vertices : &[T], faces : &[Vec<usize>])
+        -> SubdivisionStep<T, Vec<usize>, ()>
+    {
+        let edges = MeshEdges::from_faces(faces.iter());
+        let mut edge_face_count = vec![0; edges.get_edge_count()];
+        for f in 0..faces.len() {
+            edges.get_face_edges(f).iter().for_each(|e| edge_face_count[*e] += 1);
+        }
+        let is_boundary = |a : usize, b : usize| -> bool {
+            return edges.find_edge(a, b).map(|e| edge_face_count[e] != 2).unwrap_or(true);
+        };
+        let mut is_boundary_vertex = vec![false; vertices.len()];
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            if edge_face_count[e] != 2 {
+                is_boundary_vertex[*a] = true;
+                is_boundary_vertex[*b] = true;
+            }
+        }
+
+        //Faces containing each directed edge, and the corners of each vertex
+        let mut directed_edges = HashMap::<(usize, usize), usize>::new();
+        let mut vertex_corners = vec![Vec::<(usize, usize)>::new(); vertices.len()];
+        let mut corner_offsets = Vec::<usize>::with_capacity(faces.len());
+        let mut corner_count = 0;
+        for (f, face) in faces.iter().enumerate() {
+            for (i, v) in face.iter().enumerate() {
+                directed_edges.insert((*v, face[(i + 1) % face.len()]), f);
+                vertex_corners[*v].push((f, i));
+            }
+            corner_offsets.push(corner_count);
+            corner_count += face.len();
+        }
+        let find_corner = |f : usize, v : usize| -> Option<usize> {
+            return faces[f].iter().position(|w| *w == v).map(|i| corner_offsets[f] + i);
+        };
+
+        //Corner points are weighted averages of the face's vertices, as proposed by Doo and Sabin. Corners on the boundary
+        //are placed on the boundary edge with Chaikin's rule, or kept in place if both of their edges are on the boundary.
+        let mut new_vertices = Vec::<T>::with_capacity(corner_count);
+        let mut vertex_sources = Vec::<ElementSources>::with_capacity(corner_count);
+        for face in faces {
+            let n = face.len();
+            for i in 0..n {
+                let (previous, v, next) = (face[(i + n - 1) % n], face[i], face[(i + 1) % n]);
+                let stencil = match (is_boundary(previous, v), is_boundary(v, next)) {
+                    (true, true) => Vec::from([(v, 1.0)]),
+                    (true, false) => Vec::from([(v, 0.75), (previous, 0.25)]),
+                    (false, true) => Vec::from([(v, 0.75), (next, 0.25)]),
+                    (false, false) => (0..n).map(|j| {
+                        let weight = match j {
+                            0 => 0.25 + 5.0 / (4.0 * n as f64),
+                            _ => (3.0 + 2.0 * (2.0 * PI * j as f64 / n as f64).cos()) / (4.0 * n as f64),
+                        };
+                        return (face[(i + j) % n], weight);
+                    }).collect(),
+                };
+                new_vertices.push(combine(vertices, &stencil));
+                vertex_sources.push(stencil);
+            }
+        }
+
+        let mut new_faces = Vec::<Vec<usize>>::new();
+        let mut face_sources = Vec::<ElementSources>::new();
+
+        //Faces shrunk to their corner points
+        for (f, face) in faces.iter().enumerate() {
+            new_faces.push((0..face.len()).map(|i| corner_offsets[f] + i).collect());
+            face_sources.push(Vec::from([(f, 1.0)]));
+        }
+
+        //Quads between the corner points of the two faces of each interior edge
+        for (e, [a, b]) in edges.get_edges().iter().enumerate() {
+            if edge_face_count[e] != 2 {
+                continue;
+            }
+            let (Some(f), Some(g)) = (directed_edges.get(&(*a, *b)).copied(), directed_edges.get(&(*b, *a)).copied()) else {
+                continue;
+            };
+            let corners = [find_corner(g, *a), find_corner(g, *b), find_corner(f, *b), find_corner(f, *a)];
+            if corners.iter().all(|c| c.is_some()) {
+                new_faces.push(corners.iter().map(|c| c.unwrap()).collect());
+                face_sources.push(Vec::from([(f, 0.5), (g, 0.5)]));
+            }
+        }
+
+        //Faces connecting the corner points around each interior vertex, whose ring of faces is traversed by crossing the
+        //previous edge of each face
+        for (v, corners) in vertex_corners.iter().enumerate() {
+
+            if is_boundary_vertex[v] {
+                continue;
+            }
+            let Some(start) = corners.first() else {
+                continue;
+            };
+
+            let mut fan = Vec::from([*start]);
+            loop {
+                let (f, i) = *fan.last().unwrap();
+                let previous = faces[f][(i + faces[f].len() - 1) % faces[f].len()];
+                let Some(g) = directed_edges.get(&(v, previous)).copied() else {
+                    break;
+                };
+                let Some(j) = faces[g].iter().position(|w| *w == v) else {
+                    break;
+                };
+                if (g, j) == *start || fan.len() > corners.len() {
+                    break;
+                }
+                fan.push((g, j));
+            }
+
+            if fan.len() >= 3 {
+                new_faces.push(fan.iter().map(|(f, i)| corner_offsets[*f] + i).collect());
+                face_sources.push(fan.iter().map(|(f, _)| (*f, 1.0 / fan.len() as f64)).collect());
+            }
+        }
+
+        return SubdivisionStep {
+            vertices: new_vertices,
+            vertex_sources,
+            faces: new_faces,
+            face_sources,
+            creases: (),
+        };
+    }
+
+    /// Subdivides a polygon mesh with the scheme of Doo and Sabin, where each iteration replaces every face by a smaller
+    /// copy made of new points near its corners, and connects these copies by new faces for each interior edge and
+    /// interior vertex. The surface converges to a quadratic B-spline surface. Boundary corners are placed on the boundary edges with
+    /// Chaikin's rule, so that the boundary converges to a quadratic B-spline curve, while corners of faces with two
+    /// boundary edges stay in place. Creases are not supported, since the new points never lie on the old edges.
+    ///
+    /// Vertex properties are interpolated with the same weights as the positions. The faces made of a shrunk face keep its
+    /// properties, while the others blend the properties of their adjacent faces. New edges on boundary edges keep their
+    /// properties, while all other edges receive the default value of each map. Returns false and leaves the mesh unchanged,
+    /// if the new vertices cannot be addressed with the index type.
+    ///
+    /// # Examples
+    /// ```
+    /// subdivide_doo_sabin(&mut mesh, 2);
+    /// ```
+    pub fn subdivide_doo_sabin<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut FaceVertexMesh<T, U, IndexType>, iterations : usize) -> bool
+    {
+        let mut vertices = mesh.get_vertices().clone();
+        let mut faces = get_polygons(mesh);
+
+        let mut vertex_sources = (0..vertices.len()).map(|v| Vec::from([(v, 1.0)])).collect::<Vec<ElementSources>>();
+        let mut face_sources = (0..faces.len()).map(|f| Vec::from([(f, 1.0)])).collect::<Vec<ElementSources>>();
+
+        for _ in 0..iterations {
+
+            let step = subdivide_doo_sabin_step(&vertices, &faces);
+            if <IndexType as NumCast>::from(step.vertices.len()).is_none() {
+                return false;
+            }
+
+            vertex_sources = compose_sources(&step.vertex_sources, &vertex_sources);
+            face_sources = compose_sources(&step.face_sources, &face_sources);
+
+            vertices = step.vertices;
+            faces = step.faces;
+        }
+
+        if iterations == 0 {
+            return true;
+        }
+
+        let indices = faces.iter().map(|face| face.iter().map(|v| NumCast::from(*v).unwrap()).collect()).collect::<Vec<Vec<IndexType>>>();
+        mesh.replace(vertices, &vertex_sources, &indices, &face_sources);
+        return true;
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::common_properties::AttributeMap;
+        use crate::common_properties::UvMap;
+        use crate::face_vertex_mesh::FaceVertexMesh;
+        use crate::fixtures::create_box;
+        use crate::fixtures::create_octahedron;
+        use crate::mesh::Mesh;
+        use crate::mesh_edges::MeshEdges;
+        use crate::mesh_subdivision::subdivision::subdivide_catmull_clark;
+        use crate::mesh_subdivision::subdivision::subdivide_doo_sabin;
+        use crate::mesh_subdivision::subdivision::subdivide_loop;
+        use crate::property_map::EdgeProperties;
+        use crate::property_map::PropertyHandle;
+        use crate::property_map::PropertyMap;
+        use crate::property_map::PropertyType;
+        use crate::property_map::VertexProperties;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::FloatVector;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+        use crate::vector::Vector;
+
+        #[test]
+        fn test_subdivide_closed_mesh() {
+
+            let mut octahedron = create_octahedron::<Vec3f, f32>();
+            assert!(subdivide_loop(&mut octahedron, 2, None));
+
+            assert_eq!(octahedron.get_face_count(), 8 * 16);
+            assert_eq!(octahedron.get_vertices().len(), 66);
+
+            //The smooth surface shrinks towards the center, but stays symmetric
+            for v in octahedron.get_vertices() {
+                assert!(v.length() < 1.0 && v.length() > 0.4);
+            }
+            let first = octahedron.get_vertices()[0];
+            assert!((first.x() - octahedron.get_vertices()[2].y()).abs() < 1e-6);
+        }
+
+        #[test]
+        fn test_subdivide_with_boundary_and_creases() {
+
+            //A square made of two triangles
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(1.0, 1.0, 0.0), Vec3f::new(0.0, 1.0, 0.0)]);
+
+            let mut square = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, Vec::from([0, 1, 2, 0, 2, 3])).unwrap();
+            square.add_vertex_property(UvMap::from(Vec::from([[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]])));
+
+            let creases = PropertyHandle::<AttributeMap<bool>>::named("creases");
+            square.add_named_edge_property(&creases, AttributeMap::with_size(5, false));
+            for e in 0..5 {
+                square.get_named_edge_property(&creases).unwrap().set(e, true);
+            }
+
+            assert!(subdivide_loop(&mut square, 1, Some(&creases)));
+            assert_eq!(square.get_vertices().len(), 9);
+
+            //All corners have two boundary edges and the diagonal as crease, so they stay in place
+            assert_eq!(square.get_vertices()[0].x(), 0.0);
+            assert_eq!(square.get_vertices()[2].y(), 1.0);
+
+            //The midpoint of the diagonal is a crease vertex, which is not pulled to the opposite corners
+            let edges = MeshEdges::from_mesh(&square);
+            let uvs = square.get_vertex_property_ref::<UvMap<f32>, Vec<[f32; 2]>>(PropertyType::UV).unwrap();
+            let diagonal = (4..9).find(|v| uvs.get_uv(*v, 0) == [0.5, 0.5]).unwrap();
+            assert!((square.get_vertices()[diagonal].x() - 0.5).abs() < 1e-6);
+
+            //Halves of crease edges remain creases, while the new inner edges receive the default value
+            let crease_map = square.get_named_edge_property_ref(&creases).unwrap();
+            assert_eq!(crease_map.len(), edges.get_edge_count());
+            assert!(crease_map.get(edges.find_edge(0, diagonal).unwrap()));
+            assert_eq!(crease_map.as_slice().iter().filter(|crease| **crease).count(), 10);
+
+            //Without creases, the diagonal is smooth and its midpoint is pulled towards the other corners
+            let mut smooth = TriangleVertexMesh::<Vec3f, f32, u32>::from(Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(1.0, 1.0, 0.0), Vec3f::new(0.0, 1.0, 1.0)]), Vec::from([0, 1, 2, 0, 2, 3])).unwrap();
+            let midpoint = 4 + MeshEdges::from_mesh(&smooth).find_edge(0, 2).unwrap();
+            assert!(subdivide_loop(&mut smooth, 1, None));
+            assert!((smooth.get_vertices()[midpoint].z() - 0.125).abs() < 1e-6);
+        }
+        /// Cube around the origin with outward facing quads
+        fn create_cube() -> FaceVertexMesh<Vec3f, f32, u32> {
+            return create_box(Vec3f::new(-1.0, -1.0, -1.0), Vec3f::new(1.0, 1.0, 1.0));
+        }
+
+        /// Checks that all faces of a convex mesh around the origin face outwards
+        fn assert_outward_faces(mesh : &FaceVertexMesh<Vec3f, f32, u32>) {
+
+            for f in 0..mesh.get_face_count() {
+                let face = mesh.get_face(f as u32).iter().map(|v| mesh.get_vertices()[*v as usize]).collect::<Vec<Vec3f>>();
+                let mut normal = Vec3f::zero();
+                let mut centroid = Vec3f::zero();
+                for i in 0..face.len() {
+                    normal += Vec3f::cross(face[i], face[(i + 1) % face.len()]);
+                    centroid += face[i];
+                }
+                assert!(Vec3f::dot(&normal, &centroid) > 0.0);
+            }
+        }
+
+        #[test]
+        fn test_catmull_clark() {
+
+            let mut cube = create_cube();
+            assert!(subdivide_catmull_clark(&mut cube, 2, None));
+
+            assert_eq!(cube.get_face_count(), 6 * 16);
+            assert_eq!(cube.get_vertices().len(), 98);
+            assert!((0..cube.get_face_count()).all(|f| cube.get_face(f as u32).len() == 4));
+            assert_outward_faces(&cube);
+
+            //All vertices move inside, while the corners stay symmetric
+            let corner = cube.get_vertices()[7];
+            assert!((corner.x() - corner.z()).abs() < 1e-6);
+            assert!(cube.get_vertices().iter().all(|v| v.x().abs() < 1.0 && v.y().abs() < 1.0 && v.z().abs() < 1.0));
+        }
+
+        #[test]
+        fn test_catmull_clark_creases() {
+
+            let sharpness = PropertyHandle::<AttributeMap<f32>>::named("sharpness");
+
+            //All edges infinitely sharp keep the cube's shape
+            let mut sharp = create_cube();
+            sharp.add_named_edge_property(&sharpness, AttributeMap::with_size(12, 10.0));
+            assert!(subdivide_catmull_clark(&mut sharp, 1, Some(&sharpness)));
+            assert!(sharp.get_vertices().iter().all(|v| v.x().abs() == 1.0 || v.y().abs() == 1.0 || v.z().abs() == 1.0));
+            assert_eq!(sharp.get_vertices()[7].x(), 1.0);
+
+            //Both halves of each edge keep the remaining sharpness, while the new inner edges are smooth
+            let remaining = sharp.get_named_edge_property_ref(&sharpness).unwrap();
+            assert_eq!(remaining.len(), 48);
+            assert_eq!(remaining.as_slice().iter().filter(|s| **s == 9.0).count(), 24);
+            assert_eq!(remaining.as_slice().iter().filter(|s| **s == 0.0).count(), 24);
+
+            //A semi-sharp crease lies between the smooth and the sharp result
+            let corner_of = |edge_sharpness : f32| -> f32 {
+                let mut cube = create_cube();
+                cube.add_named_edge_property(&sharpness, AttributeMap::with_size(12, edge_sharpness));
+                assert!(subdivide_catmull_clark(&mut cube, 1, Some(&sharpness)));
+                return cube.get_vertices()[7].x();
+            };
+            let (smooth, semi_sharp) = (corner_of(0.0), corner_of(0.5));
+            assert!(smooth < semi_sharp && semi_sharp < 1.0);
+            assert!((semi_sharp - 0.5 * (smooth + 1.0)).abs() < 1e-6);
+        }
+
+        #[test]
+        fn test_catmull_clark_boundary() {
+
+            //Two quads in a row, with texture coordinates
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(2.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0), Vec3f::new(1.0, 1.0, 0.0), Vec3f::new(2.0, 1.0, 0.0)]);
+            let mut strip = FaceVertexMesh::<Vec3f, f32, u32>::from(vertices, Vec::from([Vec::from([0, 1, 4, 3]), Vec::from([1, 2, 5, 4])])).unwrap();
+            strip.add_vertex_property(UvMap::from(Vec::from([[0.0f32, 0.0], [0.5, 0.0], [1.0, 0.0], [0.0, 1.0], [0.5, 1.0], [1.0, 1.0]])));
+
+            assert!(subdivide_catmull_clark(&mut strip, 1, None));
+            assert_eq!(strip.get_face_count(), 8);
+            assert_eq!(strip.get_vertices().len(), 15);
+
+            //Boundary vertices only follow the boundary curve
+            assert!(strip.get_vertices().iter().all(|v| v.z() == 0.0));
+            assert!((strip.get_vertices()[1].x() - 1.0).abs() < 1e-6 && strip.get_vertices()[1].y() == 0.0);
+            assert!((strip.get_vertices()[0].x() - 0.125).abs() < 1e-6);
+
+            //Face points receive the average texture coordinates of their face
+            let uvs = strip.get_vertex_property_ref::<UvMap<f32>, Vec<[f32; 2]>>(PropertyType::UV).unwrap();
+            assert_eq!(uvs.get_uv(6, 0), [0.25, 0.5]);
+        }
+
+        #[test]
+        fn test_doo_sabin() {
+
+            let mut cube = create_cube();
+            assert!(subdivide_doo_sabin(&mut cube, 1));
+
+            //Shrunk faces, quads for the edges and triangles for the corners
+            assert_eq!(cube.get_vertices().len(), 24);
+            assert_eq!(cube.get_face_count(), 6 + 12 + 8);
+            assert_eq!((0..cube.get_face_count()).filter(|f| cube.get_face(*f as u32).len() == 3).count(), 8);
+            assert_outward_faces(&cube);
+            assert!(cube.get_vertices().iter().all(|v| [v.x(), v.y(), v.z()].iter().filter(|c| c.abs() == 0.5).count() == 2));
+
+            assert!(subdivide_doo_sabin(&mut cube, 1));
+            assert_eq!(cube.get_vertices().len(), 4 * 26 - 8);
+            assert_outward_faces(&cube);
+
+            //On a boundary, corners of a single face stay in place and other corners follow Chaikin's rule
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(2.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0), Vec3f::new(1.0, 1.0, 0.0), Vec3f::new(2.0, 1.0, 0.0)]);
+            let mut strip = FaceVertexMesh::<Vec3f, f32, u32>::from(vertices, Vec::from([Vec::from([0, 1, 4, 3]), Vec::from([1, 2, 5, 4])])).unwrap();
+            assert!(subdivide_doo_sabin(&mut strip, 1));
+
+            assert_eq!(strip.get_face_count(), 3);
+            assert_eq!(strip.get_vertices().len(), 8);
+            assert_eq!(strip.get_vertices()[0].x(), 0.0);
+            assert_eq!(strip.get_vertices()[1].x(), 0.75);
+            assert_eq!(strip.get_vertices()[1].y(), 0.0);
+        }
+
+        #[test]
+        fn test_doo_sabin_open_grid() {
+
+            //Grid of 3x3 quads, from which the quad at the top right corner is removed
+            let vertices = (0..16).map(|v| Vec3f::new((v % 4) as f32, (v / 4) as f32, 0.0)).collect::<Vec<Vec3f>>();
+            let faces = (0..9).filter(|q| *q != 8).map(|q| {
+                let v = q % 3 + q / 3 * 4;
+                return Vec::from([v, v + 1, v + 5, v + 4]);
+            }).collect::<Vec<Vec<u32>>>();
+            let mut grid = FaceVertexMesh::<Vec3f, f32, u32>::from(vertices, faces).unwrap();
+            assert!(subdivide_doo_sabin(&mut grid, 1));
+
+            //Shrunk faces and quads for the interior edges, but only the three interior vertices receive a face, while the
+            //boundary vertex at the inner corner with three faces does not
+            assert_eq!(grid.get_face_count(), 8 + 10 + 3);
+            assert_eq!((0..grid.get_face_count()).filter(|f| grid.get_face(*f as u32).len() == 3).count(), 0);
+
+            let edges = MeshEdges::from_mesh(&grid);
+            let mut edge_face_count = vec![0; edges.get_edge_count()];
+            (0..grid.get_face_count()).for_each(|f| edges.get_face_edges(f).iter().for_each(|e| edge_face_count[*e] += 1));
+            assert!(edge_face_count.iter().all(|c| *c <= 2));
+        }
+    }
+}
@@ -0,0 +1,301 @@
+pub mod mesh_tangents {
+
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::common_properties::NormalMap;
+    use crate::common_properties::TangentMap;
+    use crate::disjoint_set::DisjointSet;
+    use crate::mesh::Mesh;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
+    use crate::triangle_vertex_mesh::TriangleVertexMesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// First order tangent of a triangle, pointing in the direction of increasing u
+    struct TriangleFrame<T> {
+        tangent : T,
+        orientation_preserving : bool,
+        /// Triangles without texture space area take the orientation of their neighbours
+        group_with_any : bool,
+    }
+
+    /// Removes the component along the normal and normalizes the result, or returns zero if nothing remains
+    fn project<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(vector : T, normal : T) -> T {
+        return (vector - normal * T::dot(&normal, &vector)).normalize().unwrap_or(T::zero());
+    }
+
+    fn create_triangle_frames<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(
+        vertices : &[T], triangles : &[[usize; 3]], texture_coordinates : &[[U; 2]]) -> Vec<TriangleFrame<T>>
+    {
+        let mut frames = Vec::<TriangleFrame<T>>::with_capacity(triangles.len());
+
+        for [a, b, c] in triangles.iter().copied() {
+
+            let d1 = vertices[b] - vertices[a];
+            let d2 = vertices[c] - vertices[a];
+            let t21 = [texture_coordinates[b][0] - texture_coordinates[a][0], texture_coordinates[b][1] - texture_coordinates[a][1]];
+            let t31 = [texture_coordinates[c][0] - texture_coordinates[a][0], texture_coordinates[c][1] - texture_coordinates[a][1]];
+
+            let signed_area = t21[0] * t31[1] - t21[1] * t31[0];
+            let orientation_preserving = signed_area > U::zero();
+            let sign = if orientation_preserving { U::one() } else { -U::one() };
+
+            let tangent = d1 * t31[1] - d2 * t21[1];
+            let tangent = if signed_area != U::zero() { tangent.normalize().map(|t| t * sign).unwrap_or(T::zero()) } else { T::zero() };
+
+            frames.push(TriangleFrame { tangent, orientation_preserving, group_with_any: signed_area == U::zero() });
+        }
+
+        return frames;
+    }
+
+    /// Creates a tangent for each triangle corner for normal mapping. Requires vertex normals in the mesh and a texture coordinate
+    /// per vertex. Corners are grouped, if their triangles share an edge at the vertex and have the same texture space orientation.
+    /// The tangent of a group is the angle weighted average of the triangle tangents, projected into the tangent plane of the normal.
+    /// The fourth component is 1 for preserved and -1 for mirrored texture space orientation. The grouping follows the idea of
+    /// MikkTSpace, but is simplified, so the tangents are not guaranteed to match those of tools baking normal maps with MikkTSpace.
+    /// Vertices are identified by their index, so vertices with the same position, normal and texture coordinate should be welded
+    /// beforehand. Returns None, if normals or texture coordinates are missing.
+    ///
+    /// # Examples
+    /// ```
+    /// let mesh = obj::read_triangle_mesh(reader)?.mesh;
+    /// let uvs = mesh.get_vertex_property_ref::<UvMap<f32>, Vec<[f32; 2]>>(PropertyType::UV).unwrap();
+    /// let tangents = create_corner_tangents(&mesh, uvs.get_channel(0)).unwrap();
+    ///
+    /// //Tangent of the second corner of the first triangle
+    /// let [x, y, z, handedness] = tangents[1];
+    /// ```
+    pub fn create_corner_tangents<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>, texture_coordinates : &[[U; 2]]) -> Option<Vec<[U; 4]>>
+    {
+        let vertices = mesh.get_vertices();
+        let normals = mesh.get_vertex_property_ref::<NormalMap<T, U>, T>(PropertyType::NORMAL)?;
+        if normals.len() != vertices.len() || texture_coordinates.len() != vertices.len() {
+            return None;
+        }
+
+        let triangles = mesh.get_indices().chunks(3)
+            .map(|t| [t[0].to_usize().unwrap(), t[1].to_usize().unwrap(), t[2].to_usize().unwrap()])
+            .collect::<Vec<[usize; 3]>>();
+        let mut frames = create_triangle_frames(vertices, &triangles, texture_coordinates);
+
+        let mut edge_corners = HashMap::<(usize, usize), Vec<(usize, usize)>>::new();
+        for (f, triangle) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                let a = triangle[i];
+                let b = triangle[(i + 1) % 3];
+                if a != b {
+                    let (low, high) = if a < b { (i, (i + 1) % 3) } else { ((i + 1) % 3, i) };
+                    edge_corners.entry((a.min(b), a.max(b))).or_default().push((f * 3 + low, f * 3 + high));
+                }
+            }
+        }
+
+        let mut edges = edge_corners.into_iter().collect::<Vec<((usize, usize), Vec<(usize, usize)>)>>();
+        edges.sort_by_key(|(edge, _)| *edge);
+
+        //Triangles without texture space area adopt the orientation of a neighbour, which has one
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (_, corners) in &edges {
+                for first in corners {
+                    for second in corners {
+                        let (f, g) = (first.0 / 3, second.0 / 3);
+                        if frames[f].group_with_any && !frames[g].group_with_any {
+                            frames[f].orientation_preserving = frames[g].orientation_preserving;
+                            frames[f].group_with_any = false;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let is_degenerate = |f : usize| triangles[f][0] == triangles[f][1] || triangles[f][1] == triangles[f][2] || triangles[f][2] == triangles[f][0];
+
+        let mut groups = DisjointSet::new(triangles.len() * 3);
+        for (_, corners) in &edges {
+            for (i, first) in corners.iter().enumerate() {
+                for second in &corners[i + 1..] {
+                    let (f, g) = (first.0 / 3, second.0 / 3);
+                    if frames[f].orientation_preserving == frames[g].orientation_preserving && !is_degenerate(f) && !is_degenerate(g) {
+                        groups.union(first.0, second.0);
+                        groups.union(first.1, second.1);
+                    }
+                }
+            }
+        }
+
+        let mut sums = vec![T::zero(); triangles.len() * 3];
+        for (f, triangle) in triangles.iter().enumerate() {
+
+            if is_degenerate(f) {
+                continue;
+            }
+
+            for i in 0..3 {
+
+                let vertex = triangle[i];
+                let normal = normals.get(vertex);
+                let next_edge = project(vertices[triangle[(i + 1) % 3]] - vertices[vertex], normal);
+                let prev_edge = project(vertices[triangle[(i + 2) % 3]] - vertices[vertex], normal);
+                let angle = T::dot(&next_edge, &prev_edge).max(-U::one()).min(U::one()).acos();
+
+                let root = groups.find(f * 3 + i);
+                sums[root] += project(frames[f].tangent, normal) * angle;
+            }
+        }
+
+        //Corners of degenerate triangles take the tangent of another corner at the same vertex
+        let mut vertex_corners = vec![usize::MAX; vertices.len()];
+        for (f, triangle) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                if !is_degenerate(f) && vertex_corners[triangle[i]] == usize::MAX {
+                    vertex_corners[triangle[i]] = f * 3 + i;
+                }
+            }
+        }
+
+        let mut tangents = Vec::<[U; 4]>::with_capacity(triangles.len() * 3);
+        for (f, triangle) in triangles.iter().enumerate() {
+            for i in 0..3 {
+
+                let corner = if is_degenerate(f) && vertex_corners[triangle[i]] != usize::MAX { vertex_corners[triangle[i]] } else { f * 3 + i };
+                let tangent = sums[groups.find(corner)].normalize().unwrap_or(T::zero());
+                let handedness = if frames[corner / 3].orientation_preserving { U::one() } else { -U::one() };
+
+                tangents.push([tangent.x(), tangent.y(), tangent.z(), handedness]);
+            }
+        }
+
+        return Some(tangents);
+    }
+
+    /// Creates a tangent per vertex like [create_corner_tangents], using the tangent of the first corner of each vertex.
+    /// The corners of a vertex only differ, if its triangles have different texture space orientations (e.g. at the
+    /// symmetry plane of a mirrored texture), in which case the vertex should be split beforehand.
+    /// Vertices without triangles get a zero tangent.
+    ///
+    /// # Examples
+    /// ```
+    /// let tangents = create_tangents(&mesh, &texture_coordinates).unwrap();
+    /// mesh.add_vertex_property(tangents);
+    /// ```
+    pub fn create_tangents<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &TriangleVertexMesh<T, U, IndexType>, texture_coordinates : &[[U; 2]]) -> Option<TangentMap<U>>
+    {
+        let corner_tangents = create_corner_tangents(mesh, texture_coordinates)?;
+
+        let mut tangent_map = TangentMap::<U>::with_size(mesh.get_vertices().len(), [U::zero(), U::zero(), U::zero(), U::one()]);
+        let mut assigned = vec![false; mesh.get_vertices().len()];
+        for (corner, idx) in mesh.get_indices().iter().enumerate() {
+            let vertex = idx.to_usize().unwrap();
+            if !assigned[vertex] {
+                tangent_map.set(vertex, corner_tangents[corner]);
+                assigned[vertex] = true;
+            }
+        }
+
+        return Some(tangent_map);
+    }
+
+    /// Calculates the tangents and stores them in the vertex properties of the mesh, replacing existing ones.
+    /// Returns false, if normals or texture coordinates are missing.
+    pub fn add_tangents<T: Vec3<U> + FloatVector<U> + 'static, U : Num + PartialOrd<U> + Float + 'static, IndexType : PrimInt + Hash + Unsigned>(
+        mesh : &mut TriangleVertexMesh<T, U, IndexType>, texture_coordinates : &[[U; 2]]) -> bool
+    {
+        let tangents = match create_tangents(mesh, texture_coordinates) {
+            Some(tangents) => tangents,
+            None => return false,
+        };
+
+        match mesh.get_vertex_property::<TangentMap<U>, [U; 4]>(PropertyType::TANGENT) {
+            Some(existing) => *existing = tangents,
+            None => { mesh.add_vertex_property(tangents); }
+        }
+        return true;
+    }
+
+    /// Calculates the bitangent from the normal and a tangent with handedness, in the same way as shaders do
+    ///
+    /// # Examples
+    /// ```
+    /// let bitangent = calculate_bitangent(Vec3f::new(0.0, 0.0, 1.0), [1.0, 0.0, 0.0, -1.0]); //Returns (0, -1, 0)
+    /// ```
+    pub fn calculate_bitangent<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(normal : T, tangent : [U; 4]) -> T {
+        return T::cross(normal, T::new(tangent[0], tangent[1], tangent[2])) * tangent[3];
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::common_properties::NormalMap;
+        use crate::common_properties::TangentMap;
+        use crate::mesh_tangent_calculation::mesh_tangents::add_tangents;
+        use crate::mesh_tangent_calculation::mesh_tangents::calculate_bitangent;
+        use crate::mesh_tangent_calculation::mesh_tangents::create_corner_tangents;
+        use crate::property_map::PropertyMap;
+        use crate::property_map::PropertyType;
+        use crate::property_map::VertexProperties;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+
+        fn create_unit_square() -> TriangleVertexMesh<Vec3f, f32, u32> {
+
+            let vertices = Vec::from(
+                [Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(1.0, 1.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0)]
+            );
+
+            let mut square = TriangleVertexMesh::from(vertices, Vec::from([0, 1, 2, 0, 2, 3])).unwrap();
+            square.add_vertex_property(NormalMap::<Vec3f, f32>::with_size(4, Vec3f::new(0.0, 0.0, 1.0)));
+            return square;
+        }
+
+        #[test]
+        fn test_add_tangents() {
+
+            let mut square = create_unit_square();
+            let texture_coordinates = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
+            assert!(!add_tangents(&mut square, &texture_coordinates[0..3]));
+            assert!(add_tangents(&mut square, &texture_coordinates));
+
+            let tangents = square.get_vertex_property::<TangentMap<f32>, [f32; 4]>(PropertyType::TANGENT).unwrap();
+            assert_eq!(tangents.len(), 4);
+            for v in 0..4 {
+                assert_eq!(tangents.get(v), [1.0, 0.0, 0.0, 1.0]);
+            }
+        }
+
+        #[test]
+        fn test_mirrored_tangents() {
+
+            let square = create_unit_square();
+            let texture_coordinates = [[1.0, 0.0], [0.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+
+            let tangents = create_corner_tangents(&square, &texture_coordinates).unwrap();
+            assert_eq!(tangents.len(), 6);
+
+            //u grows to the left, v still grows upwards
+            for tangent in tangents {
+                assert_eq!(tangent, [-1.0, 0.0, 0.0, -1.0]);
+                let bitangent = calculate_bitangent(Vec3f::new(0.0, 0.0, 1.0), tangent);
+                assert_eq!(bitangent.y(), 1.0);
+            }
+        }
+    }
+}
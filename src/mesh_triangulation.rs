@@ -0,0 +1,158 @@
+pub mod face_triangulation {
+
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::cardinal_direction::CardinalDirection;
+    use crate::cardinal_direction::plane_indices;
+    use crate::mesh_normal_calculation::mesh_normals::calculate_face_normal;
+    use crate::polygon::Polygon;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec2;
+    use crate::vector::Vec2d;
+    use crate::vector::Vec3;
+
+    fn component<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(v : &T, idx : usize) -> f64 {
+        match idx {
+            0 => v.x().to_f64().unwrap(),
+            1 => v.y().to_f64().unwrap(),
+            _ => v.z().to_f64().unwrap()
+        }
+    }
+
+    /// Splits a face of a mesh, consisting of an arbitrary amount of indices, into triangles. The face is
+    /// projected into the cardinal plane most perpendicular to its normal and triangulated as a polygon.
+    /// If this is not possible (e.g. because the face is degenerate), a fan triangulation is used instead.
+    ///
+    /// The returned list contains three indices per triangle, referencing the same vertices as the face.
+    ///
+    /// # Examples
+    /// ```
+    /// let vertices = ...;
+    /// let face = Vec::from([0, 1, 2, 3]);
+    ///
+    /// let triangles = triangulate_face(&vertices, &face); //Returns 6 indices
+    /// ```
+    pub fn triangulate_face<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(vertices : &[T], face : &[IndexType])
+        -> Vec<IndexType>
+    {
+        let size = face.len();
+        let mut triangles = Vec::<IndexType>::with_capacity(size.saturating_sub(2) * 3);
+
+        if size < 3 {
+            return triangles;
+        }
+
+        if size > 3 {
+
+            let face_vertices = face.iter().map(|idx| vertices[idx.to_usize().unwrap()]).collect::<Vec<T>>();
+            let normal = calculate_face_normal(face_vertices.clone());
+
+            if let Some(n) = normal {
+
+                let abs = [n.x().abs(), n.y().abs(), n.z().abs()];
+                let axis = if abs[0] >= abs[1] && abs[0] >= abs[2] { CardinalDirection::X }
+                    else if abs[1] >= abs[2] { CardinalDirection::Y }
+                    else { CardinalDirection::Z };
+                let (i0, i1) = plane_indices(axis);
+
+                let mut points = face_vertices.iter().map(|v| Vec2d::new(component(v, i0), component(v, i1))).collect::<Vec<Vec2d>>();
+
+                //Polygons are expected to be CCW
+                let mut area = 0.0_f64;
+                for i in 0..size {
+                    area += Vec2d::wedge(points[i], points[(i + 1) % size]);
+                }
+
+                let reversed = area < 0.0;
+                let mut order = (0..size).collect::<Vec<usize>>();
+                if reversed {
+                    order.reverse();
+                    points.reverse();
+                }
+
+                let mut poly = Polygon::<f64, Vec2d>::with_capacity(size);
+                poly.push_vector(points);
+
+                if let Some(triangulation) = poly.triangulate::<usize>() {
+
+                    //Triangles of a reversed polygon are flipped back to keep the winding of the face
+                    for triangle in triangulation.chunks(3) {
+                        if reversed {
+                            triangles.push(face[order[triangle[0]]]);
+                            triangles.push(face[order[triangle[2]]]);
+                            triangles.push(face[order[triangle[1]]]);
+                        } else {
+                            triangles.push(face[order[triangle[0]]]);
+                            triangles.push(face[order[triangle[1]]]);
+                            triangles.push(face[order[triangle[2]]]);
+                        }
+                    }
+                    return triangles;
+                }
+            }
+        }
+
+        for i in 1..size - 1 {
+            triangles.push(face[0]);
+            triangles.push(face[i]);
+            triangles.push(face[i + 1]);
+        }
+
+        return triangles;
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::mesh_normal_calculation::mesh_normals::calculate_face_normal;
+        use crate::mesh_triangulation::face_triangulation::triangulate_face;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+        use crate::vector::Vector;
+
+        #[test]
+        fn test_triangulate_concave_quad() {
+
+            //Arrow-head shaped quad, where vertex 2 is concave
+            let vertices = Vec::from(
+                [Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.2, 0.2, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0)]
+            );
+
+            let triangles = triangulate_face(&vertices, &[0_u32, 1, 2, 3]);
+
+            assert_eq!(triangles.len(), 6);
+            assert_eq!(triangles[0], 2);
+        }
+
+        #[test]
+        fn test_triangulate_keeps_winding() {
+
+            //Square in the XZ-plane, listed clockwise when viewed from above
+            let vertices = Vec::from(
+                [Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(0.0, 0.0, 1.0),
+                Vec3f::new(1.0, 0.0, 1.0),
+                Vec3f::new(1.0, 0.0, 0.0)]
+            );
+
+            let face_normal = calculate_face_normal(vertices.clone()).unwrap();
+            let triangles = triangulate_face(&vertices, &[0_u32, 1, 2, 3]);
+
+            assert_eq!(triangles.len(), 6);
+
+            for t in triangles.chunks(3) {
+                let triangle = t.iter().map(|idx| vertices[*idx as usize]).collect::<Vec<Vec3f>>();
+                let normal = calculate_face_normal(triangle).unwrap();
+                assert!(Vec3f::dot(&normal, &face_normal) > 0.99);
+            }
+        }
+    }
+}
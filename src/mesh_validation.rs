@@ -0,0 +1,369 @@
+pub mod mesh_diagnostics {
+
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::hash::Hash;
+
+    use num_traits::Num;
+    use num_traits::NumCast;
+    use num_traits::PrimInt;
+    use num_traits::Unsigned;
+    use num_traits::Float;
+
+    use crate::disjoint_set::DisjointSet;
+    use crate::mesh::Mesh;
+    use crate::vector::FloatVector;
+    use crate::vector::Vec3;
+
+    /// Result of [validate]. Faces and vertices are given by their index, edges as pair of vertex indices
+    /// with the smaller index first.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct ValidationReport {
+        pub vertex_count : usize,
+        pub face_count : usize,
+        /// Faces referencing vertices that do not exist, together with the offending index
+        pub out_of_range_indices : Vec<(usize, usize)>,
+        /// Faces with repeated vertices or (almost) zero area
+        pub degenerate_faces : Vec<usize>,
+        /// Faces using the same vertices as an earlier face (regardless of orientation), together with the earlier face
+        pub duplicate_faces : Vec<(usize, usize)>,
+        /// Vertices which are not part of any face
+        pub unreferenced_vertices : Vec<usize>,
+        /// Edges shared by more than two faces
+        pub non_manifold_edges : Vec<(usize, usize)>,
+        /// Vertices whose incident faces do not form a single fan (e.g. two cones touching at their tips)
+        pub non_manifold_vertices : Vec<usize>,
+        /// Edges between two faces, which both traverse the edge in the same direction
+        pub inconsistent_edges : Vec<(usize, usize)>,
+        /// Loops of edges belonging to one face only, given as sequence of vertices
+        pub boundary_loops : Vec<Vec<usize>>,
+        /// Number of connected components, formed by the faces of the mesh
+        pub connected_components : usize,
+    }
+
+    impl ValidationReport {
+
+        pub fn is_manifold(&self) -> bool {
+            return self.non_manifold_edges.is_empty() && self.non_manifold_vertices.is_empty();
+        }
+
+        pub fn is_consistently_oriented(&self) -> bool {
+            return self.inconsistent_edges.is_empty();
+        }
+
+        /// Returns true, if the mesh has no boundary, i.e. it is watertight
+        pub fn is_closed(&self) -> bool {
+            return self.boundary_loops.is_empty();
+        }
+
+        /// Returns true, if none of the problems were found which break algorithms relying on the mesh topology.
+        /// Unreferenced vertices, boundaries and multiple components are allowed.
+        pub fn is_valid(&self) -> bool {
+            return self.out_of_range_indices.is_empty()
+                && self.degenerate_faces.is_empty()
+                && self.duplicate_faces.is_empty()
+                && self.is_manifold()
+                && self.is_consistently_oriented();
+        }
+    }
+
+    /// Returns true, if the face has repeated vertices or an area close to zero compared to its longest edge
+    pub(in crate) fn is_degenerate_face<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float>(vertices : &[T], face : &[usize]) -> bool {
+
+        let unique = face.iter().collect::<HashSet<&usize>>();
+        if unique.len() < face.len() {
+            return true;
+        }
+
+        //The area is compared to the longest edge, so the check does not depend on the scale of the mesh
+        let size = face.len();
+        let mut area_vector = T::zero();
+        let mut longest_edge = U::zero();
+
+        for i in 0..size {
+            let current = vertices[face[i]];
+            let next = vertices[face[(i + 1) % size]];
+            area_vector += T::cross(current, next);
+            longest_edge = longest_edge.max((next - current).length());
+        }
+
+        let double_area = area_vector.length();
+        return double_area <= U::epsilon() * longest_edge * longest_edge;
+    }
+
+    /// Checks the mesh for problems in its indices, geometry and topology. Unlike most other functions of this crate,
+    /// it does not panic on indices which are out of range, so it can be used on meshes from untrusted sources.
+    /// Faces with such indices are only reported and ignored for all other checks.
+    ///
+    /// # Examples
+    /// ```
+    /// let mesh = TriangleVertexMesh::from(vertices, indices).unwrap();
+    ///
+    /// let report = validate(&mesh);
+    /// if !report.is_valid() {
+    ///     println!("{:?}", report);
+    /// }
+    /// ```
+    pub fn validate<MeshType : Mesh<T, U, IndexType>, T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned>(mesh : &MeshType)
+        -> ValidationReport
+    {
+        let vertices = mesh.get_vertices();
+        let vertex_count = vertices.len();
+        let face_count = mesh.get_face_count();
+
+        let mut report = ValidationReport { vertex_count, face_count, ..Default::default() };
+
+        let mut faces = Vec::<Option<Vec<usize>>>::with_capacity(face_count);
+        for f in 0..face_count {
+
+            let face = mesh.get_face(NumCast::from(f).unwrap()).iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>();
+
+            let mut in_range = true;
+            for idx in face.iter().filter(|idx| **idx >= vertex_count) {
+                report.out_of_range_indices.push((f, *idx));
+                in_range = false;
+            }
+            faces.push(if in_range { Some(face) } else { None });
+        }
+
+        let mut referenced = vec![false; vertex_count];
+        let mut first_occurrence = HashMap::<Vec<usize>, usize>::new();
+        let mut edges = HashMap::<(usize, usize), Vec<(usize, bool)>>::new();
+        let mut vertex_faces = vec![Vec::<usize>::new(); vertex_count];
+        let mut components = DisjointSet::new(vertex_count);
+
+        for (f, face) in faces.iter().enumerate() {
+
+            let face = match face {
+                Some(face) => face,
+                None => continue,
+            };
+
+            if is_degenerate_face(vertices, face) {
+                report.degenerate_faces.push(f);
+            }
+
+            let mut key = face.clone();
+            key.sort();
+            if let Some(original) = first_occurrence.get(&key) {
+                report.duplicate_faces.push((f, *original));
+            } else {
+                first_occurrence.insert(key, f);
+            }
+
+            let size = face.len();
+            for i in 0..size {
+
+                let a = face[i];
+                let b = face[(i + 1) % size];
+
+                referenced[a] = true;
+                if !vertex_faces[a].contains(&f) {
+                    vertex_faces[a].push(f);
+                }
+                components.union(a, b);
+
+                if a != b {
+                    edges.entry((a.min(b), a.max(b))).or_default().push((f, a < b));
+                }
+            }
+        }
+
+        report.unreferenced_vertices = (0..vertex_count).filter(|v| !referenced[*v]).collect();
+
+        let mut sorted_edges = edges.into_iter().collect::<Vec<((usize, usize), Vec<(usize, bool)>)>>();
+        sorted_edges.sort_by_key(|(edge, _)| *edge);
+
+        //Faces are adjacent around a vertex, if they share a manifold edge containing the vertex
+        let mut vertex_links = vec![Vec::<(usize, usize)>::new(); vertex_count];
+        let mut boundary_edges = Vec::<(usize, usize)>::new();
+
+        for ((a, b), edge_faces) in &sorted_edges {
+
+            match edge_faces.len() {
+                1 => {
+                    let (_, forward) = edge_faces[0];
+                    boundary_edges.push(if forward { (*a, *b) } else { (*b, *a) });
+                }
+                2 => {
+                    let (f0, forward0) = edge_faces[0];
+                    let (f1, forward1) = edge_faces[1];
+                    if forward0 == forward1 {
+                        report.inconsistent_edges.push((*a, *b));
+                    }
+                    vertex_links[*a].push((f0, f1));
+                    vertex_links[*b].push((f0, f1));
+                }
+                _ => report.non_manifold_edges.push((*a, *b)),
+            }
+        }
+
+        for v in 0..vertex_count {
+
+            let incident = &vertex_faces[v];
+            if incident.len() < 2 {
+                continue;
+            }
+
+            let mut fans = DisjointSet::new(incident.len());
+            let mut fan_count = incident.len();
+            for (f0, f1) in &vertex_links[v] {
+                let i0 = incident.iter().position(|f| f == f0).unwrap();
+                let i1 = incident.iter().position(|f| f == f1).unwrap();
+                if fans.union(i0, i1) {
+                    fan_count -= 1;
+                }
+            }
+
+            if fan_count > 1 {
+                report.non_manifold_vertices.push(v);
+            }
+        }
+
+        //Boundary edges are chained through their shared vertices, ignoring their direction,
+        //so loops are found even if the orientation of the faces is inconsistent
+        let mut boundary_incidence = HashMap::<usize, Vec<usize>>::new();
+        for (e, (a, b)) in boundary_edges.iter().enumerate() {
+            boundary_incidence.entry(*a).or_default().push(e);
+            boundary_incidence.entry(*b).or_default().push(e);
+        }
+
+        let mut used = vec![false; boundary_edges.len()];
+        for start in 0..boundary_edges.len() {
+
+            if used[start] {
+                continue;
+            }
+            used[start] = true;
+
+            let (first, mut current) = boundary_edges[start];
+            let mut boundary_loop = Vec::from([first]);
+
+            while current != first {
+
+                boundary_loop.push(current);
+
+                let next = boundary_incidence[&current].iter().find(|e| !used[**e]).copied();
+                match next {
+                    Some(e) => {
+                        used[e] = true;
+                        let (a, b) = boundary_edges[e];
+                        current = if a == current { b } else { a };
+                    }
+                    None => break,
+                }
+            }
+
+            report.boundary_loops.push(boundary_loop);
+        }
+
+        let roots = (0..vertex_count).filter(|v| referenced[*v]).map(|v| components.find(v)).collect::<HashSet<usize>>();
+        report.connected_components = roots.len();
+
+        return report;
+    }
+
+    #[cfg(test)]
+    mod unit_tests {
+
+        use crate::face_vertex_mesh::FaceVertexMesh;
+        use crate::mesh_validation::mesh_diagnostics::validate;
+        use crate::triangle_vertex_mesh::TriangleVertexMesh;
+        use crate::vector::Vec3f;
+        use crate::vector::Vec3;
+
+        fn create_tetrahedron() -> (Vec<Vec3f>, Vec<u32>) {
+
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(0.0, 0.0, 1.0)]);
+
+            let indices = Vec::from([
+                0, 2, 1,
+                0, 1, 3,
+                1, 2, 3,
+                0, 3, 2]);
+
+            return (vertices, indices);
+        }
+
+        #[test]
+        fn test_closed_mesh() {
+
+            let (vertices, indices) = create_tetrahedron();
+            let mesh = TriangleVertexMesh::from(vertices, indices).unwrap();
+
+            let report = validate(&mesh);
+
+            assert!(report.is_valid());
+            assert!(report.is_closed());
+            assert_eq!(report.connected_components, 1);
+        }
+
+        #[test]
+        fn test_boundary_and_orientation() {
+
+            let (vertices, mut indices) = create_tetrahedron();
+
+            //Remove the last face and flip the first one
+            indices.truncate(9);
+            indices.swap(1, 2);
+            let mesh = TriangleVertexMesh::from(vertices, indices).unwrap();
+
+            let report = validate(&mesh);
+
+            assert_eq!(report.boundary_loops.len(), 1);
+            assert_eq!(report.boundary_loops[0].len(), 3);
+            assert_eq!(report.inconsistent_edges, Vec::from([(0, 1), (1, 2)]));
+            assert!(report.is_manifold());
+        }
+
+        #[test]
+        fn test_index_problems() {
+
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(2.0, 0.0, 0.0),
+                Vec3f::new(5.0, 5.0, 5.0)]);
+
+            let faces = Vec::from([
+                Vec::from([0, 1, 2]),
+                Vec::from([2, 1, 0]),
+                Vec::from([0, 1, 3]),
+                Vec::from([0, 1, 7])]);
+
+            let mesh = FaceVertexMesh::<Vec3f, f32, u32>::from(vertices, faces).unwrap();
+            let report = validate(&mesh);
+
+            assert_eq!(report.out_of_range_indices, Vec::from([(3, 7)]));
+            assert_eq!(report.duplicate_faces, Vec::from([(1, 0)]));
+            assert_eq!(report.degenerate_faces, Vec::from([2]));
+            assert_eq!(report.unreferenced_vertices, Vec::from([4]));
+            assert_eq!(report.non_manifold_edges, Vec::from([(0, 1)]));
+            assert!(!report.is_valid());
+        }
+
+        #[test]
+        fn test_non_manifold_vertex() {
+
+            //Two triangles touching in one vertex
+            let vertices = Vec::from([
+                Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+                Vec3f::new(-1.0, 0.0, 0.0),
+                Vec3f::new(0.0, -1.0, 0.0)]);
+
+            let mesh = TriangleVertexMesh::<Vec3f, f32, u32>::from(vertices, Vec::from([0, 1, 2, 0, 3, 4])).unwrap();
+            let report = validate(&mesh);
+
+            assert_eq!(report.non_manifold_vertices, Vec::from([0]));
+            assert_eq!(report.connected_components, 1);
+            assert_eq!(report.boundary_loops.len(), 2);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! # Point Clouds
+//!
+//! Point clouds are unstructured sets of points in 3D space, e.g. as produced by scanners. They carry no
+//! connectivity, but the same vertex properties (normals, colors, ...) as meshes.
+
+use std::marker::PhantomData;
+
+use num_traits::Num;
+use num_traits::Float;
+
+use crate::property_map::PropertyStore;
+use crate::property_map::VertexProperties;
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+#[repr(C)]
+pub struct PointCloud<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> {
+
+    points : Vec<T>,
+    vertex_properties : PropertyStore,
+    number_type : PhantomData<U>
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> PointCloud<T, U> {
+
+    pub fn new() -> Self {
+        Self {
+            points: Vec::<T>::new(),
+            vertex_properties: PropertyStore::new(),
+            number_type: PhantomData
+        }
+    }
+
+    pub fn from(points : Vec<T>) -> Self {
+        Self {
+            points,
+            vertex_properties: PropertyStore::new(),
+            number_type: PhantomData
+        }
+    }
+
+    pub fn get_mut_points(&mut self) -> &mut Vec<T> {
+        return &mut self.points;
+    }
+
+    pub fn get_points(&self) -> &Vec<T> {
+        return &self.points;
+    }
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> Default for PointCloud<T, U> {
+
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> VertexProperties for PointCloud<T, U> {
+
+    fn get_vertex_properties(&self) -> &PropertyStore {
+        return &self.vertex_properties;
+    }
+
+    fn get_vertex_properties_mut(&mut self) -> &mut PropertyStore {
+        return &mut self.vertex_properties;
+    }
+}
@@ -16,8 +16,6 @@ use num_traits::Float;
 use num_traits::NumCast;
 use num_traits::PrimInt;
 
-use crate::property_map::PropertyType;
-use crate::property_map::PropertyMap;
 use crate::property_map::PropertyStore;
 
 use crate::property_map::VertexProperties;
@@ -204,7 +202,8 @@ impl< T : Num + PartialOrd<T>, U : Vec2<T>> Polygon<T, U> {
     }
 
     /// Splits the polygon into triangles and returns a list of integers, where each triple
-    /// forms a triangle as part of the triangulation of the polygon.
+    /// forms a triangle as part of the triangulation of the polygon. The indices reference the points of the polygon,
+    /// so the vertex properties of the polygon remain valid for the triangles.
     /// 
     /// The method will return null if the polygon is self-intersecting
     /// 
@@ -287,12 +286,12 @@ impl<> PolygonFloat<f64, Vec2d> for Polygon<f64, Vec2d> {
 
 impl<T : Num + PartialOrd<T>, U : Vec2<T>> VertexProperties for Polygon<T, U> {
 
-    fn get_vertex_property<M: PropertyMap<V> + 'static, V>(&mut self, property_type: PropertyType) -> Option<&mut M> {
-        return self.vertex_properties.get_property_map::<M, V>(property_type);
+    fn get_vertex_properties(&self) -> &PropertyStore {
+        return &self.vertex_properties;
     }
 
-    fn add_vertex_property<M: PropertyMap<V> + 'static, V>(&mut self, map : M) -> bool {
-        return self.vertex_properties.add_property_map::<M, V>(map);
+    fn get_vertex_properties_mut(&mut self) -> &mut PropertyStore {
+        return &mut self.vertex_properties;
     }
 }
 
@@ -390,6 +389,30 @@ mod unit_tests {
 
 
     
+    #[test]
+    fn test_vertex_properties() {
+
+        use crate::common_properties::RgbMap;
+        use crate::common_properties::UvMap;
+        use crate::property_map::PropertyMap;
+        use crate::property_map::PropertyType;
+
+        let mut poly = Polygon::<f64, Vec2d>::regular(Vec2d::new(0.0, 0.0), 1.0, 4);
+
+        let mut uvs = UvMap::<f64>::from(Vec::from([[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]));
+        assert!(uvs.add_channel(vec![[0.5, 0.5]; 4]));
+        assert!(!uvs.add_channel(vec![[0.5, 0.5]; 3]));
+
+        assert!(poly.add_vertex_property(uvs));
+        assert!(poly.add_vertex_property(RgbMap::<f64>::with_size(4, [1.0, 0.0, 0.0])));
+
+        let uvs = poly.get_vertex_property_ref::<UvMap<f64>, Vec<[f64; 2]>>(PropertyType::UV).unwrap();
+        assert_eq!(uvs.get(2), Vec::from([[1.0, 1.0], [0.5, 0.5]]));
+
+        let colors = poly.get_vertex_property_ref::<RgbMap<f64>, [f64; 3]>(PropertyType::COLOR).unwrap();
+        assert_eq!(colors.get_rgba(3), [1.0, 0.0, 0.0, 1.0]);
+    }
+
     #[bench]
     fn bench_regular_10_fan_triangulation(b: &mut Bencher) {
 
@@ -1,12 +1,90 @@
-
 use std::any::Any;
+use std::any::TypeId;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-#[derive(PartialEq, Eq, Hash)]
+/// The built-in properties, which are read and written by the algorithms and importers of the library
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum PropertyType {
 
     NORMAL = 0,
     COLOR = 1,
+    TANGENT = 2,
+    UV = 3,
+}
+
+/// Name of a property map in a [PropertyStore]. Any number of maps can be stored, as long as their names differ.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PropertyName {
+
+    /// One of the built-in properties
+    BUILTIN(PropertyType),
+    /// A user-defined property with a name, e.g. "curvature" or "segment"
+    NAMED(Cow<'static, str>),
+    /// A user-defined property, which is identified by the type of its map
+    TYPE(TypeId),
+}
+
+impl From<PropertyType> for PropertyName {
+
+    fn from(property_type : PropertyType) -> Self {
+        return PropertyName::BUILTIN(property_type);
+    }
+}
+
+/// Typed handle of a property map, which combines its name with the type of the map.
+/// Handles are cheap to create, so they can be kept as constants or created again whenever needed.
+///
+/// # Examples
+/// ```
+/// let weights = PropertyHandle::<AttributeMap<f32>>::named("weights");
+/// mesh.add_named_vertex_property(&weights, AttributeMap::with_size(mesh.get_vertices().len(), 1.0));
+/// let w = mesh.get_named_vertex_property_ref(&weights).unwrap().get(0);
+/// ```
+pub struct PropertyHandle<M> {
+
+    name : PropertyName,
+    map_type : PhantomData<fn() -> M>,
+}
+
+impl<M : 'static> PropertyHandle<M> {
+
+    pub fn named(name : impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name : PropertyName::NAMED(name.into()),
+            map_type : PhantomData,
+        }
+    }
+
+    /// Creates a handle, which is named after the type of the map. There can only be one such map of each type.
+    pub fn of_type() -> Self {
+        Self {
+            name : PropertyName::TYPE(TypeId::of::<M>()),
+            map_type : PhantomData,
+        }
+    }
+
+    pub fn builtin(property_type : PropertyType) -> Self {
+        Self {
+            name : PropertyName::BUILTIN(property_type),
+            map_type : PhantomData,
+        }
+    }
+
+    pub fn get_name(&self) -> &PropertyName {
+        return &self.name;
+    }
+}
+
+impl<M> Clone for PropertyHandle<M> {
+
+    fn clone(&self) -> Self {
+        Self {
+            name : self.name.clone(),
+            map_type : PhantomData,
+        }
+    }
 }
 
 
@@ -22,48 +100,509 @@ pub trait PropertyMap<T>  {
 
     fn len(&self) -> usize;
 
-    fn property_type() -> PropertyType;
+    /// Creates an empty map with the same settings as this one, which is used when the store rebuilds a map
+    fn new_like(&self, capacity : usize) -> Self where Self : Sized {
+        return Self::with_capacity(capacity);
+    }
+
+    /// The built-in property, which is stored in maps of this type. User-defined maps have none,
+    /// so they are stored under the name of their type, unless they are added with a [PropertyHandle].
+    fn property_type() -> Option<PropertyType> {
+        return None;
+    }
+
+    /// Creates the value of a new element from old elements with weights, which sum up to one. This is used by operations
+    /// changing the elements of a mesh, e.g. subdivision creates edge midpoints from two vertices with a weight of one half.
+    /// By default, the value of the element with the highest weight is copied, maps of values which can be blended or voted
+    /// on override this with [blend_values] or [vote_values].
+    fn interpolate(&self, sources : &[(usize, f64)]) -> T {
+        return self.get(get_dominant_source(sources));
+    }
+
+    /// The value of new elements, which are not made of any old elements, e.g. edges inside a subdivided face
+    fn get_default(&self) -> Option<T> {
+        return None;
+    }
 }
 
+/// Old elements with their weights, which sum up to one, from which a new element is created
+pub type ElementSources = Vec<(usize, f64)>;
 
-pub(in crate) struct PropertyStore {
+/// How the values of a property are combined, when a new element is created from several old ones
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PropertyInterpolation {
 
-    property_maps : HashMap<PropertyType, Box<dyn Any>>,
+    /// The value of the element with the highest weight is copied, e.g. for flags
+    COPY = 0,
+    /// Values are blended linearly (or barycentrically for three elements), e.g. for colors or texture coordinates
+    BLEND = 1,
+    /// The value with the highest sum of weights is taken, e.g. for segmentation labels or material ids
+    VOTE = 2,
+}
+
+/// Values, which can be combined linearly
+pub trait Blend : Sized {
+
+    /// Returns the weighted sum of the values, where the weights sum up to one
+    fn blend(values : &[(Self, f64)]) -> Self;
+}
+
+impl Blend for f32 {
+
+    fn blend(values : &[(Self, f64)]) -> Self {
+        return values.iter().map(|(value, weight)| *value as f64 * weight).sum::<f64>() as f32;
+    }
+}
+
+impl Blend for f64 {
+
+    fn blend(values : &[(Self, f64)]) -> Self {
+        return values.iter().map(|(value, weight)| value * weight).sum();
+    }
+}
+
+impl<U : Blend + Copy, const N : usize> Blend for [U; N] {
+
+    fn blend(values : &[(Self, f64)]) -> Self {
+
+        let mut result = match values.first() {
+            Some((value, _)) => *value,
+            None => panic!("[CGRust]: Cannot blend an empty list of values"),
+        };
+        for (i, component) in result.iter_mut().enumerate() {
+            *component = U::blend(&values.iter().map(|(value, weight)| (value[i], *weight)).collect::<Vec<(U, f64)>>());
+        }
+        return result;
+    }
+}
+
+/// Returns the source with the highest weight, or the first one if several have the same weight
+pub fn get_dominant_source(sources : &[(usize, f64)]) -> usize {
+
+    let mut dominant = sources[0];
+    for source in &sources[1..] {
+        if source.1 > dominant.1 {
+            dominant = *source;
+        }
+    }
+    return dominant.0;
+}
+
+/// Interpolates by blending the values of the sources, which can be used to implement [PropertyMap::interpolate]
+pub fn blend_values<M : PropertyMap<T>, T : Blend>(map : &M, sources : &[(usize, f64)]) -> T {
+    return T::blend(&sources.iter().map(|(idx, weight)| (map.get(*idx), *weight)).collect::<Vec<(T, f64)>>());
+}
+
+/// Interpolates by taking the value with the highest sum of weights, which can be used to implement [PropertyMap::interpolate].
+/// Ties are resolved in favour of the value, which occurs first.
+pub fn vote_values<M : PropertyMap<T>, T : PartialEq>(map : &M, sources : &[(usize, f64)]) -> T {
+
+    let mut votes = Vec::<(T, f64)>::with_capacity(sources.len());
+    for (idx, weight) in sources {
+        let value = map.get(*idx);
+        match votes.iter_mut().find(|(v, _)| *v == value) {
+            Some(vote) => vote.1 += weight,
+            None => votes.push((value, *weight)),
+        }
+    }
+
+    let mut winner = 0;
+    for (i, vote) in votes.iter().enumerate() {
+        if vote.1 > votes[winner].1 {
+            winner = i;
+        }
+    }
+    return votes.swap_remove(winner).0;
+}
+
+fn default_property_name<M: PropertyMap<T> + 'static, T>() -> PropertyName {
+
+    return match M::property_type() {
+        Some(property_type) => PropertyName::BUILTIN(property_type),
+        None => PropertyName::TYPE(TypeId::of::<M>()),
+    };
+}
+
+
+/// Creates a type-erased map from the old elements, or None if new elements cannot be created
+type InterpolateFn = fn(&dyn Any, &[ElementSources]) -> Option<Box<dyn Any>>;
+
+/// A type-erased property map, together with the operations needed by the store, which do not depend on its type
+struct PropertyEntry {
+    map : Box<dyn Any>,
+    len : fn(&dyn Any) -> usize,
+    remap : fn(&dyn Any, &[usize]) -> Box<dyn Any>,
+    interpolate : InterpolateFn,
+    append : fn(&mut dyn Any, &dyn Any),
+}
+
+fn property_map_len<M: PropertyMap<T> + 'static, T>(map : &dyn Any) -> usize {
+    return map.downcast_ref::<M>().unwrap().len();
+}
+
+fn remap_property_map<M: PropertyMap<T> + 'static, T>(map : &dyn Any, sources : &[usize]) -> Box<dyn Any> {
+
+    let old = map.downcast_ref::<M>().unwrap();
+    let mut new = old.new_like(sources.len());
+    for source in sources {
+        new.push(old.get(*source));
+    }
+    return Box::new(new);
+}
+
+/// Returns for each element the nearest element by index, which has sources, or None if no element has sources
+fn get_nearest_sourced_elements(sources : &[ElementSources]) -> Vec<Option<usize>> {
+
+    let mut nearest = vec![None; sources.len()];
+    let mut previous = None;
+    for (i, element_sources) in sources.iter().enumerate() {
+        if !element_sources.is_empty() {
+            previous = Some(i);
+        }
+        nearest[i] = previous;
+    }
+
+    let mut next = None;
+    for (i, element_sources) in sources.iter().enumerate().rev() {
+        if !element_sources.is_empty() {
+            next = Some(i);
+        }
+        if let Some(n) = next
+            && nearest[i].is_none_or(|p| n - i < i - p) {
+            nearest[i] = Some(n);
+        }
+    }
+    return nearest;
+}
+
+fn interpolate_property_map<M: PropertyMap<T> + 'static, T>(map : &dyn Any, sources : &[ElementSources]) -> Option<Box<dyn Any>> {
+
+    let old = map.downcast_ref::<M>().unwrap();
+    let has_default = old.get_default().is_some();
+    let nearest = if has_default || sources.iter().all(|s| !s.is_empty()) { Vec::new() } else { get_nearest_sourced_elements(sources) };
+
+    let mut new = old.new_like(sources.len());
+    for (i, element_sources) in sources.iter().enumerate() {
+        if !element_sources.is_empty() {
+            new.push(old.interpolate(element_sources));
+        } else if has_default {
+            new.push(old.get_default()?);
+        } else {
+            //Without a default value, new elements take the value of the nearest element, which is usually created
+            //together with them, e.g. the other edges of a subdivided face
+            new.push(old.interpolate(&sources[nearest[i]?]));
+        }
+    }
+    return Some(Box::new(new));
+}
+
+fn append_property_map<M: PropertyMap<T> + 'static, T>(map : &mut dyn Any, other : &dyn Any) {
+
+    let other = other.downcast_ref::<M>().unwrap();
+    let map = map.downcast_mut::<M>().unwrap();
+    for i in 0..other.len() {
+        map.push(other.get(i));
+    }
+}
+
+/// Property maps of the vertices, edges or faces of a mesh, stored under their [PropertyName]
+pub struct PropertyStore {
+
+    property_maps : HashMap<PropertyName, PropertyEntry>,
+    dropped_names : Vec<PropertyName>,
+}
+
+impl Default for PropertyStore {
+
+    fn default() -> Self {
+        return Self::new();
+    }
 }
 
 impl PropertyStore {
 
     pub fn new() -> Self {
-        Self { property_maps: HashMap::new(), }
+        Self { property_maps: HashMap::new(), dropped_names: Vec::new() }
     }
 
+    /// Adds a map under its built-in property type, or the name of its type for user-defined maps.
+    /// Returns false, if there already is a map with this name.
     pub fn add_property_map<M: PropertyMap<T> + 'static, T>(&mut self, map : M) -> bool {
+        return self.insert_property_map::<M, T>(default_property_name::<M, T>(), map);
+    }
+
+    /// Adds a map under the name of the handle. Returns false, if there already is a map with this name.
+    pub fn add_named_property_map<M: PropertyMap<T> + 'static, T>(&mut self, handle : &PropertyHandle<M>, map : M) -> bool {
+        return self.insert_property_map::<M, T>(handle.name.clone(), map);
+    }
+
+    fn insert_property_map<M: PropertyMap<T> + 'static, T>(&mut self, name : PropertyName, map : M) -> bool {
 
-        if self.property_maps.contains_key(&M::property_type()) {
+        if self.property_maps.contains_key(&name) {
             return false;
         }
 
-        let p = self.property_maps.insert(M::property_type(), Box::new(map));
-        return Option::is_some(&p);
+        self.property_maps.insert(name, PropertyEntry {
+            map: Box::new(map),
+            len: property_map_len::<M, T>,
+            remap: remap_property_map::<M, T>,
+            interpolate: interpolate_property_map::<M, T>,
+            append: append_property_map::<M, T>
+        });
+        return true;
     }
 
     pub fn get_property_map<M: PropertyMap<T> + 'static, T>(&mut self, property_type : PropertyType) -> Option<&mut M> {
+        return self.property_maps.get_mut(&PropertyName::BUILTIN(property_type)).and_then(|entry| entry.map.downcast_mut::<M>());
+    }
+
+    pub fn get_property_map_ref<M: PropertyMap<T> + 'static, T>(&self, property_type : PropertyType) -> Option<&M> {
+        return self.property_maps.get(&PropertyName::BUILTIN(property_type)).and_then(|entry| entry.map.downcast_ref::<M>());
+    }
+
+    /// Returns the map of the handle, or None if there is no map with its name or the map has a different type
+    pub fn get_named_property_map<M: PropertyMap<T> + 'static, T>(&mut self, handle : &PropertyHandle<M>) -> Option<&mut M> {
+        return self.property_maps.get_mut(&handle.name).and_then(|entry| entry.map.downcast_mut::<M>());
+    }
+
+    pub fn get_named_property_map_ref<M: PropertyMap<T> + 'static, T>(&self, handle : &PropertyHandle<M>) -> Option<&M> {
+        return self.property_maps.get(&handle.name).and_then(|entry| entry.map.downcast_ref::<M>());
+    }
+
+    /// Removes the map with the given name. Returns false, if there is none.
+    pub fn remove_property_map(&mut self, name : &PropertyName) -> bool {
+        return self.property_maps.remove(name).is_some();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.property_maps.is_empty();
+    }
+
+    pub fn contains(&self, name : &PropertyName) -> bool {
+        return self.property_maps.contains_key(name);
+    }
 
-        match self.property_maps.get_mut(&property_type) {
-            Some(map) => {
-                return map.downcast_mut::<M>();
+    /// Returns the names of all stored maps in no particular order
+    pub fn get_names(&self) -> Vec<PropertyName> {
+        return self.property_maps.keys().cloned().collect();
+    }
+
+    /// Returns the names of the maps, which were dropped since the last call, because operations changing the elements
+    /// could not carry them over. This happens for maps, which are out of date, i.e. have a different amount of entries
+    /// than there are elements, and for maps missing in some of the meshes, which are merged.
+    ///
+    /// # Examples
+    /// ```
+    /// subdivide_loop(&mut mesh, 1, None);
+    /// for name in mesh.get_vertex_properties_mut().take_dropped_names() {
+    ///     println!("{:?} was out of date", name);
+    /// }
+    /// ```
+    pub fn take_dropped_names(&mut self) -> Vec<PropertyName> {
+        return std::mem::take(&mut self.dropped_names);
+    }
+
+    fn clone_entry(entry : &PropertyEntry, map : Box<dyn Any>) -> PropertyEntry {
+        return PropertyEntry {
+            map,
+            len: entry.len,
+            remap: entry.remap,
+            interpolate: entry.interpolate,
+            append: entry.append
+        };
+    }
+
+    /// Creates a store with copies of all property maps, after the elements they belong to were reordered, merged or removed.
+    /// Element i of each new map is element sources[i] of the old one. Maps with a different amount of entries than
+    /// the old element count are out of date, so they are dropped and reported by [PropertyStore::take_dropped_names].
+    pub(in crate) fn select(&self, old_count : usize, sources : &[usize]) -> Self {
+
+        let mut store = Self::new();
+        store.dropped_names = self.dropped_names.clone();
+        for (name, entry) in self.property_maps.iter() {
+            if (entry.len)(entry.map.as_ref()) == old_count {
+                store.property_maps.insert(name.clone(), Self::clone_entry(entry, (entry.remap)(entry.map.as_ref(), sources)));
+            } else {
+                store.dropped_names.push(name.clone());
+            }
+        }
+        return store;
+    }
+
+    /// Creates a store with all property maps carried over to a new set of elements, where each new element i is made of the
+    /// old elements sources[i] with their weights. Each map combines the values as declared by [PropertyMap::interpolate].
+    /// New elements without sources receive the default value of the map, or the value of the nearest new element with sources
+    /// for maps without a default value. Like in [PropertyStore::select], maps which are out of date are dropped, as well as
+    /// maps without a default value if no element has sources.
+    pub(in crate) fn interpolate(&self, old_count : usize, sources : &[ElementSources]) -> Self {
+
+        let mut store = Self::new();
+        store.dropped_names = self.dropped_names.clone();
+        for (name, entry) in self.property_maps.iter() {
+            let map = match (entry.len)(entry.map.as_ref()) == old_count {
+                true => (entry.interpolate)(entry.map.as_ref(), sources),
+                false => None,
+            };
+            match map {
+                Some(map) => { store.property_maps.insert(name.clone(), Self::clone_entry(entry, map)); }
+                None => store.dropped_names.push(name.clone()),
             }
-            None => None,
         }
+        return store;
+    }
+
+    /// Rebuilds all property maps in place, like [PropertyStore::select]
+    pub(in crate) fn remap(&mut self, old_count : usize, sources : &[usize]) {
+        *self = self.select(old_count, sources);
+    }
+
+    /// Concatenates the property maps of several stores, each given with the number of elements it belongs to.
+    /// The elements of stores, in which a map is missing or out of date, receive the default value of the map.
+    /// Maps without a default value are dropped in this case.
+    pub(in crate) fn concatenate(stores : &[(&PropertyStore, usize)]) -> Self {
+
+        let mut result = Self::new();
+        let mut dropped_names = Vec::<PropertyName>::new();
+
+        for (store, _) in stores {
+            for (name, template) in store.property_maps.iter() {
+
+                if result.property_maps.contains_key(name) || dropped_names.contains(name) {
+                    continue;
+                }
+
+                //The part of each store is copied from its map, or made of default values
+                let parts = stores.iter().map(|(other, count)| {
+                    return match other.property_maps.get(name) {
+                        Some(entry) if entry.map.as_ref().type_id() == template.map.as_ref().type_id() && (entry.len)(entry.map.as_ref()) == *count =>
+                            Some((entry.remap)(entry.map.as_ref(), &(0..*count).collect::<Vec<usize>>())),
+                        _ => (template.interpolate)(template.map.as_ref(), &vec![ElementSources::new(); *count]),
+                    };
+                }).collect::<Option<Vec<Box<dyn Any>>>>();
+
+                match parts {
+                    Some(parts) => {
+                        let mut map = (template.remap)(template.map.as_ref(), &[]);
+                        for part in parts {
+                            (template.append)(map.as_mut(), part.as_ref());
+                        }
+                        result.property_maps.insert(name.clone(), Self::clone_entry(template, map));
+                    }
+                    None => dropped_names.push(name.clone()),
+                }
+            }
+        }
+
+        for (store, _) in stores {
+            result.dropped_names.extend(store.dropped_names.iter().cloned());
+        }
+        result.dropped_names.extend(dropped_names);
+        return result;
     }
 }
 
+/// Access to the property maps of the vertices. Implementors only provide their [PropertyStore].
 pub trait VertexProperties {
-    fn get_vertex_property<M: PropertyMap<T> + 'static, T>(&mut self, property_type : PropertyType) -> Option<&mut M>;
-    fn add_vertex_property<M: PropertyMap<T> + 'static, T>(&mut self, map : M) -> bool;
+
+    fn get_vertex_properties(&self) -> &PropertyStore;
+    fn get_vertex_properties_mut(&mut self) -> &mut PropertyStore;
+
+    fn get_vertex_property<M: PropertyMap<T> + 'static, T>(&mut self, property_type : PropertyType) -> Option<&mut M> {
+        return self.get_vertex_properties_mut().get_property_map::<M, T>(property_type);
+    }
+
+    fn get_vertex_property_ref<M: PropertyMap<T> + 'static, T>(&self, property_type : PropertyType) -> Option<&M> {
+        return self.get_vertex_properties().get_property_map_ref::<M, T>(property_type);
+    }
+
+    fn add_vertex_property<M: PropertyMap<T> + 'static, T>(&mut self, map : M) -> bool {
+        return self.get_vertex_properties_mut().add_property_map::<M, T>(map);
+    }
+
+    fn get_named_vertex_property<M: PropertyMap<T> + 'static, T>(&mut self, handle : &PropertyHandle<M>) -> Option<&mut M> {
+        return self.get_vertex_properties_mut().get_named_property_map::<M, T>(handle);
+    }
+
+    fn get_named_vertex_property_ref<M: PropertyMap<T> + 'static, T>(&self, handle : &PropertyHandle<M>) -> Option<&M> {
+        return self.get_vertex_properties().get_named_property_map_ref::<M, T>(handle);
+    }
+
+    fn add_named_vertex_property<M: PropertyMap<T> + 'static, T>(&mut self, handle : &PropertyHandle<M>, map : M) -> bool {
+        return self.get_vertex_properties_mut().add_named_property_map::<M, T>(handle, map);
+    }
+
+    fn remove_vertex_property(&mut self, name : &PropertyName) -> bool {
+        return self.get_vertex_properties_mut().remove_property_map(name);
+    }
 }
 
+/// Access to the property maps of the faces, like [VertexProperties]
 pub trait FaceProperties {
-    fn get_face_property<M: PropertyMap<T> + 'static, T>(&mut self, property_type: PropertyType) -> Option<&mut M>;
-    fn add_face_property<M: PropertyMap<T> + 'static, T>(&mut self, map : M) -> bool;
-}
\ No newline at end of file
+
+    fn get_face_properties(&self) -> &PropertyStore;
+    fn get_face_properties_mut(&mut self) -> &mut PropertyStore;
+
+    fn get_face_property<M: PropertyMap<T> + 'static, T>(&mut self, property_type : PropertyType) -> Option<&mut M> {
+        return self.get_face_properties_mut().get_property_map::<M, T>(property_type);
+    }
+
+    fn get_face_property_ref<M: PropertyMap<T> + 'static, T>(&self, property_type : PropertyType) -> Option<&M> {
+        return self.get_face_properties().get_property_map_ref::<M, T>(property_type);
+    }
+
+    fn add_face_property<M: PropertyMap<T> + 'static, T>(&mut self, map : M) -> bool {
+        return self.get_face_properties_mut().add_property_map::<M, T>(map);
+    }
+
+    fn get_named_face_property<M: PropertyMap<T> + 'static, T>(&mut self, handle : &PropertyHandle<M>) -> Option<&mut M> {
+        return self.get_face_properties_mut().get_named_property_map::<M, T>(handle);
+    }
+
+    fn get_named_face_property_ref<M: PropertyMap<T> + 'static, T>(&self, handle : &PropertyHandle<M>) -> Option<&M> {
+        return self.get_face_properties().get_named_property_map_ref::<M, T>(handle);
+    }
+
+    fn add_named_face_property<M: PropertyMap<T> + 'static, T>(&mut self, handle : &PropertyHandle<M>, map : M) -> bool {
+        return self.get_face_properties_mut().add_named_property_map::<M, T>(handle, map);
+    }
+
+    fn remove_face_property(&mut self, name : &PropertyName) -> bool {
+        return self.get_face_properties_mut().remove_property_map(name);
+    }
+}
+
+/// Access to the property maps of the edges, like [VertexProperties]. Edges are addressed by the ids
+/// of [MeshEdges](crate::mesh_edges::MeshEdges).
+pub trait EdgeProperties {
+
+    fn get_edge_properties(&self) -> &PropertyStore;
+    fn get_edge_properties_mut(&mut self) -> &mut PropertyStore;
+
+    fn get_edge_property<M: PropertyMap<T> + 'static, T>(&mut self, property_type : PropertyType) -> Option<&mut M> {
+        return self.get_edge_properties_mut().get_property_map::<M, T>(property_type);
+    }
+
+    fn get_edge_property_ref<M: PropertyMap<T> + 'static, T>(&self, property_type : PropertyType) -> Option<&M> {
+        return self.get_edge_properties().get_property_map_ref::<M, T>(property_type);
+    }
+
+    fn add_edge_property<M: PropertyMap<T> + 'static, T>(&mut self, map : M) -> bool {
+        return self.get_edge_properties_mut().add_property_map::<M, T>(map);
+    }
+
+    fn get_named_edge_property<M: PropertyMap<T> + 'static, T>(&mut self, handle : &PropertyHandle<M>) -> Option<&mut M> {
+        return self.get_edge_properties_mut().get_named_property_map::<M, T>(handle);
+    }
+
+    fn get_named_edge_property_ref<M: PropertyMap<T> + 'static, T>(&self, handle : &PropertyHandle<M>) -> Option<&M> {
+        return self.get_edge_properties().get_named_property_map_ref::<M, T>(handle);
+    }
+
+    fn add_named_edge_property<M: PropertyMap<T> + 'static, T>(&mut self, handle : &PropertyHandle<M>, map : M) -> bool {
+        return self.get_edge_properties_mut().add_named_property_map::<M, T>(handle, map);
+    }
+
+    fn remove_edge_property(&mut self, name : &PropertyName) -> bool {
+        return self.get_edge_properties_mut().remove_property_map(name);
+    }
+}
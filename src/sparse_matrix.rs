@@ -0,0 +1,171 @@
+//! # Sparse Matrices
+//!
+//! Matrices in compressed sparse row format, which only store their non-zero entries. Linear systems, like those
+//! arising from Laplacians on meshes, are solved iteratively with the conjugate gradient method, which only requires
+//! the matrix to be symmetric and positive definite.
+
+pub struct SparseMatrix {
+
+    column_count : usize,
+    row_offsets : Vec<usize>,
+    columns : Vec<usize>,
+    values : Vec<f64>,
+}
+
+impl SparseMatrix {
+
+    /// Creates a matrix from (row, column, value) entries, where entries at the same position are summed up
+    ///
+    /// # Examples
+    /// ```
+    /// let matrix = SparseMatrix::from_triplets(2, 2, &[(0, 0, 2.0), (0, 1, -1.0), (1, 1, 2.0), (1, 1, 1.0)]);
+    ///
+    /// let value = matrix.get(1, 1); //Returns 3
+    /// ```
+    pub fn from_triplets(row_count : usize, column_count : usize, triplets : &[(usize, usize, f64)]) -> Self {
+
+        let mut sorted = triplets.iter().filter(|(row, column, _)| *row < row_count && *column < column_count).copied().collect::<Vec<(usize, usize, f64)>>();
+        sorted.sort_by_key(|(row, column, _)| (*row, *column));
+
+        let mut matrix = Self {
+            column_count,
+            row_offsets: vec![0; row_count + 1],
+            columns: Vec::with_capacity(sorted.len()),
+            values: Vec::with_capacity(sorted.len()),
+        };
+
+        let mut previous = None;
+        for (row, column, value) in sorted {
+            if previous == Some((row, column)) {
+                *matrix.values.last_mut().unwrap() += value;
+                continue;
+            }
+            matrix.columns.push(column);
+            matrix.values.push(value);
+            matrix.row_offsets[row + 1] += 1;
+            previous = Some((row, column));
+        }
+
+        for row in 0..row_count {
+            matrix.row_offsets[row + 1] += matrix.row_offsets[row];
+        }
+        return matrix;
+    }
+
+    pub fn get_row_count(&self) -> usize {
+        return self.row_offsets.len() - 1;
+    }
+
+    pub fn get_column_count(&self) -> usize {
+        return self.column_count;
+    }
+
+    /// Returns the entry at the position, which is zero if it is not stored
+    pub fn get(&self, row : usize, column : usize) -> f64 {
+        return self.get_row(row).find(|(c, _)| *c == column).map(|(_, value)| value).unwrap_or(0.0);
+    }
+
+    /// Returns the stored entries of a row as (column, value) pairs, ordered by column
+    pub fn get_row(&self, row : usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_offsets[row]..self.row_offsets[row + 1];
+        return self.columns[range.clone()].iter().copied().zip(self.values[range].iter().copied());
+    }
+
+    pub fn get_diagonal(&self) -> Vec<f64> {
+        return (0..self.get_row_count()).map(|row| self.get(row, row)).collect();
+    }
+
+    /// Computes the product with a vector, whose length must match the column count
+    pub fn multiply(&self, x : &[f64]) -> Vec<f64> {
+        return (0..self.get_row_count()).map(|row| self.get_row(row).map(|(column, value)| value * x[column]).sum()).collect();
+    }
+
+    /// Solves the system A x = b for a symmetric positive definite matrix with the conjugate gradient method,
+    /// preconditioned by the diagonal. Stops when the residual is below the tolerance relative to b, and returns
+    /// None if this is not reached within the maximum number of iterations.
+    ///
+    /// # Examples
+    /// ```
+    /// let x = matrix.solve_conjugate_gradient(&b, 1e-10, 1000).unwrap();
+    /// ```
+    pub fn solve_conjugate_gradient(&self, b : &[f64], tolerance : f64, max_iterations : usize) -> Option<Vec<f64>> {
+
+        let dot = |a : &[f64], b : &[f64]| -> f64 { a.iter().zip(b).map(|(x, y)| x * y).sum() };
+        let inverse_diagonal = self.get_diagonal().iter().map(|d| if *d != 0.0 { 1.0 / d } else { 1.0 }).collect::<Vec<f64>>();
+
+        let mut x = vec![0.0; b.len()];
+        let mut residual = b.to_vec();
+        let threshold = tolerance * dot(b, b).sqrt();
+        if dot(&residual, &residual).sqrt() <= threshold {
+            return Some(x);
+        }
+
+        let mut preconditioned = residual.iter().zip(&inverse_diagonal).map(|(r, d)| r * d).collect::<Vec<f64>>();
+        let mut direction = preconditioned.clone();
+        let mut rz = dot(&residual, &preconditioned);
+
+        for _ in 0..max_iterations {
+
+            let product = self.multiply(&direction);
+            let alpha = rz / dot(&direction, &product);
+            if !alpha.is_finite() {
+                return None;
+            }
+
+            x.iter_mut().zip(&direction).for_each(|(x, d)| *x += alpha * d);
+            residual.iter_mut().zip(&product).for_each(|(r, p)| *r -= alpha * p);
+            if dot(&residual, &residual).sqrt() <= threshold {
+                return Some(x);
+            }
+
+            preconditioned = residual.iter().zip(&inverse_diagonal).map(|(r, d)| r * d).collect();
+            let next_rz = dot(&residual, &preconditioned);
+            let beta = next_rz / rz;
+            rz = next_rz;
+            direction.iter_mut().zip(&preconditioned).for_each(|(d, z)| *d = z + beta * *d);
+        }
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::sparse_matrix::SparseMatrix;
+
+    #[test]
+    fn test_from_triplets() {
+
+        let matrix = SparseMatrix::from_triplets(3, 3, &[(2, 0, 1.0), (0, 0, 2.0), (1, 1, 3.0), (0, 0, 1.0), (5, 0, 1.0)]);
+
+        assert_eq!(matrix.get_row_count(), 3);
+        assert_eq!(matrix.get(0, 0), 3.0);
+        assert_eq!(matrix.get(0, 1), 0.0);
+        assert_eq!(matrix.get_row(2).collect::<Vec<(usize, f64)>>(), Vec::from([(0, 1.0)]));
+        assert_eq!(matrix.multiply(&[1.0, 2.0, 3.0]), Vec::from([3.0, 6.0, 1.0]));
+    }
+
+    #[test]
+    fn test_conjugate_gradient() {
+
+        //1D Poisson problem with a tridiagonal matrix
+        let n = 50;
+        let mut triplets = Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, 2.0));
+            if i > 0 {
+                triplets.push((i, i - 1, -1.0));
+                triplets.push((i - 1, i, -1.0));
+            }
+        }
+        let matrix = SparseMatrix::from_triplets(n, n, &triplets);
+        let b = vec![1.0; n];
+
+        let x = matrix.solve_conjugate_gradient(&b, 1e-12, 1000).unwrap();
+        let product = matrix.multiply(&x);
+        assert!(product.iter().zip(&b).all(|(p, b)| (p - b).abs() < 1e-9));
+
+        //Not converging within the iterations
+        assert!(matrix.solve_conjugate_gradient(&b, 1e-12, 2).is_none());
+    }
+}
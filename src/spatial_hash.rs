@@ -0,0 +1,132 @@
+//! # Spatial Hashing
+//!
+//! A spatial hash divides space into a uniform grid of cubic cells and only stores the cells, which actually
+//! contain points. Finding points close to a position only requires looking at the few cells around it.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use num_traits::Num;
+use num_traits::Float;
+
+use crate::vector::FloatVector;
+use crate::vector::Vec3;
+
+pub struct SpatialHash<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> {
+
+    cell_size : U,
+    cells : HashMap<(i64, i64, i64), Vec<usize>>,
+    points : Vec<T>,
+    number_type : PhantomData<U>
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float> SpatialHash<T, U> {
+
+    /// Creates an empty spatial hash. For best performance, the cell size should be close to the radius
+    /// used for queries. Cell sizes which are not strictly positive are replaced by one.
+    pub fn new(cell_size : U) -> Self {
+        Self {
+            cell_size: if cell_size > U::zero() { cell_size } else { U::one() },
+            cells: HashMap::<(i64, i64, i64), Vec<usize>>::new(),
+            points: Vec::<T>::new(),
+            number_type: PhantomData
+        }
+    }
+
+    /// Adds a point to the spatial hash and returns its index
+    pub fn insert(&mut self, point : T) -> usize {
+
+        let idx = self.points.len();
+        self.points.push(point);
+        self.cells.entry(self.cell_of(&point)).or_default().push(idx);
+        return idx;
+    }
+
+    /// Returns the index of the point closest to the given position, as long as it is within the radius
+    ///
+    /// # Examples
+    /// ```
+    /// let mut hash = SpatialHash::<Vec3f, f32>::new(0.1);
+    /// hash.insert(Vec3f::new(0.0, 0.0, 0.0));
+    ///
+    /// let nearest = hash.find_nearest(Vec3f::new(0.05, 0.0, 0.0), 0.1); //Returns Some(0)
+    /// ```
+    pub fn find_nearest(&self, point : T, radius : U) -> Option<usize> {
+
+        let (cx, cy, cz) = self.cell_of(&point);
+        let range = (radius / self.cell_size).ceil().to_i64().unwrap_or(0).max(0);
+
+        let mut nearest : Option<(usize, U)> = None;
+
+        for x in cx - range..=cx + range {
+            for y in cy - range..=cy + range {
+                for z in cz - range..=cz + range {
+
+                    if let Some(cell) = self.cells.get(&(x, y, z)) {
+                        for idx in cell {
+
+                            let distance = (self.points[*idx] - point).length();
+                            if distance <= radius && nearest.is_none_or(|(_, d)| distance < d) {
+                                nearest = Some((*idx, distance));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        return nearest.map(|(idx, _)| idx);
+    }
+
+    pub fn get_points(&self) -> &Vec<T> {
+        return &self.points;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.points.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.points.is_empty();
+    }
+
+    // Private
+
+    fn cell_of(&self, point : &T) -> (i64, i64, i64) {
+
+        let to_cell = |v : U| (v / self.cell_size).floor().to_i64().unwrap_or(0);
+        return (to_cell(point.x()), to_cell(point.y()), to_cell(point.z()));
+    }
+}
+
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::spatial_hash::SpatialHash;
+    use crate::vector::Vec3f;
+    use crate::vector::Vec3;
+
+    #[test]
+    fn test_find_nearest() {
+
+        let mut hash = SpatialHash::<Vec3f, f32>::new(0.1);
+        hash.insert(Vec3f::new(0.0, 0.0, 0.0));
+        hash.insert(Vec3f::new(0.12, 0.0, 0.0));
+        hash.insert(Vec3f::new(1.0, 1.0, 1.0));
+
+        assert_eq!(hash.find_nearest(Vec3f::new(0.09, 0.0, 0.0), 0.1), Some(1));
+        assert_eq!(hash.find_nearest(Vec3f::new(-0.05, 0.0, 0.0), 0.1), Some(0));
+        assert_eq!(hash.find_nearest(Vec3f::new(0.5, 0.5, 0.5), 0.1), None);
+    }
+
+    #[test]
+    fn test_exact_match() {
+
+        let mut hash = SpatialHash::<Vec3f, f32>::new(0.0);
+        hash.insert(Vec3f::new(0.5, 0.5, 0.5));
+
+        assert_eq!(hash.find_nearest(Vec3f::new(0.5, 0.5, 0.5), 0.0), Some(0));
+        assert_eq!(hash.find_nearest(Vec3f::new(0.5, 0.5, 0.50001), 0.0), None);
+    }
+}
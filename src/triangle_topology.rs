@@ -0,0 +1,314 @@
+//! # Triangle Topology
+//!
+//! Connectivity of a triangle mesh which can be edited locally, by splitting, collapsing and flipping edges. Each vertex
+//! knows its incident triangles, so that neighbourhoods are found without traversing the whole mesh. Removed vertices
+//! and triangles are only marked as such and keep their ids, so that ids stay valid while the topology is edited.
+//! Positions and other attributes are not stored, but kept by the algorithms in arrays indexed by the vertex ids.
+
+pub struct TriangleTopology {
+
+    triangles : Vec<[usize; 3]>,
+    face_alive : Vec<bool>,
+    vertex_faces : Vec<Vec<usize>>,
+    vertex_alive : Vec<bool>,
+}
+
+impl TriangleTopology {
+
+    /// Creates the topology of triangles made of the vertices 0..vertex_count
+    ///
+    /// # Examples
+    /// ```
+    /// let triangles = mesh.get_indices().chunks(3).map(|t| [t[0] as usize, t[1] as usize, t[2] as usize]).collect();
+    /// let mut topology = TriangleTopology::new(mesh.get_vertices().len(), triangles);
+    /// ```
+    pub fn new(vertex_count : usize, triangles : Vec<[usize; 3]>) -> Self {
+
+        let mut vertex_faces = vec![Vec::<usize>::new(); vertex_count];
+        for (f, triangle) in triangles.iter().enumerate() {
+            triangle.iter().for_each(|v| vertex_faces[*v].push(f));
+        }
+
+        Self {
+            face_alive: vec![true; triangles.len()],
+            triangles,
+            vertex_faces,
+            vertex_alive: vec![true; vertex_count],
+        }
+    }
+
+    /// Returns the number of vertex ids, including those of removed vertices
+    pub fn get_vertex_count(&self) -> usize {
+        return self.vertex_alive.len();
+    }
+
+    /// Returns the number of face ids, including those of removed faces
+    pub fn get_face_count(&self) -> usize {
+        return self.face_alive.len();
+    }
+
+    pub fn is_vertex_alive(&self, v : usize) -> bool {
+        return self.vertex_alive[v];
+    }
+
+    pub fn is_face_alive(&self, f : usize) -> bool {
+        return self.face_alive[f];
+    }
+
+    pub fn get_triangle(&self, f : usize) -> [usize; 3] {
+        return self.triangles[f];
+    }
+
+    /// Returns the remaining triangles, together with their face ids
+    pub fn get_triangles(&self) -> impl Iterator<Item = (usize, [usize; 3])> + '_ {
+        return self.triangles.iter().enumerate().filter(|(f, _)| self.face_alive[*f]).map(|(f, t)| (f, *t));
+    }
+
+    pub fn get_vertex_faces(&self, v : usize) -> &[usize] {
+        return &self.vertex_faces[v];
+    }
+
+    /// Returns the vertices sharing an edge with the vertex, in the order of their first appearance in its triangles
+    pub fn get_neighbours(&self, v : usize) -> Vec<usize> {
+
+        let mut neighbours = Vec::<usize>::new();
+        for w in self.vertex_faces[v].iter().flat_map(|f| self.triangles[*f]).filter(|w| *w != v) {
+            if !neighbours.contains(&w) {
+                neighbours.push(w);
+            }
+        }
+        return neighbours;
+    }
+
+    pub fn get_valence(&self, v : usize) -> usize {
+        return self.get_neighbours(v).len();
+    }
+
+    /// Returns the triangles containing both vertices, which are two for inner edges and one for boundary edges
+    pub fn get_shared_faces(&self, a : usize, b : usize) -> Vec<usize> {
+        return self.vertex_faces[a].iter().filter(|f| self.triangles[**f].contains(&b)).copied().collect();
+    }
+
+    /// Returns the vertices opposite of the edge in its triangles
+    pub fn get_opposite_vertices(&self, a : usize, b : usize) -> Vec<usize> {
+        return self.get_shared_faces(a, b).iter().flat_map(|f| self.triangles[*f]).filter(|v| *v != a && *v != b).collect();
+    }
+
+    /// Returns the unique edges of the remaining triangles, where the smaller vertex id comes first
+    pub fn get_edges(&self) -> Vec<[usize; 2]> {
+
+        let mut edges = self.get_triangles().flat_map(|(_, t)| [0, 1, 2].map(|i| {
+            let (a, b) = (t[i], t[(i + 1) % 3]);
+            return [a.min(b), a.max(b)];
+        })).collect::<Vec<[usize; 2]>>();
+        edges.sort();
+        edges.dedup();
+        return edges;
+    }
+
+    pub fn is_boundary_edge(&self, a : usize, b : usize) -> bool {
+        return self.get_shared_faces(a, b).len() == 1;
+    }
+
+    pub fn is_boundary_vertex(&self, v : usize) -> bool {
+        return self.get_neighbours(v).into_iter().any(|w| self.is_boundary_edge(v, w));
+    }
+
+    /// Adds a vertex without any triangles
+    pub fn add_vertex(&mut self) -> usize {
+        self.vertex_alive.push(true);
+        self.vertex_faces.push(Vec::new());
+        return self.vertex_alive.len() - 1;
+    }
+
+    fn add_face(&mut self, triangle : [usize; 3]) -> usize {
+        let f = self.triangles.len();
+        self.triangles.push(triangle);
+        self.face_alive.push(true);
+        triangle.iter().for_each(|v| self.vertex_faces[*v].push(f));
+        return f;
+    }
+
+    /// Splits the edge by a new vertex, which is connected to the opposite vertices of the edge. Each triangle of the edge
+    /// keeps the half at vertex a, while the half at vertex b is added as a new triangle with the same orientation.
+    /// Returns the new vertex, or None if the vertices share no triangle.
+    ///
+    /// # Examples
+    /// ```
+    /// let m = topology.split_edge(a, b).unwrap();
+    /// positions.push(0.5 * (positions[a] + positions[b]));
+    /// ```
+    pub fn split_edge(&mut self, a : usize, b : usize) -> Option<usize> {
+
+        let shared = self.get_shared_faces(a, b);
+        if shared.is_empty() {
+            return None;
+        }
+
+        let m = self.add_vertex();
+        for f in shared {
+            let triangle = self.triangles[f];
+            let i = (0..3).find(|i| {
+                let (p, q) = (triangle[*i], triangle[(*i + 1) % 3]);
+                return (p == a && q == b) || (p == b && q == a);
+            }).unwrap();
+            let (p, q, r) = (triangle[i], triangle[(i + 1) % 3], triangle[(i + 2) % 3]);
+
+            //The triangle (p, q, r) becomes (p, m, r) and (m, q, r), where b is either p or q
+            let (kept, added) = ([p, m, r], [m, q, r]);
+            let (kept, added) = if p == a { (kept, added) } else { (added, kept) };
+
+            self.vertex_faces[b].retain(|g| *g != f);
+            self.triangles[f] = kept;
+            self.vertex_faces[m].push(f);
+            self.add_face(added);
+        }
+        return Some(m);
+    }
+
+    /// Checks whether collapsing the edge keeps the mesh manifold. The edge must border one or two triangles, the only
+    /// common neighbours of its vertices must be the opposite vertices (link condition), these must keep at least three
+    /// neighbours (two on the boundary), and an inner edge must not connect two boundary vertices.
+    pub fn can_collapse_edge(&self, a : usize, b : usize) -> bool {
+
+        let shared = self.get_shared_faces(a, b);
+        if shared.is_empty() || shared.len() > 2 {
+            return false;
+        }
+
+        let opposite = self.get_opposite_vertices(a, b);
+        let neighbours = self.get_neighbours(a);
+        if self.get_neighbours(b).iter().any(|v| neighbours.contains(v) && !opposite.contains(v)) {
+            return false;
+        }
+        if opposite.iter().any(|v| self.get_valence(*v) <= if self.is_boundary_vertex(*v) { 2 } else { 3 }) {
+            return false;
+        }
+        return shared.len() == 1 || !(self.is_boundary_vertex(a) && self.is_boundary_vertex(b));
+    }
+
+    /// Merges vertex b into vertex a, which removes the triangles of the edge. Returns the number of removed triangles.
+    /// Whether the collapse is valid is not checked, see [TriangleTopology::can_collapse_edge].
+    pub fn collapse_edge(&mut self, a : usize, b : usize) -> usize {
+
+        let shared = self.get_shared_faces(a, b);
+        for f in &shared {
+            self.face_alive[*f] = false;
+            for v in self.triangles[*f] {
+                self.vertex_faces[v].retain(|g| g != f);
+            }
+        }
+
+        for f in std::mem::take(&mut self.vertex_faces[b]) {
+            self.triangles[f].iter_mut().filter(|v| **v == b).for_each(|v| *v = a);
+            self.vertex_faces[a].push(f);
+        }
+        self.vertex_alive[b] = false;
+        return shared.len();
+    }
+
+    /// Checks whether the edge can be flipped, i.e. whether it borders two consistently oriented triangles and its opposite
+    /// vertices are not connected yet
+    pub fn can_flip_edge(&self, a : usize, b : usize) -> bool {
+        return self.get_flip(a, b).is_some();
+    }
+
+    /// Finds the two triangles (a, b, c) and (b, a, d) of the edge together with c and d
+    fn get_flip(&self, a : usize, b : usize) -> Option<([usize; 2], [usize; 2])> {
+
+        let shared = self.get_shared_faces(a, b);
+        if shared.len() != 2 {
+            return None;
+        }
+
+        let rotate = |f : usize, first : usize| -> [usize; 3] {
+            let t = self.triangles[f];
+            let i = t.iter().position(|v| *v == first).unwrap();
+            return [t[i], t[(i + 1) % 3], t[(i + 2) % 3]];
+        };
+        let (f, g) = match rotate(shared[0], a)[1] == b {
+            true => (shared[0], shared[1]),
+            false => (shared[1], shared[0]),
+        };
+        let (first, second) = (rotate(f, a), rotate(g, b));
+        if first[1] != b || second[1] != a {
+            return None;
+        }
+
+        let (c, d) = (first[2], second[2]);
+        if c == d || self.get_neighbours(c).contains(&d) {
+            return None;
+        }
+        return Some(([f, g], [c, d]));
+    }
+
+    /// Replaces the edge by the one between its opposite vertices, keeping the orientation of the triangles. Returns the new
+    /// edge, or None if the edge cannot be flipped.
+    pub fn flip_edge(&mut self, a : usize, b : usize) -> Option<[usize; 2]> {
+
+        let ([f, g], [c, d]) = self.get_flip(a, b)?;
+
+        self.triangles[f] = [a, d, c];
+        self.triangles[g] = [b, c, d];
+        self.vertex_faces[a].retain(|h| *h != g);
+        self.vertex_faces[b].retain(|h| *h != f);
+        self.vertex_faces[c].push(g);
+        self.vertex_faces[d].push(f);
+        return Some([c, d]);
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::triangle_topology::TriangleTopology;
+
+    /// Square made of the two triangles (0, 1, 2) and (0, 2, 3)
+    fn create_square() -> TriangleTopology {
+        return TriangleTopology::new(4, Vec::from([[0, 1, 2], [0, 2, 3]]));
+    }
+
+    #[test]
+    fn test_split_and_flip() {
+
+        let mut topology = create_square();
+        assert_eq!(topology.get_edges(), Vec::from([[0, 1], [0, 2], [0, 3], [1, 2], [2, 3]]));
+        assert!(topology.is_boundary_edge(0, 1));
+        assert!(!topology.is_boundary_edge(0, 2));
+
+        //The diagonal is flipped and keeps the orientation of the triangles
+        assert_eq!(topology.flip_edge(0, 2), Some([3, 1]));
+        assert!(topology.get_shared_faces(0, 2).is_empty());
+        assert_eq!(topology.get_triangle(0), [2, 3, 1]);
+        assert_eq!(topology.get_triangle(1), [0, 1, 3]);
+        assert!(!topology.can_flip_edge(0, 1));
+
+        //Splitting the new diagonal gives four triangles around the new vertex
+        let m = topology.split_edge(1, 3).unwrap();
+        assert_eq!(m, 4);
+        assert_eq!(topology.get_triangles().count(), 4);
+        assert_eq!(topology.get_valence(m), 4);
+        assert!(topology.get_triangles().all(|(_, t)| t.contains(&m)));
+        assert!(topology.split_edge(0, 2).is_none());
+    }
+
+    #[test]
+    fn test_collapse() {
+
+        //Strip of two triangles, whose inner edge connects two boundary vertices
+        let topology = TriangleTopology::new(4, Vec::from([[0, 1, 2], [1, 3, 2]]));
+        assert!(!topology.can_collapse_edge(1, 2));
+
+        //Tetrahedron, where any collapse would leave two triangles on top of each other
+        let topology = TriangleTopology::new(4, Vec::from([[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]]));
+        assert!(!topology.can_collapse_edge(0, 1));
+
+        //Fan around the inner vertex 4 with an additional triangle at the boundary
+        let mut topology = TriangleTopology::new(6, Vec::from([[0, 1, 4], [1, 2, 4], [2, 3, 4], [3, 0, 4], [0, 5, 1]]));
+        assert!(topology.can_collapse_edge(4, 1));
+        assert_eq!(topology.collapse_edge(4, 1), 2);
+        assert!(!topology.is_vertex_alive(1));
+        assert_eq!(topology.get_triangles().count(), 3);
+        assert_eq!(topology.get_neighbours(4), Vec::from([2, 3, 0, 5]));
+    }
+}
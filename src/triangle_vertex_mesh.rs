@@ -11,15 +11,18 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::hash::Hash;
 
-use crate::property_map::PropertyType;
-use crate::property_map::PropertyMap;
 use crate::property_map::PropertyStore;
+use crate::property_map::ElementSources;
 use crate::property_map::VertexProperties;
 use crate::property_map::FaceProperties;
+use crate::property_map::EdgeProperties;
 use crate::vector::FloatVector;
 use crate::vector::Vec3;
 use crate::mesh::Mesh;
 use crate::mesh_components::MeshComponent;
+use crate::mesh_edges::MeshEdges;
+use crate::mesh_edges::get_edge_sources;
+use crate::mesh_edges::get_merged_edge_sources;
 
 use num_traits::PrimInt;
 use num_traits::Num;
@@ -33,6 +36,7 @@ pub struct TriangleVertexMesh<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<
     vertices : Vec<T>,
     indices : Vec<IndexType>,
     vertex_properties : PropertyStore,
+    edge_properties : PropertyStore,
     face_properties : PropertyStore,
     number_type : PhantomData<U>
 }
@@ -44,6 +48,7 @@ impl<T: Vec3<U> + FloatVector<U>, U: Num + PartialOrd<U> + Float, IndexType : Pr
             vertices: Vec::<T>::new(),
             indices: Vec::<IndexType>::new(),
             vertex_properties: PropertyStore::new(),
+            edge_properties: PropertyStore::new(),
             face_properties: PropertyStore::new(),
             number_type: PhantomData
         }
@@ -61,6 +66,7 @@ impl<T: Vec3<U> + FloatVector<U>, U: Num + PartialOrd<U> + Float, IndexType : Pr
             vertices: vertices,
             indices: indices,
             vertex_properties: PropertyStore::new(),
+            edge_properties: PropertyStore::new(),
             face_properties: PropertyStore::new(),
             number_type: PhantomData
         })
@@ -72,8 +78,191 @@ impl<T: Vec3<U> + FloatVector<U>, U: Num + PartialOrd<U> + Float, IndexType : Pr
         return &self.indices;
     }
 
+    /// Returns the vertices for modification. A slice is returned, so the number of vertices stays in line with the vertex properties.
+    pub fn get_mut_vertices(&mut self) -> &mut [T] {
+        return &mut self.vertices;
+    }
+
+    /// Creates a mesh from a selection of the vertices and triangles of this mesh, copying all property maps accordingly.
+    /// Vertex i of the new mesh is the old vertex vertex_sources[i] and triangle j the old triangle face_sources[j],
+    /// with each of its indices translated by vertex_map from the old to the new vertex index.
+    pub(in crate) fn extract(&self, vertex_sources : &[usize], face_sources : &[usize], vertex_map : &[usize]) -> Self {
+
+        let mut indices = Vec::<IndexType>::with_capacity(face_sources.len() * 3);
+        for f in face_sources {
+            for i in 0..3 {
+                indices.push(NumCast::from(vertex_map[self.indices[f * 3 + i].to_usize().unwrap()]).unwrap());
+            }
+        }
+
+        let single_sources = vertex_sources.iter().map(|v| Vec::from([(*v, 1.0)])).collect::<Vec<ElementSources>>();
+        return Self {
+            vertices: vertex_sources.iter().map(|v| self.vertices[*v]).collect(),
+            edge_properties: self.carry_edge_properties(&indices, &single_sources),
+            indices,
+            vertex_properties: self.vertex_properties.select(self.vertices.len(), vertex_sources),
+            face_properties: self.face_properties.select(self.get_face_count(), face_sources),
+            number_type: PhantomData
+        };
+    }
+
+    /// Replaces the vertices and triangles by a selection of the existing ones, like [TriangleVertexMesh::extract]
+    pub(in crate) fn rebuild(&mut self, vertex_sources : &[usize], face_sources : &[usize], vertex_map : &[usize]) {
+        *self = self.extract(vertex_sources, face_sources, vertex_map);
+    }
+
+    /// Creates a copy of the mesh, in which each triangle corner uses the vertex corner_vertices[c] of the new vertex buffer.
+    /// Each new vertex is a copy of the old vertex of its first corner, including the vertex properties. Triangles and their
+    /// properties stay the same. Returns None, if the new vertices cannot be addressed with the index type.
+    pub(in crate) fn split_corners(&self, corner_vertices : &[usize]) -> Option<Self> {
+
+        let vertex_count = corner_vertices.iter().map(|v| v + 1).max().unwrap_or(0);
+        let mut vertex_sources = vec![usize::MAX; vertex_count];
+        let mut indices = Vec::<IndexType>::with_capacity(corner_vertices.len());
+
+        for (corner, new_vertex) in corner_vertices.iter().enumerate() {
+            if vertex_sources[*new_vertex] == usize::MAX {
+                vertex_sources[*new_vertex] = self.indices[corner].to_usize().unwrap();
+            }
+            indices.push(NumCast::from(*new_vertex)?);
+        }
+
+        let face_sources = (0..self.get_face_count()).collect::<Vec<usize>>();
+        let single_sources = vertex_sources.iter().map(|v| Vec::from([(*v, 1.0)])).collect::<Vec<ElementSources>>();
+        return Some(Self {
+            vertices: vertex_sources.iter().map(|v| self.vertices[*v]).collect(),
+            edge_properties: self.carry_edge_properties(&indices, &single_sources),
+            indices,
+            vertex_properties: self.vertex_properties.select(self.vertices.len(), &vertex_sources),
+            face_properties: self.face_properties.select(self.get_face_count(), &face_sources),
+            number_type: PhantomData
+        });
+    }
+
+    /// Combines several meshes into one, offsetting the indices of each mesh by the vertices of the previous ones.
+    /// Meshes without a property map receive its default values, maps without one are only kept, if all meshes have them.
+    /// Returns None, if the indices exceed the index type.
+    pub(in crate) fn concatenate(meshes : &[&Self]) -> Option<Self> {
+
+        let mut vertices = Vec::<T>::new();
+        let mut indices = Vec::<IndexType>::new();
+
+        for mesh in meshes {
+            let offset = vertices.len();
+            for idx in &mesh.indices {
+                indices.push(NumCast::from(idx.to_usize().unwrap() + offset)?);
+            }
+            vertices.extend_from_slice(&mesh.vertices);
+        }
+
+        let vertex_stores = meshes.iter().map(|m| (&m.vertex_properties, m.vertices.len())).collect::<Vec<(&PropertyStore, usize)>>();
+        let face_stores = meshes.iter().map(|m| (&m.face_properties, m.get_face_count())).collect::<Vec<(&PropertyStore, usize)>>();
+
+        //Since the vertices of the meshes are disjoint, the edges of the result are the edges of all meshes in order
+        let has_edge_properties = meshes.iter().any(|m| !m.edge_properties.is_empty());
+        let edge_stores = meshes.iter().map(|m| {
+            let edge_count = if has_edge_properties { Self::make_edges(&m.indices).get_edge_count() } else { 0 };
+            return (&m.edge_properties, edge_count);
+        }).collect::<Vec<(&PropertyStore, usize)>>();
+
+        return Some(Self {
+            vertices,
+            indices,
+            vertex_properties: PropertyStore::concatenate(&vertex_stores),
+            edge_properties: PropertyStore::concatenate(&edge_stores),
+            face_properties: PropertyStore::concatenate(&face_stores),
+            number_type: PhantomData
+        });
+    }
+
+    /// Appends vertices and triangles to the mesh. Property maps are extended by interpolating the values of existing elements:
+    /// the new vertex i is made of the weighted vertices vertex_sources[i] and the new triangle j receives the properties of face_sources[j].
+    pub(in crate) fn extend(&mut self, vertices : &[T], vertex_sources : &[ElementSources], indices : &[IndexType], face_sources : &[usize]) {
+
+        let old_vertex_count = self.vertices.len();
+        let old_face_count = self.get_face_count();
+
+        let vertex_sources = (0..old_vertex_count).map(|v| Vec::from([(v, 1.0)])).chain(vertex_sources.iter().cloned()).collect::<Vec<ElementSources>>();
+        let face_sources = (0..old_face_count).chain(face_sources.iter().copied()).collect::<Vec<usize>>();
+
+        let new_indices = self.indices.iter().chain(indices.iter()).copied().collect::<Vec<IndexType>>();
+        self.edge_properties = self.carry_edge_properties(&new_indices, &vertex_sources);
+
+        self.vertices.extend_from_slice(vertices);
+        self.indices = new_indices;
+
+        self.vertex_properties = self.vertex_properties.interpolate(old_vertex_count, &vertex_sources);
+        self.face_properties.remap(old_face_count, &face_sources);
+    }
+
+    /// Replaces all vertices and triangles, carrying the property maps over to the new elements. Vertex i of the new mesh
+    /// is made of the old vertices vertex_sources[i] with their weights, which sum up to one, and triangle j of the weighted
+    /// old triangles face_sources[j]. The values are combined as declared by each property map. Edges are carried over
+    /// with the given sources, or by matching their vertices with [get_edge_sources] if there are none.
+    pub(in crate) fn replace(&mut self, vertices : Vec<T>, vertex_sources : &[ElementSources], indices : Vec<IndexType>, face_sources : &[ElementSources],
+        edge_sources : Option<&[ElementSources]>)
+    {
+        self.edge_properties = match edge_sources {
+            Some(edge_sources) => {
+                let old_edge_count = if self.edge_properties.is_empty() { 0 } else { Self::make_edges(&self.indices).get_edge_count() };
+                self.edge_properties.interpolate(old_edge_count, edge_sources)
+            },
+            None => self.carry_edge_properties(&indices, vertex_sources),
+        };
+        self.vertex_properties = self.vertex_properties.interpolate(self.vertices.len(), vertex_sources);
+        self.face_properties = self.face_properties.interpolate(self.get_face_count(), face_sources);
+        self.vertices = vertices;
+        self.indices = indices;
+    }
+
+    /// Replaces the triangles by the same number of triangles on the same vertices, e.g. with a different winding. Vertex
+    /// and face properties stay the same, while the edge properties are carried over to the edges with the same vertices,
+    /// as edge ids follow the order in which the edges appear in the triangles.
+    pub(in crate) fn replace_indices(&mut self, indices : Vec<IndexType>) {
+
+        let vertex_sources = (0..self.vertices.len()).map(|v| Vec::from([(v, 1.0)])).collect::<Vec<ElementSources>>();
+        let face_sources = (0..indices.len() / 3).map(|f| Vec::from([(f, 1.0)])).collect::<Vec<ElementSources>>();
+        let edge_sources = match self.edge_properties.is_empty() {
+            true => None,
+            false => Some(get_merged_edge_sources(&Self::make_edges(&self.indices), &Self::make_edges(&indices), &(0..self.vertices.len()).collect::<Vec<usize>>())),
+        };
+
+        let vertices = self.vertices.clone();
+        self.replace(vertices, &vertex_sources, indices, &face_sources, edge_sources.as_deref());
+    }
+
+    /// Creates a mesh from its parts, without checking that the property maps match the vertices, edges and triangles
+    pub(in crate) fn from_parts(vertices : Vec<T>, indices : Vec<IndexType>, vertex_properties : PropertyStore, edge_properties : PropertyStore,
+        face_properties : PropertyStore) -> Self
+    {
+        Self {
+            vertices,
+            indices,
+            vertex_properties,
+            edge_properties,
+            face_properties,
+            number_type: PhantomData
+        }
+    }
+
     // Private
 
+    fn make_edges(indices : &[IndexType]) -> MeshEdges {
+        return MeshEdges::from_faces(indices.chunks(3).map(|t| t.iter().map(|idx| idx.to_usize().unwrap()).collect::<Vec<usize>>()));
+    }
+
+    /// Carries the edge properties over to new triangles, where the new vertex i is made of the old vertices vertex_sources[i]
+    fn carry_edge_properties(&self, indices : &[IndexType], vertex_sources : &[ElementSources]) -> PropertyStore {
+
+        if self.edge_properties.is_empty() {
+            return PropertyStore::new();
+        }
+
+        let old_edges = Self::make_edges(&self.indices);
+        let new_edges = Self::make_edges(indices);
+        return self.edge_properties.interpolate(old_edges.get_edge_count(), &get_edge_sources(&old_edges, &new_edges, vertex_sources));
+    }
+
     fn make_vertex_vertex_incidence_map(&self, map : &mut HashMap<IndexType, HashSet<IndexType>>) {
 
         let size = self.indices.len();
@@ -256,22 +445,35 @@ impl<T: Vec3<U> + FloatVector<U>, U: Num + PartialOrd<U> + Float, IndexType : Pr
 }
 
 impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> VertexProperties for TriangleVertexMesh<T, U, IndexType> {
-    fn get_vertex_property<M: PropertyMap<V> + 'static, V>(&mut self, property_type : PropertyType) -> Option<&mut M> {
-        return self.vertex_properties.get_property_map::<M, V>(property_type);
+
+    fn get_vertex_properties(&self) -> &PropertyStore {
+        return &self.vertex_properties;
     }
 
-    fn add_vertex_property<M: PropertyMap<V> + 'static, V>(&mut self, map : M) -> bool {
-        return self.vertex_properties.add_property_map::<M, V>(map);
+    fn get_vertex_properties_mut(&mut self) -> &mut PropertyStore {
+        return &mut self.vertex_properties;
+    }
+}
+
+impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> EdgeProperties for TriangleVertexMesh<T, U, IndexType> {
+
+    fn get_edge_properties(&self) -> &PropertyStore {
+        return &self.edge_properties;
+    }
+
+    fn get_edge_properties_mut(&mut self) -> &mut PropertyStore {
+        return &mut self.edge_properties;
     }
 }
 
 impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : PrimInt + Hash + Unsigned> FaceProperties for TriangleVertexMesh<T, U, IndexType> {
-    fn get_face_property<M: PropertyMap<V> + 'static, V>(&mut self, property_type: PropertyType) -> Option<&mut M> {
-        return self.face_properties.get_property_map::<M, V>(property_type);
+
+    fn get_face_properties(&self) -> &PropertyStore {
+        return &self.face_properties;
     }
 
-    fn add_face_property<M: PropertyMap<V> + 'static, V>(&mut self, map : M) -> bool {
-        return self.face_properties.add_property_map::<M, V>(map);
+    fn get_face_properties_mut(&mut self) -> &mut PropertyStore {
+        return &mut self.face_properties;
     }
 }
 
@@ -287,19 +489,14 @@ impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : P
             MeshComponent::VERTEX => 
                 match incident_type {
                     MeshComponent::VERTEX => self.make_vertex_vertex_incidence_map(&mut map),
-                    MeshComponent::EDGE => todo!(),
+                    MeshComponent::EDGE => Self::make_edges(&self.indices).make_incidence_map(origin_type, incident_type, &mut map),
                     MeshComponent::FACE => self.make_vertex_face_incidence_map(&mut map)
                 }
-            MeshComponent::EDGE => 
-                match incident_type {
-                    MeshComponent::VERTEX => todo!(),
-                    MeshComponent::EDGE => todo!(),
-                    MeshComponent::FACE => todo!()
-                }
+            MeshComponent::EDGE => Self::make_edges(&self.indices).make_incidence_map(origin_type, incident_type, &mut map),
             MeshComponent::FACE => 
                 match incident_type {
                     MeshComponent::VERTEX => self.make_face_vertex_incidence_map(&mut map),
-                    MeshComponent::EDGE => todo!(),
+                    MeshComponent::EDGE => Self::make_edges(&self.indices).make_incidence_map(origin_type, incident_type, &mut map),
                     MeshComponent::FACE => self.make_face_face_incidence_map(&mut map)
                 }
         }
@@ -327,6 +524,10 @@ impl<T: Vec3<U> + FloatVector<U>, U : Num + PartialOrd<U> + Float, IndexType : P
         return triangle_indices;
     }
 
+    fn get_face_count(&self) -> usize {
+        return self.indices.len() / 3;
+    }
+
     fn get_vertices(&self) -> &Vec<T> {
         return &self.vertices;
     }
@@ -341,6 +542,16 @@ mod unit_tests {
     use crate::mesh_components::MeshComponent;
     use crate::vector::Vec3f;
     use crate::vector::Vec3;
+    use crate::common_properties::AttributeMap;
+    use crate::common_properties::NormalMap;
+    use crate::property_map::FaceProperties;
+    use crate::property_map::EdgeProperties;
+    use crate::mesh_edges::MeshEdges;
+    use crate::property_map::PropertyHandle;
+    use crate::property_map::PropertyMap;
+    use crate::property_map::PropertyName;
+    use crate::property_map::PropertyType;
+    use crate::property_map::VertexProperties;
 
     fn create_unit_cube() -> TriangleVertexMesh<Vec3f, f32, u32> {
 
@@ -448,4 +659,85 @@ mod unit_tests {
         assert_eq!(uf0_incidence.len(), 3);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_named_properties() {
+
+        let mut ucube = create_unit_cube();
+
+        let curvature = PropertyHandle::<AttributeMap<f32>>::named("curvature");
+        let weights = PropertyHandle::<AttributeMap<f32>>::named("weights");
+        let labels = PropertyHandle::<AttributeMap<u32>>::of_type();
+
+        assert!(ucube.add_vertex_property(NormalMap::with_size(8, Vec3f::new(0.0, 0.0, 1.0))));
+        assert!(ucube.add_named_vertex_property(&curvature, AttributeMap::with_size(8, 0.5)));
+        assert!(ucube.add_named_vertex_property(&weights, AttributeMap::with_size(8, 1.0)));
+        assert!(!ucube.add_named_vertex_property(&weights, AttributeMap::with_size(8, 2.0)));
+        assert!(ucube.add_face_property(AttributeMap::from((0..12).collect::<Vec<u32>>())));
+
+        ucube.get_named_vertex_property(&weights).unwrap().set(3, 4.0);
+        assert_eq!(ucube.get_named_vertex_property_ref(&curvature).unwrap().get(3), 0.5);
+        assert_eq!(ucube.get_named_vertex_property_ref(&weights).unwrap().get(3), 4.0);
+        assert_eq!(ucube.get_named_face_property_ref(&labels).unwrap().get(11), 11);
+        assert!(ucube.get_vertex_property_ref::<NormalMap<Vec3f, f32>, Vec3f>(PropertyType::NORMAL).is_some());
+
+        // A handle with the right name, but the wrong type of map
+        let wrong_type = PropertyHandle::<AttributeMap<u32>>::named("weights");
+        assert!(ucube.get_named_vertex_property_ref(&wrong_type).is_none());
+
+        assert_eq!(ucube.get_vertex_properties().get_names().len(), 3);
+        assert!(ucube.remove_vertex_property(curvature.get_name()));
+        assert!(!ucube.remove_vertex_property(&PropertyName::NAMED("curvature".into())));
+        assert!(ucube.get_named_vertex_property_ref(&curvature).is_none());
+        assert!(ucube.get_vertex_properties().contains(&PropertyName::BUILTIN(PropertyType::NORMAL)));
+    }
+
+    #[test]
+    fn test_edge_properties() {
+
+        let mut ucube = create_unit_cube();
+        let edges = MeshEdges::from_mesh(&ucube);
+        assert_eq!(edges.get_edge_count(), 18);
+
+        let edge_faces = ucube.make_incidence_map(MeshComponent::EDGE, MeshComponent::FACE);
+        assert_eq!(edge_faces.len(), 18);
+        assert!(edge_faces.values().all(|faces| faces.len() == 2));
+        assert_eq!(ucube.make_incidence_map(MeshComponent::VERTEX, MeshComponent::EDGE)[&0].len(), 4);
+
+        let creases = PropertyHandle::<AttributeMap<bool>>::named("creases");
+        assert!(ucube.add_named_edge_property(&creases, AttributeMap::with_size(18, false)));
+        ucube.get_named_edge_property(&creases).unwrap().set(edges.find_edge(1, 3).unwrap(), true);
+
+        //Only the front and the right side are kept, which share the edge between vertex 1 and 3
+        let front_right = ucube.extract(&[0, 1, 2, 3, 5, 7], &[0, 1, 6, 7], &[0, 1, 2, 3, usize::MAX, 4, usize::MAX, 5]);
+        let new_edges = MeshEdges::from_mesh(&front_right);
+        let new_creases = front_right.get_named_edge_property_ref(&creases).unwrap();
+
+        assert_eq!(new_creases.len(), new_edges.get_edge_count());
+        for (e, [a, b]) in new_edges.get_edges().iter().enumerate() {
+            assert_eq!(new_creases.get(e), *a == 1 && *b == 3);
+        }
+    }
+
+    #[test]
+    fn test_replace_indices() {
+
+        let mut ucube = create_unit_cube();
+        let edges = MeshEdges::from_mesh(&ucube);
+
+        let vertex_pairs = PropertyHandle::<AttributeMap<[usize; 2]>>::named("vertex_pairs");
+        ucube.add_named_edge_property(&vertex_pairs, AttributeMap::from(edges.get_edges().to_vec()));
+
+        //Flipping the first triangle changes the order, in which its edges appear, and thus their ids
+        let mut indices = ucube.get_indices().clone();
+        indices.swap(1, 2);
+        ucube.replace_indices(indices);
+
+        let new_edges = MeshEdges::from_mesh(&ucube);
+        assert_ne!(new_edges.get_edges(), edges.get_edges());
+        let pairs = ucube.get_named_edge_property_ref(&vertex_pairs).unwrap();
+        for (e, edge) in new_edges.get_edges().iter().enumerate() {
+            assert_eq!(pairs.get(e), *edge);
+        }
+        assert_eq!(ucube.get_face(0), Vec::from([0, 3, 1]));
+    }
+}
@@ -753,7 +753,7 @@ impl Vec3<i32> for Vec3i {
 
     fn wedge(a : Self, b : Self) -> Self {
         return Vec3i::new(a.y() * b.z() - a.z() * b.y(),
-            a.x() * b.z() - a.z() * b.y(),
+            a.z() * b.x() - a.x() * b.z(),
             a.x() * b.y() - a.y() * b.x());
     }
 
@@ -889,7 +889,7 @@ impl Vec3<i64> for Vec3l {
 
     fn wedge(a : Self, b : Self) -> Self {
         return Vec3l::new(a.y() * b.z() - a.z() * b.y(),
-            a.x() * b.z() - a.z() * b.y(),
+            a.z() * b.x() - a.x() * b.z(),
             a.x() * b.y() - a.y() * b.x());
     }
 
@@ -1045,7 +1045,7 @@ impl Vec3<f32> for Vec3f {
 
     fn wedge(a : Self, b : Self) -> Self {
         return Vec3f::new(a.y() * b.z() - a.z() * b.y(),
-            a.x() * b.z() - a.z() * b.y(),
+            a.z() * b.x() - a.x() * b.z(),
             a.x() * b.y() - a.y() * b.x());
      }
 
@@ -1201,7 +1201,7 @@ impl Vec3<f64> for Vec3d {
 
     fn wedge(a : Self, b : Self) -> Self {
         return Vec3d::new(a.y() * b.z() - a.z() * b.y(),
-            a.x() * b.z() - a.z() * b.y(),
+            a.z() * b.x() - a.x() * b.z(),
             a.x() * b.y() - a.y() * b.x());
     }
 
@@ -1216,4 +1216,38 @@ impl Vec3<f64> for Vec3d {
 
 
 //TODO: Vector4
-//TODO: Template
\ No newline at end of file
+//TODO: Template
+
+#[cfg(test)]
+mod unit_tests {
+
+    use crate::vector::Vec3;
+    use crate::vector::Vec3d;
+    use crate::vector::Vec3f;
+    use crate::vector::Vec3i;
+    use crate::vector::Vec3l;
+
+    fn assert_cross_products<V : Vec3<T> + Copy, T : num_traits::Num + PartialOrd<T> + Copy + std::fmt::Debug>() {
+
+        let (o, l) = (T::zero(), T::one());
+        let (x, y, z) = (V::new(l, o, o), V::new(o, l, o), V::new(o, o, l));
+        let components = |v : V| [v.x(), v.y(), v.z()];
+
+        assert_eq!(components(V::cross(x, y)), components(z));
+        assert_eq!(components(V::cross(y, z)), components(x));
+        assert_eq!(components(V::cross(z, x)), components(y));
+        assert_eq!(components(V::wedge(z, x)), components(y));
+    }
+
+    #[test]
+    fn test_cross() {
+
+        assert_cross_products::<Vec3i, i32>();
+        assert_cross_products::<Vec3l, i64>();
+        assert_cross_products::<Vec3f, f32>();
+        assert_cross_products::<Vec3d, f64>();
+
+        let c = Vec3d::cross(Vec3d::new(1.0, 2.0, 3.0), Vec3d::new(4.0, 5.0, 6.0));
+        assert_eq!([c.x(), c.y(), c.z()], [-3.0, 6.0, -3.0]);
+    }
+}